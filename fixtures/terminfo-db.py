@@ -0,0 +1,229 @@
+"""Writes an ncurses hashed database in the hash format of Berkeley DB 1.85, for the tests of
+src/hashed.rs:
+
+    python3 fixtures/terminfo-db.py fixtures fixtures/terminfo.db
+
+The pages are filled like hash_page.c (__addel, putpair, __add_ovflpage) and hash_bigkey.c
+(__big_insert) of 4.4BSD do, with 64 byte pages so that entries span several pages. The
+database has four buckets and grew from two, so the bitmap page sits at split point 1 and the
+later overflow pages at split point 2, after the last bucket. Unlike __addel it doesn't squeeze
+pairs onto pages which already link to an overflow page.
+"""
+import struct, sys
+
+BSHIFT = 6
+BSIZE = 1 << BSHIFT
+HDRPAGES = ((512 - 1) >> BSHIFT) + 1
+MAX_BUCKET = 3
+HIGH_MASK, LOW_MASK = 7, 3
+SPLITSHIFT = 11
+OVFLPAGE, PARTIAL_KEY, FULL_KEY, FULL_KEY_DATA, REAL_KEY = 0, 1, 2, 3, 4
+BIGOVERHEAD = 8
+OVFLSIZE = 4
+
+
+def hash4(key):
+    h = 0
+    for c in key:
+        h = (h * 33 + c) & 0xFFFFFFFF
+    return h
+
+
+class Page:
+    def __init__(self):
+        self.b = bytearray(BSIZE)
+        self[0] = 0
+        self[1] = BSIZE - 6
+        self[2] = BSIZE
+
+    def __getitem__(self, i):
+        return struct.unpack_from("<H", self.b, i * 2)[0]
+
+    def __setitem__(self, i, v):
+        struct.pack_into("<H", self.b, i * 2, v & 0xFFFF)
+
+    def free(self):
+        return self[self[0] + 1]
+
+    def set_free(self, v):
+        self[self[0] + 1] = v
+
+    def offset(self):
+        return self[self[0] + 2]
+
+    def set_offset(self, v):
+        self[self[0] + 2] = v
+
+
+class Db:
+    def __init__(self):
+        self.buckets = [Page() for _ in range(MAX_BUCKET + 1)]
+        # Overflow pages at split point 2, keyed by their page offset.
+        self.ovfl = []
+        self.nkeys = 0
+
+    def bucket_page(self, bucket):
+        # spares[0] = 0 and spares[1] = 1, for the bitmap page at split point 1.
+        spares = 0 if bucket < 2 else 1
+        return bucket + HDRPAGES + spares
+
+    def add_ovflpage(self, page):
+        self.ovfl.append(Page())
+        addr = (2 << SPLITSHIFT) | len(self.ovfl)
+        ndx = page[0]
+        page[ndx + 4] = page.offset()
+        page[ndx + 3] = page.free() - OVFLSIZE
+        page[ndx + 1] = addr
+        page[ndx + 2] = OVFLPAGE
+        page[0] = ndx + 2
+        return self.ovfl[-1]
+
+    def next_page(self, page):
+        addr = page[page[0] - 1]
+        return self.ovfl[(addr & 0x7FF) - 1]
+
+    def insert(self, key, val):
+        self.nkeys += 1
+        bucket = hash4(key) & HIGH_MASK
+        if bucket > MAX_BUCKET:
+            bucket &= LOW_MASK
+        p = self.buckets[bucket]
+        while p[0] and (p[2] < REAL_KEY or p[p[0]] < REAL_KEY):
+            if p[2] == FULL_KEY_DATA and p[0] == 2:
+                break
+            elif p[2] < REAL_KEY and p[p[0]] != OVFLPAGE:
+                p = self.next_page(p)
+            elif p[p[0]] != OVFLPAGE:
+                break
+            else:
+                p = self.next_page(p)
+        if fits(p, key, val):
+            putpair(p, key, val)
+        else:
+            p = self.add_ovflpage(p)
+            if fits(p, key, val):
+                putpair(p, key, val)
+            else:
+                self.big_insert(p, key, val)
+
+    def big_insert(self, p, key, val):
+        key_data, val_data, val_size = key, val, len(val)
+        space = p.free() - BIGOVERHEAD
+        while key_data:
+            move = min(space, len(key_data))
+            off = p.offset() - move
+            p.b[off:off + move] = key_data[:move]
+            key_data = key_data[move:]
+            n = p[0] + 1
+            p[n] = off
+            n += 1
+            p[0] = n
+            p.set_free(off - (n + 3) * 2)
+            p.set_offset(off)
+            p[n] = PARTIAL_KEY
+            new = self.add_ovflpage(p)
+            n = p[0]
+            if not key_data:
+                space = p.free()
+                if space and not (space == val_size and val_size == len(val)):
+                    move = min(space, val_size)
+                    off = p.offset() - move
+                    p.b[off:off + move] = val_data[:move]
+                    val_data, val_size = val_data[move:], val_size - move
+                    p[n] = off
+                    p[n - 2] = FULL_KEY_DATA
+                    p.set_free(p.free() - move)
+                    p.set_offset(off)
+                else:
+                    p[n - 2] = FULL_KEY
+            p = new
+            space = p.free() - BIGOVERHEAD
+        while val_size:
+            move = min(space, val_size)
+            if space == val_size and val_size == len(val):
+                move -= 1
+            off = p.offset() - move
+            p.b[off:off + move] = val_data[:move]
+            val_data, val_size = val_data[move:], val_size - move
+            n = p[0] + 1
+            p[n] = off
+            n += 1
+            p[0] = n
+            p.set_free(off - (n + 3) * 2)
+            p.set_offset(off)
+            if val_size:
+                p[n] = FULL_KEY
+                p = self.add_ovflpage(p)
+            else:
+                p[n] = FULL_KEY_DATA
+            space = p.free() - BIGOVERHEAD
+
+    def write(self, path):
+        spares = [0, 1] + [1 + len(self.ovfl)] * 30
+        bitmaps = [(1 << SPLITSHIFT) | 1] + [0] * 31
+        header = struct.pack(
+            ">17I32I32H",
+            0x061561, 2, 1234, BSIZE, BSHIFT, 256, 256, 8, 2, 0, MAX_BUCKET, HIGH_MASK,
+            LOW_MASK, 8, self.nkeys, HDRPAGES, hash4(b"%$sniglet^&"), *spares, *bitmaps)
+        pages = [bytearray(BSIZE) for _ in range(HDRPAGES)]
+        data = bytearray(b"".join(pages))
+        data[:len(header)] = header
+        bitmap = bytearray(BSIZE)
+        bitmap[0] = 1
+        data += self.buckets[0].b + self.buckets[1].b + bitmap
+        data += self.buckets[2].b + self.buckets[3].b
+        for page in self.ovfl:
+            data += page.b
+        open(path, "wb").write(data)
+
+
+def fits(p, key, val):
+    return p[2] >= REAL_KEY and 4 + len(key) + len(val) + OVFLSIZE <= p.free()
+
+
+def putpair(p, key, val):
+    n = p[0]
+    off = p.offset() - len(key)
+    p.b[off:off + len(key)] = key
+    n += 1
+    p[n] = off
+    off -= len(val)
+    p.b[off:off + len(val)] = val
+    n += 1
+    p[n] = off
+    p[0] = n
+    p[n + 1] = off - (n + 3) * 2
+    p[n + 2] = off
+
+
+def with_names(entry, names):
+    """Returns the compiled entry with another names field."""
+    size, booleans = struct.unpack_from("<hh", entry, 2)
+    head = bytearray(entry[:12])
+    struct.pack_into("<h", head, 2, len(names) + 1)
+    # The numbers start on an even offset, after a padding byte if needed.
+    start = 12 + size + booleans
+    rest = entry[start + start % 2:]
+    booleans = entry[12 + size:start]
+    start = 12 + len(names) + 1 + len(booleans)
+    return bytes(head) + names + b"\0" + booleans + b"\0" * (start % 2) + rest
+
+
+def main(fixtures, out):
+    entries = [open(f"{fixtures}/{name}", "rb").read() for name in ("vt100", "dumb")]
+    # A names field of 100 bytes fills the first page and the next one, so that the key
+    # continues on a second page and the data starts on a third.
+    names = b"longname|longname-alias|"
+    names += b"x" * (100 - len(names) - 1) + b"."
+    entries.append(with_names(entries[1], names))
+    db = Db()
+    for entry in entries:
+        size = struct.unpack_from("<h", entry, 2)[0]
+        names = entry[12:12 + size - 1]
+        db.insert(names, b"\0" + entry)
+        for name in names.split(b"|")[:-1]:
+            db.insert(name, b"\2" + names)
+    db.write(out)
+
+
+main(*sys.argv[1:])
@@ -0,0 +1,189 @@
+//! The alternate character set of a terminal, used for drawing lines and boxes.
+
+use crate::cap::StringCap;
+use crate::term::Term;
+use std::collections::HashMap;
+
+/// A symbol of the alternate character set.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum AcsSymbol {
+    RightArrow,
+    LeftArrow,
+    UpArrow,
+    DownArrow,
+    Block,
+    Diamond,
+    Checkerboard,
+    Degree,
+    PlusMinus,
+    Board,
+    Lantern,
+    LowerRightCorner,
+    UpperRightCorner,
+    UpperLeftCorner,
+    LowerLeftCorner,
+    Plus,
+    ScanLine1,
+    ScanLine3,
+    HorizontalLine,
+    ScanLine7,
+    ScanLine9,
+    LeftTee,
+    RightTee,
+    BottomTee,
+    TopTee,
+    VerticalLine,
+    LessEqual,
+    GreaterEqual,
+    Pi,
+    NotEqual,
+    Sterling,
+    Bullet,
+}
+
+/// The symbols with the characters representing them in `acsc` and their Unicode equivalents.
+const SYMBOLS: [(AcsSymbol, u8, char); 32] = [
+    (AcsSymbol::RightArrow, b'+', '→'),
+    (AcsSymbol::LeftArrow, b',', '←'),
+    (AcsSymbol::UpArrow, b'-', '↑'),
+    (AcsSymbol::DownArrow, b'.', '↓'),
+    (AcsSymbol::Block, b'0', '█'),
+    (AcsSymbol::Diamond, b'`', '◆'),
+    (AcsSymbol::Checkerboard, b'a', '▒'),
+    (AcsSymbol::Degree, b'f', '°'),
+    (AcsSymbol::PlusMinus, b'g', '±'),
+    (AcsSymbol::Board, b'h', '░'),
+    (AcsSymbol::Lantern, b'i', '␋'),
+    (AcsSymbol::LowerRightCorner, b'j', '┘'),
+    (AcsSymbol::UpperRightCorner, b'k', '┐'),
+    (AcsSymbol::UpperLeftCorner, b'l', '┌'),
+    (AcsSymbol::LowerLeftCorner, b'm', '└'),
+    (AcsSymbol::Plus, b'n', '┼'),
+    (AcsSymbol::ScanLine1, b'o', '⎺'),
+    (AcsSymbol::ScanLine3, b'p', '⎻'),
+    (AcsSymbol::HorizontalLine, b'q', '─'),
+    (AcsSymbol::ScanLine7, b'r', '⎼'),
+    (AcsSymbol::ScanLine9, b's', '⎽'),
+    (AcsSymbol::LeftTee, b't', '├'),
+    (AcsSymbol::RightTee, b'u', '┤'),
+    (AcsSymbol::BottomTee, b'v', '┴'),
+    (AcsSymbol::TopTee, b'w', '┬'),
+    (AcsSymbol::VerticalLine, b'x', '│'),
+    (AcsSymbol::LessEqual, b'y', '≤'),
+    (AcsSymbol::GreaterEqual, b'z', '≥'),
+    (AcsSymbol::Pi, b'{', 'π'),
+    (AcsSymbol::NotEqual, b'|', '≠'),
+    (AcsSymbol::Sterling, b'}', '£'),
+    (AcsSymbol::Bullet, b'~', '·'),
+];
+
+impl AcsSymbol {
+    /// Returns the symbol represented by the character in `acsc`, which is the character that
+    /// draws it on a VT100 in line drawing mode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unibilium::acs::AcsSymbol;
+    ///
+    /// assert_eq!(AcsSymbol::from_byte(b'q'), Some(AcsSymbol::HorizontalLine));
+    /// assert_eq!(AcsSymbol::from_byte(b'A'), None);
+    /// ```
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        SYMBOLS
+            .iter()
+            .find(|(_, b, _)| *b == byte)
+            .map(|(symbol, _, _)| *symbol)
+    }
+
+    /// Returns the character representing the symbol in `acsc`.
+    pub fn to_byte(self) -> u8 {
+        SYMBOLS
+            .iter()
+            .find(|(symbol, _, _)| *symbol == self)
+            .map(|(_, b, _)| *b)
+            .expect("Every symbol is in the table")
+    }
+
+    /// Returns the Unicode character drawing the symbol, for terminals without an alternate
+    /// character set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unibilium::acs::AcsSymbol;
+    ///
+    /// assert_eq!(AcsSymbol::UpperLeftCorner.to_unicode(), '┌');
+    /// ```
+    pub fn to_unicode(self) -> char {
+        SYMBOLS
+            .iter()
+            .find(|(symbol, _, _)| *symbol == self)
+            .map(|(_, _, c)| *c)
+            .expect("Every symbol is in the table")
+    }
+}
+
+impl Term {
+    /// Returns the symbols of the alternate character set of the terminal, mapped to the bytes
+    /// drawing them between enter_acs and exit_acs. The map is parsed from `acsc`, which lists
+    /// pairs of the symbol and the byte drawing it; unknown symbols are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::acs::AcsSymbol;
+    /// use unibilium::Term;
+    ///
+    /// let acs = Term::from_term_name("vt100")?.acs();
+    /// assert_eq!(acs.get(&AcsSymbol::HorizontalLine), Some(&b'q'));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn acs(&self) -> HashMap<AcsSymbol, u8> {
+        let acsc = match self.string_cstr(StringCap::AcsChars) {
+            Some(acsc) => acsc.to_bytes(),
+            None => return HashMap::new(),
+        };
+        acsc.chunks_exact(2)
+            .filter_map(|pair| Some((AcsSymbol::from_byte(pair[0])?, pair[1])))
+            .collect()
+    }
+
+    /// Returns the sequence which must be sent once before using the alternate character set
+    /// (`enacs`), or None if the terminal doesn't need it.
+    pub fn enable_acs(&self) -> Option<Vec<u8>> {
+        self.expand(StringCap::EnaAcs, &[])
+    }
+
+    /// Returns the sequence starting the alternate character set (`smacs`), or None if the
+    /// terminal doesn't have one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert_eq!(term.enter_acs(), Some(b"\x0e".to_vec()));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn enter_acs(&self) -> Option<Vec<u8>> {
+        self.expand(StringCap::EnterAltCharsetMode, &[])
+    }
+
+    /// Returns the sequence ending the alternate character set (`rmacs`), or None if the
+    /// terminal doesn't have one.
+    pub fn exit_acs(&self) -> Option<Vec<u8>> {
+        self.expand(StringCap::ExitAltCharsetMode, &[])
+    }
+}
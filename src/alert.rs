@@ -0,0 +1,51 @@
+//! Alerting the user with the bell or by flashing the screen.
+
+use crate::cap::StringCap;
+use crate::term::Term;
+
+/// The kind of alert the caller prefers. The other kind is used if the terminal can't do the
+/// preferred one.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum AlertKind {
+    /// The audible bell (`bel`).
+    Audible,
+    /// The visible bell, flashing the screen (`flash`), e.g. for users who can't hear the bell.
+    Visual,
+}
+
+impl Term {
+    /// Returns the sequence alerting the user in the preferred way, or in the other way if the
+    /// terminal can't, or None if it can't do either. Padding directives are dropped; run_events
+    /// keeps the delay many terminals need between turning the flash on and off.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::alert::AlertKind;
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("xterm")?;
+    /// assert_eq!(term.alert(AlertKind::Audible), Some(b"\x07".to_vec()));
+    /// assert_eq!(
+    ///     term.alert(AlertKind::Visual),
+    ///     Some(b"\x1b[?5h\x1b[?5l".to_vec())
+    /// );
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert_eq!(term.alert(AlertKind::Visual), Some(b"\x07".to_vec()));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn alert(&self, kind: AlertKind) -> Option<Vec<u8>> {
+        let (preferred, fallback) = match kind {
+            AlertKind::Audible => (StringCap::Bell, StringCap::FlashScreen),
+            AlertKind::Visual => (StringCap::FlashScreen, StringCap::Bell),
+        };
+        self.expand(preferred, &[])
+            .or_else(|| self.expand(fallback, &[]))
+    }
+}
@@ -0,0 +1,112 @@
+//! Strategies generating random but structurally valid terminals for property tests with
+//! `proptest`, enabled by the `proptest` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use proptest::test_runner::{Config, TestRunner};
+//! use unibilium::arbitrary;
+//! use unibilium::lint::{self, Problem};
+//!
+//! let mut runner = TestRunner::new(Config::with_cases(16));
+//! runner.run(&arbitrary::term(), |term| {
+//!     let problems = lint::check(&term);
+//!     assert!(!problems
+//!         .iter()
+//!         .any(|problem| matches!(problem, Problem::MalformedParam { .. })));
+//!     Ok(())
+//! })?;
+//! #
+//! #    Ok(())
+//! # }
+//! ```
+
+use crate::cap::{BooleanCap, NumericCap, StringCap};
+use crate::term::Term;
+use crate::testing::MockTerm;
+use proptest::collection::vec;
+use proptest::prelude::*;
+use proptest::sample::{select, subsequence};
+
+/// Generates well-formed parameterized strings: printable text mixed with `%` operations,
+/// including arithmetic and nested conditionals, which never underflow the stack.
+pub fn param_string() -> impl Strategy<Value = Vec<u8>> {
+    let text = vec(
+        (0x20u8..0x7f).prop_filter("starts an escape or a delay", |c| *c != b'%' && *c != b'$'),
+        1..4,
+    );
+    let op = select(&b"+-*/m&|^=<>AO"[..]);
+    let binary = (1u8..=9, 1u8..=9, op)
+        .prop_map(|(a, b, op)| format!("%p{}%p{}%{}%d", a, b, char::from(op)).into_bytes());
+    let leaf = prop_oneof![
+        text,
+        Just(b"%%".to_vec()),
+        Just(b"%i".to_vec()),
+        (1u8..=9).prop_map(|n| format!("%p{}%d", n).into_bytes()),
+        (0i32..1000).prop_map(|n| format!("%{{{}}}%d", n).into_bytes()),
+        binary,
+    ];
+    leaf.prop_recursive(3, 24, 4, |inner| {
+        prop_oneof![
+            vec(inner.clone(), 1..4).prop_map(|parts| parts.concat()),
+            (1u8..=9, inner.clone(), inner).prop_map(|(n, then, otherwise)| {
+                let mut out = format!("%?%p{}%t", n).into_bytes();
+                out.extend(then);
+                out.extend(b"%e");
+                out.extend(otherwise);
+                out.extend(b"%;");
+                out
+            }),
+        ]
+    })
+}
+
+/// Generates names of extended capabilities which don't clash with standard ones.
+fn ext_name() -> impl Strategy<Value = String> {
+    vec(b'a'..=b'z', 1..6).prop_map(|name| format!("X{}", String::from_utf8_lossy(&name)))
+}
+
+/// Generates MockTerms with random standard and extended capabilities. String capabilities
+/// hold values generated by param_string.
+pub fn mock_term() -> impl Strategy<Value = MockTerm> {
+    (
+        subsequence(BooleanCap::ALL.to_vec(), 0..=BooleanCap::ALL.len()),
+        vec((select(NumericCap::ALL.to_vec()), 0i32..=1000), 0..16),
+        vec((select(StringCap::ALL.to_vec()), param_string()), 0..32),
+        vec(ext_name(), 0..4),
+        vec((ext_name(), 0i32..=1000), 0..4),
+        vec((ext_name(), param_string()), 0..4),
+    )
+        .prop_map(
+            |(booleans, numerics, strings, ext_booleans, ext_numerics, ext_strings)| {
+                let mut term = MockTerm::new("proptest");
+                for cap in booleans {
+                    term = term.bool(cap.short_name(), true);
+                }
+                for (cap, value) in numerics {
+                    term = term.num(cap.short_name(), value);
+                }
+                for (cap, value) in strings {
+                    term = term.string(cap.short_name(), value);
+                }
+                for name in ext_booleans {
+                    term = term.bool(&name, true);
+                }
+                for (name, value) in ext_numerics {
+                    term = term.num(&name, value);
+                }
+                for (name, value) in ext_strings {
+                    term = term.string(&name, value);
+                }
+                term
+            },
+        )
+}
+
+/// Generates terminals built from mock_term.
+pub fn term() -> impl Strategy<Value = Term> {
+    mock_term().prop_map(|term| term.build().expect("Failed to build a generated terminal"))
+}
@@ -0,0 +1,206 @@
+//! Text attributes and the sequences selecting them on a terminal.
+
+use crate::cap::StringCap;
+use crate::color::{self, Color};
+use crate::format::Param;
+use crate::term::Term;
+use std::ops::{BitAnd, BitOr, BitOrAssign, Sub};
+
+/// A set of text attributes.
+///
+/// # Examples
+///
+/// ```
+/// use unibilium::attributes::Attributes;
+///
+/// let attrs = Attributes::BOLD | Attributes::UNDERLINE;
+/// assert!(attrs.contains(Attributes::BOLD));
+/// assert!(!attrs.contains(Attributes::BOLD | Attributes::REVERSE));
+/// ```
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Attributes(u8);
+
+impl Attributes {
+    pub const BOLD: Attributes = Attributes(1);
+    pub const UNDERLINE: Attributes = Attributes(1 << 1);
+    pub const REVERSE: Attributes = Attributes(1 << 2);
+    pub const DIM: Attributes = Attributes(1 << 3);
+    pub const ITALIC: Attributes = Attributes(1 << 4);
+    pub const BLINK: Attributes = Attributes(1 << 5);
+
+    /// Returns the empty set of attributes.
+    pub fn empty() -> Self {
+        Attributes(0)
+    }
+
+    /// Returns the set of all attributes.
+    pub fn all() -> Self {
+        Attributes(0b11_1111)
+    }
+
+    /// Returns true if no attribute is set.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns true if all attributes of other are set.
+    pub fn contains(self, other: Attributes) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for Attributes {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Attributes(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for Attributes {
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl BitAnd for Attributes {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        Attributes(self.0 & other.0)
+    }
+}
+
+impl Sub for Attributes {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Attributes(self.0 & !other.0)
+    }
+}
+
+/// An underline style, as selected by the `Smulx` extended capability.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum UnderlineStyle {
+    None,
+    Single,
+    Double,
+    Curly,
+    Dotted,
+    Dashed,
+}
+
+impl UnderlineStyle {
+    /// Returns the parameter of `Smulx` for the style.
+    fn to_param(self) -> i32 {
+        match self {
+            UnderlineStyle::None => 0,
+            UnderlineStyle::Single => 1,
+            UnderlineStyle::Double => 2,
+            UnderlineStyle::Curly => 3,
+            UnderlineStyle::Dotted => 4,
+            UnderlineStyle::Dashed => 5,
+        }
+    }
+}
+
+impl Term {
+    /// Returns the sequence turning off all attributes and then turning on the given ones.
+    /// Attributes the terminal doesn't support are left out.
+    ///
+    /// Uses `sgr` when available, followed by `sitm` for italic, which `sgr` can't set.
+    /// Otherwise all attributes are turned off with `sgr0` and turned on with their individual
+    /// capabilities, like `bold` or `smul`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::attributes::Attributes;
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("xterm")?;
+    /// assert_eq!(
+    ///     term.style(Attributes::BOLD | Attributes::UNDERLINE),
+    ///     b"\x1b(B\x1b[0;1;4m".to_vec()
+    /// );
+    /// assert_eq!(term.style(Attributes::empty()), b"\x1b(B\x1b[m".to_vec());
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn style(&self, attrs: Attributes) -> Vec<u8> {
+        if attrs.is_empty() {
+            if let Some(sgr0) = self.expand(StringCap::ExitAttributeMode, &[]) {
+                return sgr0;
+            }
+        }
+        let flag = |attr| Param::num(attrs.contains(attr) as i32);
+        let params = [
+            Param::num(0),
+            flag(Attributes::UNDERLINE),
+            flag(Attributes::REVERSE),
+            flag(Attributes::BLINK),
+            flag(Attributes::DIM),
+            flag(Attributes::BOLD),
+            Param::num(0),
+            Param::num(0),
+            Param::num(0),
+        ];
+        let (mut out, rest) = match self.expand(StringCap::SetAttributes, &params) {
+            Some(sgr) => (sgr, attrs & Attributes::ITALIC),
+            None => (
+                self.expand(StringCap::ExitAttributeMode, &[])
+                    .unwrap_or_default(),
+                attrs,
+            ),
+        };
+        let individual = [
+            (Attributes::BOLD, StringCap::EnterBoldMode),
+            (Attributes::UNDERLINE, StringCap::EnterUnderlineMode),
+            (Attributes::REVERSE, StringCap::EnterReverseMode),
+            (Attributes::DIM, StringCap::EnterDimMode),
+            (Attributes::ITALIC, StringCap::EnterItalicsMode),
+            (Attributes::BLINK, StringCap::EnterBlinkMode),
+        ];
+        for (attr, cap) in individual.iter() {
+            if rest.contains(*attr) {
+                if let Some(seq) = self.expand(*cap, &[]) {
+                    out.extend(seq);
+                }
+            }
+        }
+        out
+    }
+
+    /// Returns the sequence selecting the underline style with the `Smulx` extended
+    /// capability, or None if the terminal doesn't define it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::attributes::UnderlineStyle;
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert_eq!(term.set_underline_style(UnderlineStyle::Curly), None);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn set_underline_style(&self, style: UnderlineStyle) -> Option<Vec<u8>> {
+        self.expand_ext("Smulx", &[Param::num(style.to_param())])
+    }
+
+    /// Returns the sequence setting the underline color with the `Setulc` extended capability,
+    /// or None if the terminal doesn't define it. `Setulc` takes an RGB value, so indexed colors
+    /// are converted using the default xterm palette.
+    pub fn set_underline_color(&self, color: Color) -> Option<Vec<u8>> {
+        self.expand_ext("Setulc", &[Param::num(color::pack(color.to_rgb()))])
+    }
+}
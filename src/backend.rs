@@ -0,0 +1,55 @@
+//! The storage a Term reads its capabilities from.
+//!
+//! A Term is backed by unibilium, or with the `pure-rust` feature and on WebAssembly by an Entry
+//! parsed in Rust. The capability views only read through the Backend trait, so adding a backend
+//! doesn't touch them. There is no separate backend for test doubles: terms built in memory, like
+//! those of MockTerm and the fixtures, are written as an Entry in the compiled format and loaded
+//! into the backend of the build like an entry of the database.
+
+use crate::cap::{BooleanCap, NumericCap, StringCap};
+use std::ffi::CStr;
+
+/// Read access to the names and the capabilities of a terminal entry.
+///
+/// Extended capabilities are addressed by their index, which is below the count of their kind.
+/// Invalid indexes yield None, false or -1 rather than panicking.
+pub(crate) trait Backend: Send + Sync {
+    /// Returns the name of the terminal, which is the last field of the names section.
+    fn name(&self) -> &CStr;
+
+    /// Returns the other names of the terminal, in the order of the names section.
+    fn aliases(&self) -> Vec<&CStr>;
+
+    /// Returns whether the standard boolean capability is set.
+    fn boolean(&self, cap: BooleanCap) -> bool;
+
+    /// Returns the value of the standard numeric capability, -1 if it is absent and -2 if it is
+    /// cancelled.
+    fn numeric(&self, cap: NumericCap) -> i32;
+
+    /// Returns the value of the standard string capability, or None if it is absent or
+    /// cancelled.
+    fn string(&self, cap: StringCap) -> Option<&CStr>;
+
+    fn ext_boolean_count(&self) -> u64;
+
+    fn ext_boolean_name(&self, index: u64) -> Option<&CStr>;
+
+    fn ext_boolean(&self, index: u64) -> bool;
+
+    fn ext_numeric_count(&self) -> u64;
+
+    fn ext_numeric_name(&self, index: u64) -> Option<&CStr>;
+
+    fn ext_numeric(&self, index: u64) -> i32;
+
+    fn ext_string_count(&self) -> u64;
+
+    fn ext_string_name(&self, index: u64) -> Option<&CStr>;
+
+    fn ext_string(&self, index: u64) -> Option<&CStr>;
+
+    /// Writes the entry in the compiled terminfo format, or returns None if it can't be
+    /// represented in that format.
+    fn dump(&self) -> Option<Vec<u8>>;
+}
@@ -0,0 +1,150 @@
+//! The layout of entries in the compiled terminfo format.
+
+use crate::term::Term;
+
+/// Magic number of the legacy format, storing numbers in 16 bits.
+pub const MAGIC_16BIT: u16 = 0o432;
+
+/// Magic number of the extended number format, storing numbers in 32 bits.
+pub const MAGIC_32BIT: u16 = 0o1036;
+
+/// The sizes of the extended section of a compiled entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExtendedInfo {
+    pub booleans: usize,
+    pub numerics: usize,
+    pub strings: usize,
+    /// Size of the string table in bytes, holding both the values and the names of the
+    /// extended capabilities.
+    pub string_table_size: usize,
+}
+
+/// The header and section sizes of a compiled entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BinaryInfo {
+    /// MAGIC_16BIT or MAGIC_32BIT.
+    pub magic: u16,
+    /// Size of the whole entry in bytes.
+    pub size: usize,
+    /// Size of the names section in bytes, including the terminating NUL.
+    pub names_size: usize,
+    pub booleans: usize,
+    pub numerics: usize,
+    pub strings: usize,
+    /// Size of the string table in bytes.
+    pub string_table_size: usize,
+    /// The extended section, if present.
+    pub extended: Option<ExtendedInfo>,
+}
+
+impl BinaryInfo {
+    /// Returns the width of numbers in bytes, 2 or 4.
+    pub fn numeric_width(&self) -> usize {
+        if self.magic == MAGIC_32BIT {
+            4
+        } else {
+            2
+        }
+    }
+
+    /// Reads the header of a compiled entry, e.g. a file of the terminfo database. Returns None
+    /// if the magic number is unknown or the data is shorter than the header announces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs;
+    /// use unibilium::binary::{self, BinaryInfo};
+    ///
+    /// for path in ["/usr/share/terminfo/v/vt100", "/lib/terminfo/v/vt100"].iter() {
+    ///     if let Ok(data) = fs::read(path) {
+    ///         let info = BinaryInfo::parse(&data).unwrap();
+    ///         assert_eq!(info.magic, binary::MAGIC_16BIT);
+    ///         assert_eq!(info.size, data.len());
+    ///     }
+    /// }
+    /// assert_eq!(BinaryInfo::parse(b"not terminfo"), None);
+    /// ```
+    pub fn parse(data: &[u8]) -> Option<BinaryInfo> {
+        let word = |offset: usize| word(data, offset);
+        let mut info = BinaryInfo::parse_header(data)?;
+        let mut end = info.legacy_size();
+        if end > data.len() {
+            return None;
+        }
+        end += end % 2;
+        if end + 10 <= data.len() {
+            info.extended = Some(ExtendedInfo {
+                booleans: word(end)?,
+                numerics: word(end + 2)?,
+                strings: word(end + 4)?,
+                string_table_size: word(end + 8)?,
+            });
+        }
+        Some(info)
+    }
+
+    /// Reads the header fields, without checking that the data holds the sections they
+    /// announce. Returns None if the header is truncated or the magic number is unknown.
+    pub(crate) fn parse_header(data: &[u8]) -> Option<BinaryInfo> {
+        let word = |offset: usize| word(data, offset);
+        let magic = word(0)? as u16;
+        if magic != MAGIC_16BIT && magic != MAGIC_32BIT {
+            return None;
+        }
+        Some(BinaryInfo {
+            magic,
+            size: data.len(),
+            names_size: word(2)?,
+            booleans: word(4)?,
+            numerics: word(6)?,
+            strings: word(8)?,
+            string_table_size: word(10)?,
+            extended: None,
+        })
+    }
+
+    /// Returns the size of the legacy sections, up to the end of the string table.
+    pub(crate) fn legacy_size(&self) -> usize {
+        let mut end = 12 + self.names_size + self.booleans;
+        end += end % 2;
+        end + self.numerics * self.numeric_width() + self.strings * 2 + self.string_table_size
+    }
+}
+
+/// Reads the little endian word at the offset, treating negative values as 0.
+fn word(data: &[u8], offset: usize) -> Option<usize> {
+    let bytes = data.get(offset..offset + 2)?;
+    let value = i16::from_le_bytes([bytes[0], bytes[1]]);
+    Some(value.max(0) as usize)
+}
+
+impl Term {
+    /// Returns the header and section sizes of the terminal in the compiled format, or None if
+    /// unibilium can't represent it in that format.
+    ///
+    /// The information describes the entry as unibilium writes it, which uses the legacy
+    /// format unless a number needs 32 bits. It can differ from the file the terminal was
+    /// loaded from; use BinaryInfo::parse to inspect files.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::binary;
+    /// use unibilium::Term;
+    ///
+    /// let info = Term::from_term_name("vt100")?.binary_info().unwrap();
+    /// assert_eq!(info.magic, binary::MAGIC_16BIT);
+    /// assert_eq!(info.numeric_width(), 2);
+    /// assert!(info.strings > 0);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn binary_info(&self) -> Option<BinaryInfo> {
+        BinaryInfo::parse(&self.dump()?)
+    }
+}
@@ -1,57 +1,102 @@
+use crate::cap::BooleanCap;
+use crate::error::CapError;
 use crate::Term;
-use std::ffi::CStr;
+use std::borrow::Cow;
 use std::fmt;
-use unibilium_sys::unibi_boolean;
 
 /// Represents boolean terminal capability.
-#[derive(Debug)]
 pub struct Boolean<'a> {
-    boolean: unibi_boolean,
+    cap: BooleanCap,
     term: &'a Term,
 }
 
 impl<'a> Boolean<'a> {
-    /// Creates a Boolean from lower level unibi_boolean struct. It's intended for internal
-    /// use.
-    ///
-    /// # Warning
-    ///
-    /// For efficiency reasons it expects the caller to make sure that the boolean is valid.
-    pub(crate) fn from_unibi_bool_unchecked(boolean: unibi_boolean, term: &'a Term) -> Self {
-        Boolean { boolean, term }
+    /// Creates a Boolean for the standard capability. It's intended for internal use.
+    pub(crate) fn from_cap(cap: BooleanCap, term: &'a Term) -> Self {
+        Boolean { cap, term }
     }
 
     /// Returns name of the capability.
-    ///
-    /// # Panics
-    ///
-    /// Panics if it internally encounters invalid UTF-8 characters.
     pub fn name(&self) -> &str {
-        // Returns static string if called with value between begin and end.
-        let name = unsafe { unibilium_sys::unibi_name_bool(self.boolean) };
-        if name.is_null() {
-            panic!("Invalid unibi_bool value: {}", self.boolean);
-        }
-        let name = unsafe { CStr::from_ptr(name) };
-        name.to_str().expect("Invalid UTF-8 string encountered")
+        self.cap.name()
+    }
+
+    /// Returns the name of the capability like name, but returns an error instead of panicking.
+    pub fn try_name(&self) -> Result<&str, CapError> {
+        Ok(self.cap.name())
+    }
+
+    /// Returns the name of the capability, replacing invalid UTF-8 sequences with
+    /// U+FFFD REPLACEMENT CHARACTER instead of panicking.
+    pub fn name_lossy(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.cap.name())
+    }
+
+    /// Returns the short terminfo name of the capability.
+    pub fn short_name(&self) -> &str {
+        self.cap.short_name()
+    }
+
+    /// Returns the short terminfo name of the capability like short_name, but returns an error
+    /// instead of panicking.
+    pub fn try_short_name(&self) -> Result<&str, CapError> {
+        Ok(self.cap.short_name())
     }
 
     /// Returns whether the associated terminal supports this capability.
     pub fn supported(&self) -> bool {
-        let result = unsafe { unibilium_sys::unibi_get_bool(self.term.unibi_term(), self.boolean) };
-        result > 0
+        self.term.has_boolean(self.cap)
+    }
+
+    /// Copies the names and the value of the capability into a BooleanValue, which doesn't
+    /// borrow the Term.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let am = Term::from_term_name("vt100")?
+    ///     .booleans()
+    ///     .find(|b| b.short_name() == "am")
+    ///     .unwrap()
+    ///     .to_owned();
+    /// assert_eq!(am.name, "auto_right_margin");
+    /// assert!(am.value);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn to_owned(&self) -> BooleanValue {
+        BooleanValue {
+            name: self.name_lossy().into_owned(),
+            short_name: self.try_short_name().unwrap_or_default().to_owned(),
+            value: self.supported(),
+        }
+    }
+}
+
+impl<'a> fmt::Debug for Boolean<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Boolean")
+            .field("name", &self.name_lossy())
+            .field("short_name", &self.try_short_name().unwrap_or_default())
+            .field("value", &self.supported())
+            .finish()
     }
 }
 
 impl<'a> fmt::Display for Boolean<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}: {}", self.name(), self.supported())
+        write!(f, "{}: {}", self.name_lossy(), self.supported())
     }
 }
 
 /// Represents extended boolean capabilities. Each terminal can support custom boolean
 /// capabilities.
-#[derive(Debug)]
 pub struct ExtBoolean<'a> {
     index: u64,
     term: &'a Term,
@@ -63,7 +108,7 @@ impl<'a> ExtBoolean<'a> {
     /// # Warning
     ///
     /// For efficiency reasons it expects the caller to make sure that the index is valid, i.e.
-    /// less than unibilium_sys::unibi_count_ext_bool.
+    /// less than the number of extended boolean capabilities of the terminal.
     pub(crate) fn from_index_unchecked(index: u64, term: &'a Term) -> Self {
         ExtBoolean { index, term }
     }
@@ -74,26 +119,79 @@ impl<'a> ExtBoolean<'a> {
     ///
     /// Panics if it internally encounters invalid UTF-8 characters.
     pub fn name(&self) -> &str {
-        // Returns static string if called with value between 0 and count
-        let name =
-            unsafe { unibilium_sys::unibi_get_ext_bool_name(self.term.unibi_term(), self.index) };
-        if name.is_null() {
-            panic!("Invalid index for extended bool capability: {}", self.index);
-        }
-        let name = unsafe { CStr::from_ptr(name) };
+        let name = self
+            .term
+            .backend()
+            .ext_boolean_name(self.index)
+            .unwrap_or_else(|| {
+                panic!("Invalid index for extended bool capability: {}", self.index)
+            });
         name.to_str().expect("Invalid UTF-8 string encountered")
     }
 
+    /// Returns the name of the capability like name, but returns an error instead of panicking.
+    pub fn try_name(&self) -> Result<&str, CapError> {
+        let name = self
+            .term
+            .backend()
+            .ext_boolean_name(self.index)
+            .ok_or(CapError::InvalidIndex(self.index))?;
+        name.to_str().map_err(CapError::NotUnicode)
+    }
+
+    /// Returns the name of the capability, replacing invalid UTF-8 sequences with
+    /// U+FFFD REPLACEMENT CHARACTER instead of panicking.
+    pub fn name_lossy(&self) -> Cow<'_, str> {
+        match self.term.backend().ext_boolean_name(self.index) {
+            Some(name) => name.to_string_lossy(),
+            None => Cow::Borrowed(""),
+        }
+    }
+
     /// Returns whether the associated terminal supports this capability.
     pub fn supported(&self) -> bool {
-        let result =
-            unsafe { unibilium_sys::unibi_get_ext_bool(self.term.unibi_term(), self.index) };
-        result > 0
+        self.term.backend().ext_boolean(self.index)
+    }
+
+    /// Copies the name and the value of the capability into a BooleanValue, which doesn't
+    /// borrow the Term. Extended capabilities only have a short name, which is used for both
+    /// names.
+    pub fn to_owned(&self) -> BooleanValue {
+        let name = self.name_lossy().into_owned();
+        BooleanValue {
+            short_name: name.clone(),
+            name,
+            value: self.supported(),
+        }
+    }
+}
+
+impl<'a> fmt::Debug for ExtBoolean<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtBoolean")
+            .field("name", &self.name_lossy())
+            .field("value", &self.supported())
+            .finish()
     }
 }
 
 impl<'a> fmt::Display for ExtBoolean<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}: {}", self.name(), self.supported())
+        write!(f, "{}: {}", self.name_lossy(), self.supported())
+    }
+}
+
+/// The names and the value of a boolean capability, standard or extended, owned rather than
+/// borrowed from a Term.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct BooleanValue {
+    pub name: String,
+    pub short_name: String,
+    pub value: bool,
+}
+
+impl fmt::Display for BooleanValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.value)
     }
 }
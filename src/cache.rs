@@ -0,0 +1,161 @@
+//! A snapshot of the capabilities of a terminal in Rust-owned storage.
+
+use crate::cap::{BooleanCap, NumericCap, StringCap};
+use crate::entry::Entry;
+use crate::error::TermError;
+use crate::term::Term;
+use std::collections::{HashMap, HashSet};
+use std::ffi::{CStr, CString};
+
+/// The values of all capabilities of a terminal, copied out of the C library. Queries are plain
+/// hash map lookups, which makes it suited for hot paths like render loops.
+///
+/// Only defined capabilities are kept, so absent and cancelled capabilities both read as
+/// missing, like with the methods of Term.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct CachedTerm {
+    pub(crate) booleans: HashSet<BooleanCap>,
+    pub(crate) numerics: HashMap<NumericCap, i32>,
+    pub(crate) strings: HashMap<StringCap, Vec<u8>>,
+    pub(crate) ext_booleans: HashSet<String>,
+    pub(crate) ext_numerics: HashMap<String, i32>,
+    pub(crate) ext_strings: HashMap<String, Vec<u8>>,
+}
+
+impl CachedTerm {
+    /// Returns whether the boolean capability is set.
+    pub fn has_boolean(&self, cap: BooleanCap) -> bool {
+        self.booleans.contains(&cap)
+    }
+
+    /// Returns the value of the numeric capability, or None if the terminal doesn't define it.
+    pub fn numeric(&self, cap: NumericCap) -> Option<i32> {
+        self.numerics.get(&cap).copied()
+    }
+
+    /// Returns the value of the string capability, or None if the terminal doesn't define it.
+    pub fn string(&self, cap: StringCap) -> Option<&[u8]> {
+        self.strings.get(&cap).map(Vec::as_slice)
+    }
+
+    /// Returns whether the extended boolean capability with the given name is set.
+    pub fn ext_boolean(&self, name: &str) -> bool {
+        self.ext_booleans.contains(name)
+    }
+
+    /// Returns the value of the extended numeric capability with the given name, or None if
+    /// the terminal doesn't define it.
+    pub fn ext_numeric(&self, name: &str) -> Option<i32> {
+        self.ext_numerics.get(name).copied()
+    }
+
+    /// Returns the value of the extended string capability with the given name, or None if the
+    /// terminal doesn't define it.
+    pub fn ext_string(&self, name: &str) -> Option<&[u8]> {
+        self.ext_strings.get(name).map(Vec::as_slice)
+    }
+
+    /// Creates a Term with the capabilities of the cache and the given name and aliases. Values
+    /// containing NUL are left out, since unibilium can't represent them. It is intended for
+    /// internal use.
+    pub(crate) fn to_term(&self, name: &CStr, aliases: &[&CStr]) -> Result<Term, TermError> {
+        let cstring = |bytes: &[u8]| CString::new(bytes).ok();
+        let aliases = aliases.iter().map(|alias| CStr::to_owned(alias)).collect();
+        let mut entry = Entry::new(name.to_owned(), aliases);
+        for cap in self.booleans.iter() {
+            entry.set_boolean(*cap, true);
+        }
+        for (cap, value) in self.numerics.iter() {
+            entry.set_numeric(*cap, *value);
+        }
+        for (cap, value) in self.strings.iter() {
+            if let Some(value) = cstring(value) {
+                entry.set_string(*cap, value);
+            }
+        }
+        // Extended capabilities are added in the order of their names, so equal caches give
+        // equal terminals.
+        let mut ext_booleans: Vec<CString> = self
+            .ext_booleans
+            .iter()
+            .filter_map(|name| cstring(name.as_bytes()))
+            .collect();
+        ext_booleans.sort();
+        for name in ext_booleans {
+            entry.add_ext_boolean(name, true);
+        }
+        let mut ext_numerics: Vec<(CString, i32)> = self
+            .ext_numerics
+            .iter()
+            .filter_map(|(name, value)| Some((cstring(name.as_bytes())?, *value)))
+            .collect();
+        ext_numerics.sort();
+        for (name, value) in ext_numerics {
+            entry.add_ext_numeric(name, value);
+        }
+        let mut ext_strings: Vec<(CString, CString)> = self
+            .ext_strings
+            .iter()
+            .filter_map(|(name, value)| Some((cstring(name.as_bytes())?, cstring(value)?)))
+            .collect();
+        ext_strings.sort();
+        for (name, value) in ext_strings {
+            entry.add_ext_string(name, value);
+        }
+        Term::from_entry(&entry)
+    }
+}
+
+impl Term {
+    /// Copies the values of all capabilities into a CachedTerm, which answers queries without
+    /// calling into the C library.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::{BooleanCap, NumericCap, StringCap, Term};
+    ///
+    /// let cache = Term::from_term_name("vt100")?.cache();
+    /// assert!(cache.has_boolean(BooleanCap::AutoRightMargin));
+    /// assert_eq!(cache.numeric(NumericCap::Columns), Some(80));
+    /// assert_eq!(cache.string(StringCap::CursorUp), Some(&b"\x1b[A$<2>"[..]));
+    /// assert_eq!(cache.string(StringCap::SetAForeground), None);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn cache(&self) -> CachedTerm {
+        CachedTerm {
+            booleans: BooleanCap::ALL
+                .iter()
+                .copied()
+                .filter(|cap| self.has_boolean(*cap))
+                .collect(),
+            numerics: NumericCap::ALL
+                .iter()
+                .filter_map(|cap| Some((*cap, self.numeric_value(*cap)?)))
+                .collect(),
+            strings: StringCap::ALL
+                .iter()
+                .filter_map(|cap| Some((*cap, self.string_cstr(*cap)?.to_bytes().to_vec())))
+                .collect(),
+            ext_booleans: self
+                .ext_booleans()
+                .filter(|b| b.supported())
+                .map(|b| b.name_lossy().into_owned())
+                .collect(),
+            ext_numerics: self
+                .ext_numerics()
+                .filter(|n| n.value() >= 0)
+                .map(|n| (n.name_lossy().into_owned(), n.value()))
+                .collect(),
+            ext_strings: self
+                .ext_strings()
+                .filter_map(|s| Some((s.name_lossy().into_owned(), s.value_bytes()?.to_vec())))
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,618 @@
+//! Typed identifiers of the standard (predefined) terminal capabilities.
+//!
+//! Each enum lists the capabilities in the order defined by unibilium, so converting to the lower
+//! level representation is free. Names are kept in static tables and never require a `Term`.
+
+use std::sync::OnceLock;
+#[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+use unibilium_sys::{unibi_boolean, unibi_numeric, unibi_string};
+
+macro_rules! capabilities {
+    (
+        $(#[$meta:meta])*
+        $cap:ident => $unibi:ident($begin:ident), $count:expr;
+        $($variant:ident = $name:expr, $short:expr;)*
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #[repr(u32)]
+        pub enum $cap {
+            $($variant,)*
+        }
+
+        impl $cap {
+            /// All capabilities of this kind in unibilium order.
+            pub const ALL: [$cap; $count] = [$($cap::$variant,)*];
+
+            /// Returns the long name of the capability, as used by unibilium and `term.h`.
+            pub fn name(self) -> &'static str {
+                match self {
+                    $($cap::$variant => $name,)*
+                }
+            }
+
+            /// Returns the short terminfo name (capname) of the capability.
+            pub fn short_name(self) -> &'static str {
+                match self {
+                    $($cap::$variant => $short,)*
+                }
+            }
+
+            /// Looks up a capability by its short or long name.
+            ///
+            /// It binary searches a table of both names sorted on first use, so lookups stay
+            /// cheap in hot paths.
+            pub fn from_name(name: &str) -> Option<Self> {
+                static INDEX: OnceLock<Vec<(&'static str, $cap)>> = OnceLock::new();
+                let index = INDEX.get_or_init(|| {
+                    let mut index: Vec<_> = $cap::ALL
+                        .iter()
+                        .flat_map(|cap| vec![(cap.name(), *cap), (cap.short_name(), *cap)])
+                        .collect();
+                    index.sort_unstable();
+                    index
+                });
+                index
+                    .binary_search_by_key(&name, |(name, _)| *name)
+                    .ok()
+                    .map(|i| index[i].1)
+            }
+
+            /// Returns the lower level value used by unibilium for this capability.
+            #[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+            pub(crate) fn to_unibi(self) -> $unibi {
+                // Variants are declared in unibilium order, which starts right after the begin
+                // marker.
+                $unibi(self as u32 + $unibi::$begin.0 + 1)
+            }
+        }
+    };
+}
+
+capabilities! {
+    /// Identifies one of the standard boolean capabilities.
+    BooleanCap => unibi_boolean(unibi_boolean_begin_), 44;
+    AutoLeftMargin = "auto_left_margin", "bw";
+    AutoRightMargin = "auto_right_margin", "am";
+    NoEscCtlc = "no_esc_ctlc", "xsb";
+    CeolStandoutGlitch = "ceol_standout_glitch", "xhp";
+    EatNewlineGlitch = "eat_newline_glitch", "xenl";
+    EraseOverstrike = "erase_overstrike", "eo";
+    GenericType = "generic_type", "gn";
+    HardCopy = "hard_copy", "hc";
+    HasMetaKey = "has_meta_key", "km";
+    HasStatusLine = "has_status_line", "hs";
+    InsertNullGlitch = "insert_null_glitch", "in";
+    MemoryAbove = "memory_above", "da";
+    MemoryBelow = "memory_below", "db";
+    MoveInsertMode = "move_insert_mode", "mir";
+    MoveStandoutMode = "move_standout_mode", "msgr";
+    OverStrike = "over_strike", "os";
+    StatusLineEscOk = "status_line_esc_ok", "eslok";
+    DestTabsMagicSmso = "dest_tabs_magic_smso", "xt";
+    TildeGlitch = "tilde_glitch", "hz";
+    TransparentUnderline = "transparent_underline", "ul";
+    XonXoff = "xon_xoff", "xon";
+    NeedsXonXoff = "needs_xon_xoff", "nxon";
+    PrtrSilent = "prtr_silent", "mc5i";
+    HardCursor = "hard_cursor", "chts";
+    NonRevRmcup = "non_rev_rmcup", "nrrmc";
+    NoPadChar = "no_pad_char", "npc";
+    NonDestScrollRegion = "non_dest_scroll_region", "ndscr";
+    CanChange = "can_change", "ccc";
+    BackColorErase = "back_color_erase", "bce";
+    HueLightnessSaturation = "hue_lightness_saturation", "hls";
+    ColAddrGlitch = "col_addr_glitch", "xhpa";
+    CrCancelsMicroMode = "cr_cancels_micro_mode", "crxm";
+    HasPrintWheel = "has_print_wheel", "daisy";
+    RowAddrGlitch = "row_addr_glitch", "xvpa";
+    SemiAutoRightMargin = "semi_auto_right_margin", "sam";
+    CpiChangesRes = "cpi_changes_res", "cpix";
+    LpiChangesRes = "lpi_changes_res", "lpix";
+    BackspacesWithBs = "backspaces_with_bs", "OTbs";
+    CrtNoScrolling = "crt_no_scrolling", "OTns";
+    NoCorrectlyWorkingCr = "no_correctly_working_cr", "OTnc";
+    GnuHasMetaKey = "gnu_has_meta_key", "OTMT";
+    LinefeedIsNewline = "linefeed_is_newline", "OTNL";
+    HasHardwareTabs = "has_hardware_tabs", "OTpt";
+    ReturnDoesClrEol = "return_does_clr_eol", "OTxr";
+}
+
+capabilities! {
+    /// Identifies one of the standard numeric capabilities.
+    NumericCap => unibi_numeric(unibi_numeric_begin_), 39;
+    Columns = "columns", "cols";
+    InitTabs = "init_tabs", "it";
+    Lines = "lines", "lines";
+    LinesOfMemory = "lines_of_memory", "lm";
+    MagicCookieGlitch = "magic_cookie_glitch", "xmc";
+    PaddingBaudRate = "padding_baud_rate", "pb";
+    VirtualTerminal = "virtual_terminal", "vt";
+    WidthStatusLine = "width_status_line", "wsl";
+    NumLabels = "num_labels", "nlab";
+    LabelHeight = "label_height", "lh";
+    LabelWidth = "label_width", "lw";
+    MaxAttributes = "max_attributes", "ma";
+    MaximumWindows = "maximum_windows", "wnum";
+    MaxColors = "max_colors", "colors";
+    MaxPairs = "max_pairs", "pairs";
+    NoColorVideo = "no_color_video", "ncv";
+    BufferCapacity = "buffer_capacity", "bufsz";
+    DotVertSpacing = "dot_vert_spacing", "spinv";
+    DotHorzSpacing = "dot_horz_spacing", "spinh";
+    MaxMicroAddress = "max_micro_address", "maddr";
+    MaxMicroJump = "max_micro_jump", "mjump";
+    MicroColSize = "micro_col_size", "mcs";
+    MicroLineSize = "micro_line_size", "mls";
+    NumberOfPins = "number_of_pins", "npins";
+    OutputResChar = "output_res_char", "orc";
+    OutputResLine = "output_res_line", "orl";
+    OutputResHorzInch = "output_res_horz_inch", "orhi";
+    OutputResVertInch = "output_res_vert_inch", "orvi";
+    PrintRate = "print_rate", "cps";
+    WideCharSize = "wide_char_size", "widcs";
+    Buttons = "buttons", "btns";
+    BitImageEntwining = "bit_image_entwining", "bitwin";
+    BitImageType = "bit_image_type", "bitype";
+    MagicCookieGlitchUl = "magic_cookie_glitch_ul", "OTug";
+    CarriageReturnDelay = "carriage_return_delay", "OTdC";
+    NewLineDelay = "new_line_delay", "OTdN";
+    BackspaceDelay = "backspace_delay", "OTdB";
+    HorizontalTabDelay = "horizontal_tab_delay", "OTdT";
+    NumberOfFunctionKeys = "number_of_function_keys", "OTkn";
+}
+
+capabilities! {
+    /// Identifies one of the standard string capabilities.
+    StringCap => unibi_string(unibi_string_begin_), 414;
+    BackTab = "back_tab", "cbt";
+    Bell = "bell", "bel";
+    CarriageReturn = "carriage_return", "cr";
+    ChangeScrollRegion = "change_scroll_region", "csr";
+    ClearAllTabs = "clear_all_tabs", "tbc";
+    ClearScreen = "clear_screen", "clear";
+    ClrEol = "clr_eol", "el";
+    ClrEos = "clr_eos", "ed";
+    ColumnAddress = "column_address", "hpa";
+    CommandCharacter = "command_character", "cmdch";
+    CursorAddress = "cursor_address", "cup";
+    CursorDown = "cursor_down", "cud1";
+    CursorHome = "cursor_home", "home";
+    CursorInvisible = "cursor_invisible", "civis";
+    CursorLeft = "cursor_left", "cub1";
+    CursorMemAddress = "cursor_mem_address", "mrcup";
+    CursorNormal = "cursor_normal", "cnorm";
+    CursorRight = "cursor_right", "cuf1";
+    CursorToLl = "cursor_to_ll", "ll";
+    CursorUp = "cursor_up", "cuu1";
+    CursorVisible = "cursor_visible", "cvvis";
+    DeleteCharacter = "delete_character", "dch1";
+    DeleteLine = "delete_line", "dl1";
+    DisStatusLine = "dis_status_line", "dsl";
+    DownHalfLine = "down_half_line", "hd";
+    EnterAltCharsetMode = "enter_alt_charset_mode", "smacs";
+    EnterBlinkMode = "enter_blink_mode", "blink";
+    EnterBoldMode = "enter_bold_mode", "bold";
+    EnterCaMode = "enter_ca_mode", "smcup";
+    EnterDeleteMode = "enter_delete_mode", "smdc";
+    EnterDimMode = "enter_dim_mode", "dim";
+    EnterInsertMode = "enter_insert_mode", "smir";
+    EnterSecureMode = "enter_secure_mode", "invis";
+    EnterProtectedMode = "enter_protected_mode", "prot";
+    EnterReverseMode = "enter_reverse_mode", "rev";
+    EnterStandoutMode = "enter_standout_mode", "smso";
+    EnterUnderlineMode = "enter_underline_mode", "smul";
+    EraseChars = "erase_chars", "ech";
+    ExitAltCharsetMode = "exit_alt_charset_mode", "rmacs";
+    ExitAttributeMode = "exit_attribute_mode", "sgr0";
+    ExitCaMode = "exit_ca_mode", "rmcup";
+    ExitDeleteMode = "exit_delete_mode", "rmdc";
+    ExitInsertMode = "exit_insert_mode", "rmir";
+    ExitStandoutMode = "exit_standout_mode", "rmso";
+    ExitUnderlineMode = "exit_underline_mode", "rmul";
+    FlashScreen = "flash_screen", "flash";
+    FormFeed = "form_feed", "ff";
+    FromStatusLine = "from_status_line", "fsl";
+    Init1string = "init_1string", "is1";
+    Init2string = "init_2string", "is2";
+    Init3string = "init_3string", "is3";
+    InitFile = "init_file", "if";
+    InsertCharacter = "insert_character", "ich1";
+    InsertLine = "insert_line", "il1";
+    InsertPadding = "insert_padding", "ip";
+    KeyBackspace = "key_backspace", "kbs";
+    KeyCatab = "key_catab", "ktbc";
+    KeyClear = "key_clear", "kclr";
+    KeyCtab = "key_ctab", "kctab";
+    KeyDc = "key_dc", "kdch1";
+    KeyDl = "key_dl", "kdl1";
+    KeyDown = "key_down", "kcud1";
+    KeyEic = "key_eic", "krmir";
+    KeyEol = "key_eol", "kel";
+    KeyEos = "key_eos", "ked";
+    KeyF0 = "key_f0", "kf0";
+    KeyF1 = "key_f1", "kf1";
+    KeyF10 = "key_f10", "kf10";
+    KeyF2 = "key_f2", "kf2";
+    KeyF3 = "key_f3", "kf3";
+    KeyF4 = "key_f4", "kf4";
+    KeyF5 = "key_f5", "kf5";
+    KeyF6 = "key_f6", "kf6";
+    KeyF7 = "key_f7", "kf7";
+    KeyF8 = "key_f8", "kf8";
+    KeyF9 = "key_f9", "kf9";
+    KeyHome = "key_home", "khome";
+    KeyIc = "key_ic", "kich1";
+    KeyIl = "key_il", "kil1";
+    KeyLeft = "key_left", "kcub1";
+    KeyLl = "key_ll", "kll";
+    KeyNpage = "key_npage", "knp";
+    KeyPpage = "key_ppage", "kpp";
+    KeyRight = "key_right", "kcuf1";
+    KeySf = "key_sf", "kind";
+    KeySr = "key_sr", "kri";
+    KeyStab = "key_stab", "khts";
+    KeyUp = "key_up", "kcuu1";
+    KeypadLocal = "keypad_local", "rmkx";
+    KeypadXmit = "keypad_xmit", "smkx";
+    LabF0 = "lab_f0", "lf0";
+    LabF1 = "lab_f1", "lf1";
+    LabF10 = "lab_f10", "lf10";
+    LabF2 = "lab_f2", "lf2";
+    LabF3 = "lab_f3", "lf3";
+    LabF4 = "lab_f4", "lf4";
+    LabF5 = "lab_f5", "lf5";
+    LabF6 = "lab_f6", "lf6";
+    LabF7 = "lab_f7", "lf7";
+    LabF8 = "lab_f8", "lf8";
+    LabF9 = "lab_f9", "lf9";
+    MetaOff = "meta_off", "rmm";
+    MetaOn = "meta_on", "smm";
+    Newline = "newline", "nel";
+    PadChar = "pad_char", "pad";
+    ParmDch = "parm_dch", "dch";
+    ParmDeleteLine = "parm_delete_line", "dl";
+    ParmDownCursor = "parm_down_cursor", "cud";
+    ParmIch = "parm_ich", "ich";
+    ParmIndex = "parm_index", "indn";
+    ParmInsertLine = "parm_insert_line", "il";
+    ParmLeftCursor = "parm_left_cursor", "cub";
+    ParmRightCursor = "parm_right_cursor", "cuf";
+    ParmRindex = "parm_rindex", "rin";
+    ParmUpCursor = "parm_up_cursor", "cuu";
+    PkeyKey = "pkey_key", "pfkey";
+    PkeyLocal = "pkey_local", "pfloc";
+    PkeyXmit = "pkey_xmit", "pfx";
+    PrintScreen = "print_screen", "mc0";
+    PrtrOff = "prtr_off", "mc4";
+    PrtrOn = "prtr_on", "mc5";
+    RepeatChar = "repeat_char", "rep";
+    Reset1string = "reset_1string", "rs1";
+    Reset2string = "reset_2string", "rs2";
+    Reset3string = "reset_3string", "rs3";
+    ResetFile = "reset_file", "rf";
+    RestoreCursor = "restore_cursor", "rc";
+    RowAddress = "row_address", "vpa";
+    SaveCursor = "save_cursor", "sc";
+    ScrollForward = "scroll_forward", "ind";
+    ScrollReverse = "scroll_reverse", "ri";
+    SetAttributes = "set_attributes", "sgr";
+    SetTab = "set_tab", "hts";
+    SetWindow = "set_window", "wind";
+    Tab = "tab", "ht";
+    ToStatusLine = "to_status_line", "tsl";
+    UnderlineChar = "underline_char", "uc";
+    UpHalfLine = "up_half_line", "hu";
+    InitProg = "init_prog", "iprog";
+    KeyA1 = "key_a1", "ka1";
+    KeyA3 = "key_a3", "ka3";
+    KeyB2 = "key_b2", "kb2";
+    KeyC1 = "key_c1", "kc1";
+    KeyC3 = "key_c3", "kc3";
+    PrtrNon = "prtr_non", "mc5p";
+    CharPadding = "char_padding", "rmp";
+    AcsChars = "acs_chars", "acsc";
+    PlabNorm = "plab_norm", "pln";
+    KeyBtab = "key_btab", "kcbt";
+    EnterXonMode = "enter_xon_mode", "smxon";
+    ExitXonMode = "exit_xon_mode", "rmxon";
+    EnterAmMode = "enter_am_mode", "smam";
+    ExitAmMode = "exit_am_mode", "rmam";
+    XonCharacter = "xon_character", "xonc";
+    XoffCharacter = "xoff_character", "xoffc";
+    EnaAcs = "ena_acs", "enacs";
+    LabelOn = "label_on", "smln";
+    LabelOff = "label_off", "rmln";
+    KeyBeg = "key_beg", "kbeg";
+    KeyCancel = "key_cancel", "kcan";
+    KeyClose = "key_close", "kclo";
+    KeyCommand = "key_command", "kcmd";
+    KeyCopy = "key_copy", "kcpy";
+    KeyCreate = "key_create", "kcrt";
+    KeyEnd = "key_end", "kend";
+    KeyEnter = "key_enter", "kent";
+    KeyExit = "key_exit", "kext";
+    KeyFind = "key_find", "kfnd";
+    KeyHelp = "key_help", "khlp";
+    KeyMark = "key_mark", "kmrk";
+    KeyMessage = "key_message", "kmsg";
+    KeyMove = "key_move", "kmov";
+    KeyNext = "key_next", "knxt";
+    KeyOpen = "key_open", "kopn";
+    KeyOptions = "key_options", "kopt";
+    KeyPrevious = "key_previous", "kprv";
+    KeyPrint = "key_print", "kprt";
+    KeyRedo = "key_redo", "krdo";
+    KeyReference = "key_reference", "kref";
+    KeyRefresh = "key_refresh", "krfr";
+    KeyReplace = "key_replace", "krpl";
+    KeyRestart = "key_restart", "krst";
+    KeyResume = "key_resume", "kres";
+    KeySave = "key_save", "ksav";
+    KeySuspend = "key_suspend", "kspd";
+    KeyUndo = "key_undo", "kund";
+    KeySbeg = "key_sbeg", "kBEG";
+    KeyScancel = "key_scancel", "kCAN";
+    KeyScommand = "key_scommand", "kCMD";
+    KeyScopy = "key_scopy", "kCPY";
+    KeyScreate = "key_screate", "kCRT";
+    KeySdc = "key_sdc", "kDC";
+    KeySdl = "key_sdl", "kDL";
+    KeySelect = "key_select", "kslt";
+    KeySend = "key_send", "kEND";
+    KeySeol = "key_seol", "kEOL";
+    KeySexit = "key_sexit", "kEXT";
+    KeySfind = "key_sfind", "kFND";
+    KeyShelp = "key_shelp", "kHLP";
+    KeyShome = "key_shome", "kHOM";
+    KeySic = "key_sic", "kIC";
+    KeySleft = "key_sleft", "kLFT";
+    KeySmessage = "key_smessage", "kMSG";
+    KeySmove = "key_smove", "kMOV";
+    KeySnext = "key_snext", "kNXT";
+    KeySoptions = "key_soptions", "kOPT";
+    KeySprevious = "key_sprevious", "kPRV";
+    KeySprint = "key_sprint", "kPRT";
+    KeySredo = "key_sredo", "kRDO";
+    KeySreplace = "key_sreplace", "kRPL";
+    KeySright = "key_sright", "kRIT";
+    KeySrsume = "key_srsume", "kRES";
+    KeySsave = "key_ssave", "kSAV";
+    KeySsuspend = "key_ssuspend", "kSPD";
+    KeySundo = "key_sundo", "kUND";
+    ReqForInput = "req_for_input", "rfi";
+    KeyF11 = "key_f11", "kf11";
+    KeyF12 = "key_f12", "kf12";
+    KeyF13 = "key_f13", "kf13";
+    KeyF14 = "key_f14", "kf14";
+    KeyF15 = "key_f15", "kf15";
+    KeyF16 = "key_f16", "kf16";
+    KeyF17 = "key_f17", "kf17";
+    KeyF18 = "key_f18", "kf18";
+    KeyF19 = "key_f19", "kf19";
+    KeyF20 = "key_f20", "kf20";
+    KeyF21 = "key_f21", "kf21";
+    KeyF22 = "key_f22", "kf22";
+    KeyF23 = "key_f23", "kf23";
+    KeyF24 = "key_f24", "kf24";
+    KeyF25 = "key_f25", "kf25";
+    KeyF26 = "key_f26", "kf26";
+    KeyF27 = "key_f27", "kf27";
+    KeyF28 = "key_f28", "kf28";
+    KeyF29 = "key_f29", "kf29";
+    KeyF30 = "key_f30", "kf30";
+    KeyF31 = "key_f31", "kf31";
+    KeyF32 = "key_f32", "kf32";
+    KeyF33 = "key_f33", "kf33";
+    KeyF34 = "key_f34", "kf34";
+    KeyF35 = "key_f35", "kf35";
+    KeyF36 = "key_f36", "kf36";
+    KeyF37 = "key_f37", "kf37";
+    KeyF38 = "key_f38", "kf38";
+    KeyF39 = "key_f39", "kf39";
+    KeyF40 = "key_f40", "kf40";
+    KeyF41 = "key_f41", "kf41";
+    KeyF42 = "key_f42", "kf42";
+    KeyF43 = "key_f43", "kf43";
+    KeyF44 = "key_f44", "kf44";
+    KeyF45 = "key_f45", "kf45";
+    KeyF46 = "key_f46", "kf46";
+    KeyF47 = "key_f47", "kf47";
+    KeyF48 = "key_f48", "kf48";
+    KeyF49 = "key_f49", "kf49";
+    KeyF50 = "key_f50", "kf50";
+    KeyF51 = "key_f51", "kf51";
+    KeyF52 = "key_f52", "kf52";
+    KeyF53 = "key_f53", "kf53";
+    KeyF54 = "key_f54", "kf54";
+    KeyF55 = "key_f55", "kf55";
+    KeyF56 = "key_f56", "kf56";
+    KeyF57 = "key_f57", "kf57";
+    KeyF58 = "key_f58", "kf58";
+    KeyF59 = "key_f59", "kf59";
+    KeyF60 = "key_f60", "kf60";
+    KeyF61 = "key_f61", "kf61";
+    KeyF62 = "key_f62", "kf62";
+    KeyF63 = "key_f63", "kf63";
+    ClrBol = "clr_bol", "el1";
+    ClearMargins = "clear_margins", "mgc";
+    SetLeftMargin = "set_left_margin", "smgl";
+    SetRightMargin = "set_right_margin", "smgr";
+    LabelFormat = "label_format", "fln";
+    SetClock = "set_clock", "sclk";
+    DisplayClock = "display_clock", "dclk";
+    RemoveClock = "remove_clock", "rmclk";
+    CreateWindow = "create_window", "cwin";
+    GotoWindow = "goto_window", "wingo";
+    Hangup = "hangup", "hup";
+    DialPhone = "dial_phone", "dial";
+    QuickDial = "quick_dial", "qdial";
+    Tone = "tone", "tone";
+    Pulse = "pulse", "pulse";
+    FlashHook = "flash_hook", "hook";
+    FixedPause = "fixed_pause", "pause";
+    WaitTone = "wait_tone", "wait";
+    User0 = "user0", "u0";
+    User1 = "user1", "u1";
+    User2 = "user2", "u2";
+    User3 = "user3", "u3";
+    User4 = "user4", "u4";
+    User5 = "user5", "u5";
+    User6 = "user6", "u6";
+    User7 = "user7", "u7";
+    User8 = "user8", "u8";
+    User9 = "user9", "u9";
+    OrigPair = "orig_pair", "op";
+    OrigColors = "orig_colors", "oc";
+    InitializeColor = "initialize_color", "initc";
+    InitializePair = "initialize_pair", "initp";
+    SetColorPair = "set_color_pair", "scp";
+    SetForeground = "set_foreground", "setf";
+    SetBackground = "set_background", "setb";
+    ChangeCharPitch = "change_char_pitch", "cpi";
+    ChangeLinePitch = "change_line_pitch", "lpi";
+    ChangeResHorz = "change_res_horz", "chr";
+    ChangeResVert = "change_res_vert", "cvr";
+    DefineChar = "define_char", "defc";
+    EnterDoublewideMode = "enter_doublewide_mode", "swidm";
+    EnterDraftQuality = "enter_draft_quality", "sdrfq";
+    EnterItalicsMode = "enter_italics_mode", "sitm";
+    EnterLeftwardMode = "enter_leftward_mode", "slm";
+    EnterMicroMode = "enter_micro_mode", "smicm";
+    EnterNearLetterQuality = "enter_near_letter_quality", "snlq";
+    EnterNormalQuality = "enter_normal_quality", "snrmq";
+    EnterShadowMode = "enter_shadow_mode", "sshm";
+    EnterSubscriptMode = "enter_subscript_mode", "ssubm";
+    EnterSuperscriptMode = "enter_superscript_mode", "ssupm";
+    EnterUpwardMode = "enter_upward_mode", "sum";
+    ExitDoublewideMode = "exit_doublewide_mode", "rwidm";
+    ExitItalicsMode = "exit_italics_mode", "ritm";
+    ExitLeftwardMode = "exit_leftward_mode", "rlm";
+    ExitMicroMode = "exit_micro_mode", "rmicm";
+    ExitShadowMode = "exit_shadow_mode", "rshm";
+    ExitSubscriptMode = "exit_subscript_mode", "rsubm";
+    ExitSuperscriptMode = "exit_superscript_mode", "rsupm";
+    ExitUpwardMode = "exit_upward_mode", "rum";
+    MicroColumnAddress = "micro_column_address", "mhpa";
+    MicroDown = "micro_down", "mcud1";
+    MicroLeft = "micro_left", "mcub1";
+    MicroRight = "micro_right", "mcuf1";
+    MicroRowAddress = "micro_row_address", "mvpa";
+    MicroUp = "micro_up", "mcuu1";
+    OrderOfPins = "order_of_pins", "porder";
+    ParmDownMicro = "parm_down_micro", "mcud";
+    ParmLeftMicro = "parm_left_micro", "mcub";
+    ParmRightMicro = "parm_right_micro", "mcuf";
+    ParmUpMicro = "parm_up_micro", "mcuu";
+    SelectCharSet = "select_char_set", "scs";
+    SetBottomMargin = "set_bottom_margin", "smgb";
+    SetBottomMarginParm = "set_bottom_margin_parm", "smgbp";
+    SetLeftMarginParm = "set_left_margin_parm", "smglp";
+    SetRightMarginParm = "set_right_margin_parm", "smgrp";
+    SetTopMargin = "set_top_margin", "smgt";
+    SetTopMarginParm = "set_top_margin_parm", "smgtp";
+    StartBitImage = "start_bit_image", "sbim";
+    StartCharSetDef = "start_char_set_def", "scsd";
+    StopBitImage = "stop_bit_image", "rbim";
+    StopCharSetDef = "stop_char_set_def", "rcsd";
+    SubscriptCharacters = "subscript_characters", "subcs";
+    SuperscriptCharacters = "superscript_characters", "supcs";
+    TheseCauseCr = "these_cause_cr", "docr";
+    ZeroMotion = "zero_motion", "zerom";
+    CharSetNames = "char_set_names", "csnm";
+    KeyMouse = "key_mouse", "kmous";
+    MouseInfo = "mouse_info", "minfo";
+    ReqMousePos = "req_mouse_pos", "reqmp";
+    GetMouse = "get_mouse", "getm";
+    SetAForeground = "set_a_foreground", "setaf";
+    SetABackground = "set_a_background", "setab";
+    PkeyPlab = "pkey_plab", "pfxl";
+    DeviceType = "device_type", "devt";
+    CodeSetInit = "code_set_init", "csin";
+    Set0DesSeq = "set0_des_seq", "s0ds";
+    Set1DesSeq = "set1_des_seq", "s1ds";
+    Set2DesSeq = "set2_des_seq", "s2ds";
+    Set3DesSeq = "set3_des_seq", "s3ds";
+    SetLrMargin = "set_lr_margin", "smglr";
+    SetTbMargin = "set_tb_margin", "smgtb";
+    BitImageRepeat = "bit_image_repeat", "birep";
+    BitImageNewline = "bit_image_newline", "binel";
+    BitImageCarriageReturn = "bit_image_carriage_return", "bicr";
+    ColorNames = "color_names", "colornm";
+    DefineBitImageRegion = "define_bit_image_region", "defbi";
+    EndBitImageRegion = "end_bit_image_region", "endbi";
+    SetColorBand = "set_color_band", "setcolor";
+    SetPageLength = "set_page_length", "slines";
+    DisplayPcChar = "display_pc_char", "dispc";
+    EnterPcCharsetMode = "enter_pc_charset_mode", "smpch";
+    ExitPcCharsetMode = "exit_pc_charset_mode", "rmpch";
+    EnterScancodeMode = "enter_scancode_mode", "smsc";
+    ExitScancodeMode = "exit_scancode_mode", "rmsc";
+    PcTermOptions = "pc_term_options", "pctrm";
+    ScancodeEscape = "scancode_escape", "scesc";
+    AltScancodeEsc = "alt_scancode_esc", "scesa";
+    EnterHorizontalHlMode = "enter_horizontal_hl_mode", "ehhlm";
+    EnterLeftHlMode = "enter_left_hl_mode", "elhlm";
+    EnterLowHlMode = "enter_low_hl_mode", "elohlm";
+    EnterRightHlMode = "enter_right_hl_mode", "erhlm";
+    EnterTopHlMode = "enter_top_hl_mode", "ethlm";
+    EnterVerticalHlMode = "enter_vertical_hl_mode", "evhlm";
+    SetAAttributes = "set_a_attributes", "sgr1";
+    SetPglenInch = "set_pglen_inch", "slength";
+    TermcapInit2 = "termcap_init2", "OTi2";
+    TermcapReset = "termcap_reset", "OTrs";
+    LinefeedIfNotLf = "linefeed_if_not_lf", "OTnl";
+    BackspaceIfNotBs = "backspace_if_not_bs", "OTbc";
+    OtherNonFunctionKeys = "other_non_function_keys", "OTko";
+    ArrowKeyMap = "arrow_key_map", "OTma";
+    AcsUlcorner = "acs_ulcorner", "OTG2";
+    AcsLlcorner = "acs_llcorner", "OTG3";
+    AcsUrcorner = "acs_urcorner", "OTG1";
+    AcsLrcorner = "acs_lrcorner", "OTG4";
+    AcsLtee = "acs_ltee", "OTGR";
+    AcsRtee = "acs_rtee", "OTGL";
+    AcsBtee = "acs_btee", "OTGU";
+    AcsTtee = "acs_ttee", "OTGD";
+    AcsHline = "acs_hline", "OTGH";
+    AcsVline = "acs_vline", "OTGV";
+    AcsPlus = "acs_plus", "OTGC";
+    MemoryLock = "memory_lock", "meml";
+    MemoryUnlock = "memory_unlock", "memu";
+    BoxChars1 = "box_chars_1", "box1";
+}
+
+/// Identifies a standard capability of any kind, for fetching values of different kinds at
+/// once with Term::get_many.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CapId {
+    Boolean(BooleanCap),
+    Numeric(NumericCap),
+    String(StringCap),
+}
+
+impl From<BooleanCap> for CapId {
+    fn from(cap: BooleanCap) -> Self {
+        CapId::Boolean(cap)
+    }
+}
+
+impl From<NumericCap> for CapId {
+    fn from(cap: NumericCap) -> Self {
+        CapId::Numeric(cap)
+    }
+}
+
+impl From<StringCap> for CapId {
+    fn from(cap: StringCap) -> Self {
+        CapId::String(cap)
+    }
+}
+
+/// The value of a standard capability, of the kind of the CapId it was fetched for. Numeric
+/// and string values are None if the terminal doesn't define the capability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CapValue<'a> {
+    Boolean(bool),
+    Numeric(Option<i32>),
+    String(Option<&'a [u8]>),
+}
@@ -0,0 +1,293 @@
+use crate::boolean::{Boolean, ExtBoolean};
+use crate::numeric::{ExtNumeric, Numeric};
+use crate::string::{Escape, ExtString, String};
+use crate::term::Term;
+use std::borrow::Cow;
+use std::fmt;
+
+/// Represents any capability of a terminal, standard or extended, of any kind.
+#[derive(Debug)]
+pub enum Capability<'a> {
+    Boolean(Boolean<'a>),
+    ExtBoolean(ExtBoolean<'a>),
+    Numeric(Numeric<'a>),
+    ExtNumeric(ExtNumeric<'a>),
+    String(String<'a>),
+    ExtString(ExtString<'a>),
+}
+
+impl<'a> Capability<'a> {
+    /// Returns the short terminfo name of the capability. Extended capabilities only have this
+    /// name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if it internally encounters invalid UTF-8 characters in the name of an extended
+    /// capability, see short_name_lossy.
+    pub fn short_name(&self) -> &str {
+        match self {
+            Capability::Boolean(b) => b.short_name(),
+            Capability::ExtBoolean(b) => b.name(),
+            Capability::Numeric(n) => n.short_name(),
+            Capability::ExtNumeric(n) => n.name(),
+            Capability::String(s) => s.short_name(),
+            Capability::ExtString(s) => s.name(),
+        }
+    }
+
+    /// Returns the short terminfo name of the capability like short_name, replacing invalid
+    /// UTF-8 sequences in the names of extended capabilities with U+FFFD REPLACEMENT CHARACTER
+    /// instead of panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// let cap = term.capabilities().find(|cap| cap.short_name_lossy() == "cols");
+    /// assert_eq!(cap.unwrap().name_lossy(), "columns");
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn short_name_lossy(&self) -> Cow<'_, str> {
+        match self {
+            Capability::Boolean(b) => Cow::Borrowed(b.short_name()),
+            Capability::ExtBoolean(b) => b.name_lossy(),
+            Capability::Numeric(n) => Cow::Borrowed(n.short_name()),
+            Capability::ExtNumeric(n) => n.name_lossy(),
+            Capability::String(s) => Cow::Borrowed(s.short_name()),
+            Capability::ExtString(s) => s.name_lossy(),
+        }
+    }
+
+    /// Returns the name of the capability, the long one for standard capabilities, replacing
+    /// invalid UTF-8 sequences with U+FFFD REPLACEMENT CHARACTER.
+    pub fn name_lossy(&self) -> Cow<'_, str> {
+        match self {
+            Capability::Boolean(b) => b.name_lossy(),
+            Capability::ExtBoolean(b) => b.name_lossy(),
+            Capability::Numeric(n) => n.name_lossy(),
+            Capability::ExtNumeric(n) => n.name_lossy(),
+            Capability::String(s) => s.name_lossy(),
+            Capability::ExtString(s) => s.name_lossy(),
+        }
+    }
+
+    /// Returns whether this is an extended capability.
+    pub fn is_extended(&self) -> bool {
+        matches!(
+            self,
+            Capability::ExtBoolean(_) | Capability::ExtNumeric(_) | Capability::ExtString(_)
+        )
+    }
+
+    /// Returns the value of a string capability escaped like in terminfo source files, or None
+    /// for other kinds and undefined strings.
+    fn escaped_value(&self) -> Option<std::string::String> {
+        match self {
+            Capability::String(s) => s.escaped_value_with(Escape::Terminfo),
+            Capability::ExtString(s) => s.escaped_value_with(Escape::Terminfo),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the terminal defines the capability. Extended capabilities are always
+    /// defined.
+    fn is_defined(&self) -> bool {
+        match self {
+            Capability::Boolean(b) => b.supported(),
+            Capability::Numeric(n) => n.value() >= 0,
+            Capability::String(s) => s.value_bytes().is_some(),
+            _ => true,
+        }
+    }
+}
+
+/// Writes the name and the value of the capability like the Display of each kind, e.g.
+/// `columns: 80`. A width pads the name, so `format!("{:20}", cap)` aligns the values of
+/// capabilities written one per line.
+impl<'a> fmt::Display for Capability<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self.name_lossy();
+        write!(f, "{:<width$}: ", name, width = f.width().unwrap_or(0))?;
+        match self {
+            Capability::Boolean(b) => write!(f, "{}", b.supported()),
+            Capability::ExtBoolean(b) => write!(f, "{}", b.supported()),
+            Capability::Numeric(n) => write!(f, "{}", n.value()),
+            Capability::ExtNumeric(n) => write!(f, "{}", n.value()),
+            Capability::String(s) => match s.display_value(Escape::Caret) {
+                None => write!(f, "NULL"),
+                Some(value) => write!(f, "{}", value),
+            },
+            Capability::ExtString(s) => match s.display_value(Escape::Caret) {
+                None => write!(f, "NULL"),
+                Some(value) => write!(f, "{}", value),
+            },
+        }
+    }
+}
+
+/// Options for Term::display_capabilities.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct DisplayOptions {
+    /// Sorts the capabilities by name instead of listing them in the order of
+    /// Term::capabilities.
+    pub sorted: bool,
+    /// Pads the names to the longest one, aligning the values.
+    pub aligned: bool,
+    /// Leaves out standard capabilities the terminal doesn't define.
+    pub defined_only: bool,
+}
+
+/// Displays the capabilities of a terminal one per line, see Term::display_capabilities.
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayCapabilities<'a> {
+    term: &'a Term,
+    options: DisplayOptions,
+}
+
+impl<'a> fmt::Display for DisplayCapabilities<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut caps: Vec<Capability> = self
+            .term
+            .capabilities()
+            .filter(|cap| !self.options.defined_only || cap.is_defined())
+            .collect();
+        if self.options.sorted {
+            caps.sort_by(|a, b| a.name_lossy().cmp(&b.name_lossy()));
+        }
+        let width = if self.options.aligned {
+            caps.iter()
+                .map(|cap| cap.name_lossy().len())
+                .max()
+                .unwrap_or(0)
+        } else {
+            0
+        };
+        for cap in caps {
+            writeln!(f, "{:width$}", cap, width = width)?;
+        }
+        Ok(())
+    }
+}
+
+impl Term {
+    /// Returns a value displaying the capabilities of the terminal one per line, in the format
+    /// of the Display of Capability, with the given options. Sorting and aligning makes dumps of
+    /// two terminals easy to compare side by side.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::capability::DisplayOptions;
+    /// use unibilium::testing::MockTerm;
+    ///
+    /// let term = MockTerm::new("fake")
+    ///     .num("cols", 80)
+    ///     .bool("am", true)
+    ///     .string("bel", "\x07")
+    ///     .build()?;
+    /// let options = DisplayOptions {
+    ///     sorted: true,
+    ///     aligned: true,
+    ///     defined_only: true,
+    /// };
+    /// assert_eq!(
+    ///     term.display_capabilities(options).to_string(),
+    ///     "auto_right_margin: true\n\
+    ///      bell             : ^G\n\
+    ///      columns          : 80\n"
+    /// );
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn display_capabilities(&self, options: DisplayOptions) -> DisplayCapabilities<'_> {
+        DisplayCapabilities {
+            term: self,
+            options,
+        }
+    }
+
+    /// Returns the capabilities the terminal defines whose short name or long name contains the
+    /// query, ignoring case, or whose value escaped like in terminfo source files contains it,
+    /// e.g. `\E[A`. This is the search of interactive inspectors, which show values escaped
+    /// this way. An empty query matches every defined capability. The capabilities are in the
+    /// order of Term::capabilities.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::testing::MockTerm;
+    ///
+    /// let term = MockTerm::new("fake")
+    ///     .num("cols", 80)
+    ///     .string("cuu1", "\x1b[A")
+    ///     .string("kcuu1", "\x1bOA")
+    ///     .string("cub1", "\x08")
+    ///     .build()?;
+    /// let names = |query| {
+    ///     term.search(query)
+    ///         .iter()
+    ///         .map(|cap| cap.short_name_lossy().into_owned())
+    ///         .collect::<Vec<_>>()
+    /// };
+    /// assert_eq!(names("CUU"), ["cuu1", "kcuu1"]);
+    /// assert_eq!(names("column"), ["cols"]);
+    /// assert_eq!(names("\\E[A"), ["cuu1"]);
+    /// assert_eq!(names("\\b"), ["cub1"]);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn search(&self, query: &str) -> Vec<Capability<'_>> {
+        let lowercase = query.to_lowercase();
+        self.capabilities()
+            .filter(Capability::is_defined)
+            .filter(|cap| {
+                cap.short_name_lossy().to_lowercase().contains(&lowercase)
+                    || cap.name_lossy().to_lowercase().contains(&lowercase)
+                    || cap
+                        .escaped_value()
+                        .is_some_and(|value| value.contains(query))
+            })
+            .collect()
+    }
+}
+
+/// Matches a capability name against a glob pattern. `*` matches any sequence of characters and
+/// `?` matches exactly one character, so a prefix search is written as `"k*"`.
+pub(crate) fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.as_bytes();
+    let name = name.as_bytes();
+    let (mut p, mut n) = (0, 0);
+    // Position in the pattern after the last '*' and the name position it was tried against.
+    let mut backtrack = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == b'?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == b'*' {
+            backtrack = Some((p + 1, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = backtrack {
+            p = star_p;
+            n = star_n + 1;
+            backtrack = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == b'*')
+}
@@ -0,0 +1,57 @@
+//! Helpers for repeating and erasing characters.
+
+use crate::cap::StringCap;
+use crate::format::Param;
+use crate::term::Term;
+
+impl Term {
+    /// Returns the sequence printing the character count times, or None if the terminal can't
+    /// repeat characters.
+    ///
+    /// The character should be a printable ASCII character, since `rep` repeats a single byte.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("xterm")?;
+    /// assert_eq!(term.repeat_char(b'-', 80), Some(b"-\x1b[79b".to_vec()));
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert_eq!(term.repeat_char(b'-', 80), None);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn repeat_char(&self, ch: u8, count: u16) -> Option<Vec<u8>> {
+        self.expand(
+            StringCap::RepeatChar,
+            &[Param::num(i32::from(ch)), Param::num(i32::from(count))],
+        )
+    }
+
+    /// Returns the sequence erasing count characters starting at the cursor without moving it,
+    /// or None if the terminal can't erase characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("linux")?;
+    /// assert_eq!(term.erase_chars(10), Some(b"\x1b[10X".to_vec()));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn erase_chars(&self, count: u16) -> Option<Vec<u8>> {
+        self.expand(StringCap::EraseChars, &[Param::num(i32::from(count))])
+    }
+}
@@ -0,0 +1,84 @@
+//! Setting the clipboard through the terminal, as in xterm's OSC 52.
+
+use crate::format::Param;
+use crate::term::Term;
+use std::ffi::CString;
+
+/// A selection the terminal can set.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Selection {
+    Clipboard,
+    Primary,
+    Secondary,
+    /// The selection configured in the terminal, usually the primary selection or the clipboard.
+    Select,
+}
+
+impl Selection {
+    /// Returns the parameter of OSC 52 for the selection.
+    fn to_param(self) -> &'static str {
+        match self {
+            Selection::Clipboard => "c",
+            Selection::Primary => "p",
+            Selection::Secondary => "q",
+            Selection::Select => "s",
+        }
+    }
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes the data with the standard base64 alphabet and padding.
+fn base64(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let bits = u32::from(bytes[0]) << 16 | u32::from(bytes[1]) << 8 | u32::from(bytes[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64_ALPHABET[(bits >> (18 - 6 * i) & 0x3f) as usize]);
+            } else {
+                out.push(b'=');
+            }
+        }
+    }
+    out
+}
+
+impl Term {
+    /// Returns the sequence setting the selection to the data with the `Ms` extended
+    /// capability, or None if the terminal doesn't define it. The data is base64 encoded as the
+    /// capability expects.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::clipboard::Selection;
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("xterm-256color")?;
+    /// assert_eq!(
+    ///     term.set_clipboard(Selection::Clipboard, b"hello"),
+    ///     Some(b"\x1b]52;c;aGVsbG8=\x07".to_vec())
+    /// );
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert_eq!(term.set_clipboard(Selection::Clipboard, b"hello"), None);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn set_clipboard(&self, selection: Selection, data: &[u8]) -> Option<Vec<u8>> {
+        let selection = CString::new(selection.to_param()).expect("Selection contains NUL");
+        let data = CString::new(base64(data)).expect("Base64 contains NUL");
+        self.expand_ext("Ms", &[Param::str(&selection), Param::str(&data)])
+    }
+}
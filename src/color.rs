@@ -0,0 +1,371 @@
+//! Colors and the sequences selecting them on a terminal.
+
+use crate::cap::StringCap;
+use crate::env::EnvSnapshot;
+use crate::format::Param;
+use crate::term::Term;
+
+/// The number of colors from which a terminal is considered to accept direct RGB values in the
+/// parameter of `setaf` and `setab`, as the ncurses `-direct` entries do.
+const DIRECT_COLORS: u32 = 1 << 24;
+
+/// How many colors an application should use on a terminal, ordered from no colors to RGB
+/// colors.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum ColorLevel {
+    None,
+    /// The 8 ANSI colors and their bright variants.
+    Ansi16,
+    /// The 256 color palette.
+    Ansi256,
+    /// RGB colors.
+    TrueColor,
+}
+
+/// The levels of each component in the 6x6x6 color cube of the 256 color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The default values of the 16 ANSI colors, as used by xterm.
+const ANSI_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// A color, either an index into the 256 color palette of the terminal or an RGB value.
+///
+/// The first 16 indexes are the ANSI colors, followed by the 6x6x6 color cube and 24 shades of
+/// grey.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Color {
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    pub const BLACK: Color = Color::Indexed(0);
+    pub const RED: Color = Color::Indexed(1);
+    pub const GREEN: Color = Color::Indexed(2);
+    pub const YELLOW: Color = Color::Indexed(3);
+    pub const BLUE: Color = Color::Indexed(4);
+    pub const MAGENTA: Color = Color::Indexed(5);
+    pub const CYAN: Color = Color::Indexed(6);
+    pub const WHITE: Color = Color::Indexed(7);
+    pub const BRIGHT_BLACK: Color = Color::Indexed(8);
+    pub const BRIGHT_RED: Color = Color::Indexed(9);
+    pub const BRIGHT_GREEN: Color = Color::Indexed(10);
+    pub const BRIGHT_YELLOW: Color = Color::Indexed(11);
+    pub const BRIGHT_BLUE: Color = Color::Indexed(12);
+    pub const BRIGHT_MAGENTA: Color = Color::Indexed(13);
+    pub const BRIGHT_CYAN: Color = Color::Indexed(14);
+    pub const BRIGHT_WHITE: Color = Color::Indexed(15);
+
+    /// Returns the RGB value of the color. Indexed colors use the default xterm palette.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unibilium::color::Color;
+    ///
+    /// assert_eq!(Color::Indexed(196).to_rgb(), (255, 0, 0));
+    /// assert_eq!(Color::Indexed(232).to_rgb(), (8, 8, 8));
+    /// ```
+    pub fn to_rgb(self) -> (u8, u8, u8) {
+        match self {
+            Color::Rgb(r, g, b) => (r, g, b),
+            Color::Indexed(index) if index < 16 => ANSI_RGB[index as usize],
+            Color::Indexed(index) if index < 232 => {
+                let cube = index as usize - 16;
+                (
+                    CUBE_LEVELS[cube / 36],
+                    CUBE_LEVELS[cube / 6 % 6],
+                    CUBE_LEVELS[cube % 6],
+                )
+            }
+            Color::Indexed(index) => {
+                let grey = 8 + (index - 232) * 10;
+                (grey, grey, grey)
+            }
+        }
+    }
+
+    /// Returns the palette index closest to the color on a terminal supporting the given number
+    /// of colors. Indexed colors within the palette are returned unchanged.
+    ///
+    /// RGB colors are only mapped to the color cube and the shades of grey on 256 color
+    /// terminals, since the ANSI colors are often redefined by users.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unibilium::color::Color;
+    ///
+    /// assert_eq!(Color::Rgb(250, 10, 10).to_indexed(256), 196);
+    /// assert_eq!(Color::Rgb(250, 10, 10).to_indexed(16), 9);
+    /// assert_eq!(Color::Indexed(9).to_indexed(8), 1);
+    /// ```
+    pub fn to_indexed(self, colors: u32) -> u8 {
+        let colors = colors.clamp(1, 256);
+        if let Color::Indexed(index) = self {
+            if u32::from(index) < colors {
+                return index;
+            }
+        }
+        let first = if colors == 256 { 16 } else { 0 };
+        let rgb = self.to_rgb();
+        (first..colors)
+            .map(|index| index as u8)
+            .min_by_key(|index| distance(rgb, Color::Indexed(*index).to_rgb()))
+            .unwrap_or(0)
+    }
+}
+
+/// Returns the squared euclidean distance between two RGB values.
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let component = |a: u8, b: u8| {
+        let d = i32::from(a) - i32::from(b);
+        (d * d) as u32
+    };
+    component(a.0, b.0) + component(a.1, b.1) + component(a.2, b.2)
+}
+
+/// Returns the RGB value packed the way direct color terminals expect it.
+pub(crate) fn pack(rgb: (u8, u8, u8)) -> i32 {
+    i32::from(rgb.0) << 16 | i32::from(rgb.1) << 8 | i32::from(rgb.2)
+}
+
+/// Converts an ANSI color index to the numbering of `setf` and `setb`, which swaps red and blue.
+fn to_legacy_index(index: u8) -> u8 {
+    index & !0b101 | (index & 1) << 2 | (index & 0b100) >> 2
+}
+
+/// The capabilities used for setting either the foreground or the background color.
+struct ColorCaps {
+    ansi: StringCap,
+    legacy: StringCap,
+    rgb_ext: &'static str,
+    sgr: u8,
+}
+
+const FOREGROUND: ColorCaps = ColorCaps {
+    ansi: StringCap::SetAForeground,
+    legacy: StringCap::SetForeground,
+    rgb_ext: "setrgbf",
+    sgr: 38,
+};
+
+const BACKGROUND: ColorCaps = ColorCaps {
+    ansi: StringCap::SetABackground,
+    legacy: StringCap::SetBackground,
+    rgb_ext: "setrgbb",
+    sgr: 48,
+};
+
+impl Term {
+    /// Returns the sequence setting the foreground color, or None if the terminal doesn't support
+    /// colors.
+    ///
+    /// RGB colors are set with the `setrgbf` extended capability, with `setaf` on direct color
+    /// terminals, or with the ISO 8613-6 SGR sequence when the terminal has the `Tc` or `RGB`
+    /// extended boolean capability. Otherwise colors are converted to the closest color the
+    /// terminal supports and set with `setaf` or `setf`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::color::Color;
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("xterm-256color")?;
+    /// assert_eq!(term.set_foreground(Color::RED), Some(b"\x1b[31m".to_vec()));
+    /// assert_eq!(term.set_foreground(Color::Rgb(250, 10, 10)), Some(b"\x1b[38;5;196m".to_vec()));
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert_eq!(term.set_foreground(Color::RED), None);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn set_foreground(&self, color: Color) -> Option<Vec<u8>> {
+        self.set_color(color, &FOREGROUND)
+    }
+
+    /// Returns the sequence setting the background color, or None if the terminal doesn't support
+    /// colors. The capabilities are chosen like in set_foreground, using `setrgbb`, `setab` and
+    /// `setb` instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::color::Color;
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("xterm")?;
+    /// assert_eq!(term.set_background(Color::Indexed(196)), Some(b"\x1b[41m".to_vec()));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn set_background(&self, color: Color) -> Option<Vec<u8>> {
+        self.set_color(color, &BACKGROUND)
+    }
+
+    /// Returns whether RGB colors can be used on the terminal.
+    ///
+    /// Recognizes the `Tc` extended boolean capability used by tmux, the `RGB` extended
+    /// capability of ncurses in its boolean, numeric and string forms, the `setrgbf` and
+    /// `setrgbb` extended string capabilities, and direct color entries with at least 2^24
+    /// colors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("xterm-256color")?;
+    /// assert!(!term.supports_truecolor());
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn supports_truecolor(&self) -> bool {
+        self.max_colors()
+            .filter(|colors| *colors >= DIRECT_COLORS)
+            .is_some()
+            || self.ext_boolean_value("Tc")
+            || self.has_ext("RGB")
+            || (self.ext_string_cstr("setrgbf").is_some()
+                && self.ext_string_cstr("setrgbb").is_some())
+    }
+
+    /// Returns the color level to use on the terminal, combining its capabilities with the
+    /// conventions of the environment variables:
+    ///
+    /// * NO_COLOR set to a non-empty value disables colors, and takes precedence over the others.
+    /// * CLICOLOR_FORCE set to a value other than `0` enables at least the 16 ANSI colors, even
+    ///   on terminals without colors like `dumb`.
+    /// * COLORTERM set to `truecolor` or `24bit` enables RGB colors on any terminal with colors,
+    ///   since entries rarely declare them.
+    ///
+    /// Otherwise the level follows supports_truecolor and the number of colors (`colors`).
+    /// Dumb terminals (see is_dumb) get no colors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::color::ColorLevel;
+    /// use unibilium::env::EnvSnapshot;
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("xterm-256color")?;
+    /// let env = EnvSnapshot::new();
+    /// assert_eq!(term.color_level_with_env(&env), ColorLevel::Ansi256);
+    /// let env = env.set("COLORTERM", "truecolor");
+    /// assert_eq!(term.color_level_with_env(&env), ColorLevel::TrueColor);
+    /// let env = env.set("NO_COLOR", "1");
+    /// assert_eq!(term.color_level_with_env(&env), ColorLevel::None);
+    ///
+    /// let term = Term::from_term_name("dumb")?;
+    /// let env = EnvSnapshot::new();
+    /// assert_eq!(term.color_level_with_env(&env), ColorLevel::None);
+    /// let env = env.set("CLICOLOR_FORCE", "1");
+    /// assert_eq!(term.color_level_with_env(&env), ColorLevel::Ansi16);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn color_level_with_env(&self, env: &EnvSnapshot) -> ColorLevel {
+        if env.is_non_empty("NO_COLOR") {
+            return ColorLevel::None;
+        }
+        let colors = self.max_colors().unwrap_or(0);
+        let mut level = if self.is_dumb() {
+            ColorLevel::None
+        } else if self.supports_truecolor() {
+            ColorLevel::TrueColor
+        } else if colors >= 256 {
+            ColorLevel::Ansi256
+        } else if colors >= 8 {
+            ColorLevel::Ansi16
+        } else {
+            ColorLevel::None
+        };
+        let truecolor = matches!(env.get_str("COLORTERM"), Some("truecolor") | Some("24bit"));
+        if truecolor && level > ColorLevel::None {
+            level = ColorLevel::TrueColor;
+        }
+        if env.get("CLICOLOR_FORCE").is_some_and(|value| value != "0") {
+            level = level.max(ColorLevel::Ansi16);
+        }
+        level
+    }
+
+    /// Returns the color level to use on the terminal with the environment of the process, see
+    /// color_level_with_env.
+    pub fn color_level(&self) -> ColorLevel {
+        self.color_level_with_env(&EnvSnapshot::capture())
+    }
+
+    fn set_color(&self, color: Color, caps: &ColorCaps) -> Option<Vec<u8>> {
+        let colors = self.max_colors().unwrap_or(0);
+        if let Color::Rgb(r, g, b) = color {
+            let params = [
+                Param::num(i32::from(r)),
+                Param::num(i32::from(g)),
+                Param::num(i32::from(b)),
+            ];
+            if let Some(out) = self.expand_ext(caps.rgb_ext, &params) {
+                return Some(out);
+            }
+            if colors < DIRECT_COLORS
+                && (self.ext_boolean_value("Tc") || self.ext_boolean_value("RGB"))
+            {
+                return Some(format!("\x1b[{};2;{};{};{}m", caps.sgr, r, g, b).into_bytes());
+            }
+        }
+        if colors >= DIRECT_COLORS {
+            // Direct color terminals only keep the first 8 colors of the palette.
+            let value = match color {
+                Color::Indexed(index) if index < 8 => i32::from(index),
+                _ => pack(color.to_rgb()),
+            };
+            return self.expand(caps.ansi, &[Param::num(value)]);
+        }
+        if colors == 0 {
+            return None;
+        }
+        let index = color.to_indexed(colors);
+        match self.expand(caps.ansi, &[Param::num(i32::from(index))]) {
+            Some(out) => Some(out),
+            None => self.expand(
+                caps.legacy,
+                &[Param::num(i32::from(to_legacy_index(index)))],
+            ),
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! Checking whether a terminal has the capabilities an application needs.
+
+use crate::cap::{BooleanCap, CapId, NumericCap, StringCap};
+use crate::term::Term;
+
+/// The capabilities an application needs, built by chaining the methods adding them.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Requirements {
+    caps: Vec<CapId>,
+    ext: Vec<String>,
+}
+
+impl Requirements {
+    /// Returns empty requirements.
+    pub fn new() -> Self {
+        Requirements::default()
+    }
+
+    /// Requires the boolean capability to be set.
+    pub fn boolean(mut self, cap: BooleanCap) -> Self {
+        self.caps.push(CapId::Boolean(cap));
+        self
+    }
+
+    /// Requires the numeric capability to be defined, with at least the value of the reference
+    /// terminal.
+    pub fn numeric(mut self, cap: NumericCap) -> Self {
+        self.caps.push(CapId::Numeric(cap));
+        self
+    }
+
+    /// Requires the string capability to be defined.
+    pub fn string(mut self, cap: StringCap) -> Self {
+        self.caps.push(CapId::String(cap));
+        self
+    }
+
+    /// Requires the extended capability with the given name to be defined, see Term::has_ext.
+    pub fn ext(mut self, name: &str) -> Self {
+        self.ext.push(name.to_owned());
+        self
+    }
+}
+
+/// The result of Term::is_compatible_with.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Compatibility {
+    /// The required standard capabilities the terminal doesn't define or set.
+    pub missing: Vec<CapId>,
+    /// The required extended capabilities the terminal doesn't define.
+    pub missing_ext: Vec<String>,
+    /// The required numeric capabilities with a lower value than in the reference terminal,
+    /// with the value of the terminal and then of the reference.
+    pub weaker: Vec<(NumericCap, i32, i32)>,
+}
+
+impl Compatibility {
+    /// Returns true if nothing is missing or weaker.
+    pub fn is_compatible(&self) -> bool {
+        self.missing.is_empty() && self.missing_ext.is_empty() && self.weaker.is_empty()
+    }
+}
+
+impl Term {
+    /// Checks the required capabilities of the terminal against a reference terminal, e.g. the
+    /// one the application was developed with. Reports the required capabilities the terminal
+    /// lacks, and the numeric ones, like `colors`, with a lower value than in the reference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::cap::CapId;
+    /// use unibilium::compat::Requirements;
+    /// use unibilium::{NumericCap, StringCap, Term};
+    ///
+    /// let reference = Term::from_term_name("xterm-256color")?;
+    /// let requirements = Requirements::new()
+    ///     .string(StringCap::CursorAddress)
+    ///     .string(StringCap::SetAForeground)
+    ///     .numeric(NumericCap::MaxColors);
+    ///
+    /// let result = Term::from_term_name("xterm")?.is_compatible_with(&reference, &requirements);
+    /// assert!(result.missing.is_empty());
+    /// assert_eq!(result.weaker, [(NumericCap::MaxColors, 8, 256)]);
+    ///
+    /// let result = Term::from_term_name("vt100")?.is_compatible_with(&reference, &requirements);
+    /// assert_eq!(
+    ///     result.missing,
+    ///     [
+    ///         CapId::String(StringCap::SetAForeground),
+    ///         CapId::Numeric(NumericCap::MaxColors),
+    ///     ]
+    /// );
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn is_compatible_with(
+        &self,
+        reference: &Term,
+        requirements: &Requirements,
+    ) -> Compatibility {
+        let mut result = Compatibility::default();
+        for cap in requirements.caps.iter() {
+            match *cap {
+                CapId::Boolean(b) if !self.has_boolean(b) => result.missing.push(*cap),
+                CapId::Numeric(n) => match (self.numeric_value(n), reference.numeric_value(n)) {
+                    (None, _) => result.missing.push(*cap),
+                    (Some(value), Some(wanted)) if value < wanted => {
+                        result.weaker.push((n, value, wanted))
+                    }
+                    _ => {}
+                },
+                CapId::String(s) if !self.has_string(s) => result.missing.push(*cap),
+                _ => {}
+            }
+        }
+        result.missing_ext = requirements
+            .ext
+            .iter()
+            .filter(|name| !self.has_ext(name))
+            .cloned()
+            .collect();
+        result
+    }
+}
@@ -0,0 +1,423 @@
+//! Parameterized string capabilities parsed once and executed in Rust.
+
+use crate::cap::StringCap;
+use crate::format::{Param, Var, VarContext, MAX_PARAMS};
+use crate::param::{self, BinaryOp, Conversion, Op, ParseError, Printf, UnaryOp};
+use crate::term::Term;
+use std::ffi::CString;
+use std::io::{self, Write};
+
+/// Represents a value on the stack of a running program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Value {
+    Num(i32),
+    Str(Vec<u8>),
+}
+
+impl Value {
+    pub(crate) fn num(&self) -> i32 {
+        match self {
+            Value::Num(n) => *n,
+            Value::Str(_) => 0,
+        }
+    }
+}
+
+impl Default for Value {
+    fn default() -> Self {
+        Value::Num(0)
+    }
+}
+
+impl From<&Var> for Value {
+    fn from(var: &Var) -> Self {
+        match var {
+            Var::Num(n) => Value::Num(*n),
+            Var::Str(s) => Value::Str(s.as_bytes().to_vec()),
+        }
+    }
+}
+
+impl From<Value> for Var {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Num(n) => Var::Num(n),
+            // Values only come from parameters and variables, which can't contain NUL.
+            Value::Str(s) => Var::Str(CString::new(s).unwrap_or_default()),
+        }
+    }
+}
+
+/// Represents a single instruction of a compiled program. Conditionals are turned into jumps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Instr {
+    Literal(Vec<u8>),
+    Printf(Printf),
+    Char,
+    Param(usize),
+    SetVar(char),
+    GetVar(char),
+    Push(i32),
+    StrLen,
+    Binary(BinaryOp),
+    Unary(UnaryOp),
+    Increment,
+    /// Pops a value and jumps to the instruction if it is zero.
+    JumpIfFalse(usize),
+    Jump(usize),
+}
+
+/// Represents a parameterized string capability parsed once, which can be executed repeatedly
+/// without parsing it again. Padding directives are dropped. Created by Term::compile or
+/// CompiledCap::new.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompiledCap {
+    code: Vec<Instr>,
+}
+
+impl CompiledCap {
+    /// Compiles a parameterized string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::compiled::CompiledCap;
+    /// use unibilium::format::Param;
+    ///
+    /// let cup = CompiledCap::new("\x1b[%i%p1%d;%p2%dH")?;
+    /// assert_eq!(cup.expand(&[Param::num(5), Param::num(10)]), b"\x1b[6;11H");
+    /// assert_eq!(cup.expand(&[Param::num(0), Param::num(0)]), b"\x1b[1;1H");
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the string is malformed, see param::parse.
+    pub fn new<S: AsRef<[u8]> + ?Sized>(s: &S) -> Result<CompiledCap, ParseError> {
+        let program = param::parse(s)?;
+        let mut code = vec![];
+        compile_ops(program.ops(), &mut code);
+        Ok(CompiledCap { code })
+    }
+
+    /// Executes the program and streams the result into out. Variables set by the program are
+    /// discarded afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error reported by out.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than MAX_PARAMS parameters are given.
+    pub fn run<W: Write + ?Sized>(&self, params: &[Param], out: &mut W) -> io::Result<()> {
+        self.run_with(params, &mut VarContext::new(), out)
+    }
+
+    /// Executes the program like run, but reads and updates the variables of the given context.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error reported by out. Variables set before the error keep their values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than MAX_PARAMS parameters are given.
+    pub fn run_with<W: Write + ?Sized>(
+        &self,
+        params: &[Param],
+        vars: &mut VarContext,
+        out: &mut W,
+    ) -> io::Result<()> {
+        assert!(
+            params.len() <= MAX_PARAMS,
+            "At most {} parameters are supported, got {}",
+            MAX_PARAMS,
+            params.len()
+        );
+        let mut regs: [Value; MAX_PARAMS] = Default::default();
+        for (reg, param) in regs.iter_mut().zip(params) {
+            *reg = match param {
+                Param::Num(n) => Value::Num(*n),
+                Param::Str(s) => Value::Str(s.to_bytes().to_vec()),
+            };
+        }
+        let mut stack = vec![];
+        let mut pc = 0;
+        while let Some(instr) = self.code.get(pc) {
+            pc += 1;
+            // Popping from an empty stack yields zero, like unibilium does.
+            let mut pop = || stack.pop().unwrap_or_default();
+            match instr {
+                Instr::Literal(bytes) => out.write_all(bytes)?,
+                Instr::Printf(printf) => write_printf(printf, &pop(), out)?,
+                Instr::Char => out.write_all(&[pop().num() as u8])?,
+                Instr::Param(n) => stack.push(regs[*n].clone()),
+                Instr::SetVar(name) => {
+                    let value = pop();
+                    vars.set_var(*name, value.into());
+                }
+                Instr::GetVar(name) => {
+                    stack.push(vars.var(*name).map(Value::from).unwrap_or_default())
+                }
+                Instr::Push(n) => stack.push(Value::Num(*n)),
+                Instr::StrLen => {
+                    let len = match pop() {
+                        Value::Str(s) => s.len() as i32,
+                        Value::Num(_) => 0,
+                    };
+                    stack.push(Value::Num(len));
+                }
+                Instr::Binary(op) => {
+                    let b = pop().num();
+                    let a = pop().num();
+                    stack.push(Value::Num(binary(*op, a, b)));
+                }
+                Instr::Unary(op) => {
+                    let a = pop().num();
+                    let result = match op {
+                        UnaryOp::Not => (a == 0) as i32,
+                        UnaryOp::Complement => !a,
+                    };
+                    stack.push(Value::Num(result));
+                }
+                Instr::Increment => {
+                    for reg in regs.iter_mut().take(2) {
+                        if let Value::Num(n) = reg {
+                            *n = n.wrapping_add(1);
+                        }
+                    }
+                }
+                Instr::JumpIfFalse(target) => {
+                    if pop().num() == 0 {
+                        pc = *target;
+                    }
+                }
+                Instr::Jump(target) => pc = *target,
+            }
+        }
+        Ok(())
+    }
+
+    /// Executes the program and returns the result.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than MAX_PARAMS parameters are given.
+    pub fn expand(&self, params: &[Param]) -> Vec<u8> {
+        let mut out = vec![];
+        self.run(params, &mut out).expect("Writing to Vec failed");
+        out
+    }
+}
+
+pub(crate) fn binary(op: BinaryOp, a: i32, b: i32) -> i32 {
+    match op {
+        BinaryOp::Add => a.wrapping_add(b),
+        BinaryOp::Sub => a.wrapping_sub(b),
+        BinaryOp::Mul => a.wrapping_mul(b),
+        // Division by zero yields zero instead of failing.
+        BinaryOp::Div => a.checked_div(b).unwrap_or(0),
+        BinaryOp::Mod => a.checked_rem(b).unwrap_or(0),
+        BinaryOp::BitAnd => a & b,
+        BinaryOp::BitOr => a | b,
+        BinaryOp::BitXor => a ^ b,
+        BinaryOp::Eq => (a == b) as i32,
+        BinaryOp::Gt => (a > b) as i32,
+        BinaryOp::Lt => (a < b) as i32,
+        BinaryOp::And => (a != 0 && b != 0) as i32,
+        BinaryOp::Or => (a != 0 || b != 0) as i32,
+    }
+}
+
+fn compile_ops(ops: &[Op], code: &mut Vec<Instr>) {
+    for op in ops {
+        match op {
+            Op::Literal(bytes) => code.push(Instr::Literal(bytes.clone())),
+            Op::Delay(_) => {}
+            Op::Printf(printf) => code.push(Instr::Printf(*printf)),
+            Op::Char => code.push(Instr::Char),
+            Op::Param(n) => code.push(Instr::Param(usize::from(*n) - 1)),
+            Op::SetVar(v) => code.push(Instr::SetVar(*v)),
+            Op::GetVar(v) => code.push(Instr::GetVar(*v)),
+            Op::CharConst(c) => code.push(Instr::Push(i32::from(*c))),
+            Op::IntConst(n) => code.push(Instr::Push(*n)),
+            Op::StrLen => code.push(Instr::StrLen),
+            Op::Binary(op) => code.push(Instr::Binary(*op)),
+            Op::Unary(op) => code.push(Instr::Unary(*op)),
+            Op::Increment => code.push(Instr::Increment),
+            Op::Conditional(conditional) => {
+                // Jumps to the end are patched once its position is known.
+                let mut to_end = vec![];
+                for branch in &conditional.branches {
+                    compile_ops(&branch.condition, code);
+                    let skip = code.len();
+                    code.push(Instr::JumpIfFalse(0));
+                    compile_ops(&branch.then, code);
+                    to_end.push(code.len());
+                    code.push(Instr::Jump(0));
+                    code[skip] = Instr::JumpIfFalse(code.len());
+                }
+                if let Some(otherwise) = &conditional.otherwise {
+                    compile_ops(otherwise, code);
+                }
+                let end = code.len();
+                for jump in to_end {
+                    code[jump] = Instr::Jump(end);
+                }
+            }
+        }
+    }
+}
+
+/// Writes the value formatted like C printf would with the given specification.
+pub(crate) fn write_printf<W: Write + ?Sized>(
+    printf: &Printf,
+    value: &Value,
+    out: &mut W,
+) -> io::Result<()> {
+    // Large enough for any i32 in octal with prefix and sign.
+    let mut buf = [0u8; 16];
+    let mut prefix: &[u8] = b"";
+    let body: &[u8] = match (printf.conversion, value) {
+        (Conversion::String, Value::Str(s)) => match printf.precision {
+            Some(precision) if precision < s.len() => &s[..precision],
+            _ => s,
+        },
+        (Conversion::String, Value::Num(_)) => b"",
+        (conversion, value) => {
+            let n = value.num();
+            let (magnitude, base, upper) = match conversion {
+                Conversion::Decimal => {
+                    prefix = if n < 0 {
+                        b"-"
+                    } else if printf.sign {
+                        b"+"
+                    } else if printf.space {
+                        b" "
+                    } else {
+                        b""
+                    };
+                    (n.unsigned_abs(), 10, false)
+                }
+                Conversion::Octal => (n as u32, 8, false),
+                Conversion::Hex => (n as u32, 16, false),
+                _ => (n as u32, 16, true),
+            };
+            let mut start = buf.len();
+            let mut rest = magnitude;
+            while rest > 0 {
+                let digit = (rest % base) as u8;
+                start -= 1;
+                buf[start] = match digit {
+                    0..=9 => b'0' + digit,
+                    _ if upper => b'A' + digit - 10,
+                    _ => b'a' + digit - 10,
+                };
+                rest /= base;
+            }
+            // Without a precision zero still has one digit.
+            if magnitude == 0 && printf.precision != Some(0) {
+                start -= 1;
+                buf[start] = b'0';
+            }
+            if printf.alternate && magnitude != 0 {
+                prefix = match conversion {
+                    Conversion::Octal => b"0",
+                    Conversion::Hex => b"0x",
+                    _ => b"0X",
+                };
+            }
+            &buf[start..]
+        }
+    };
+    let precision_zeros = match (printf.conversion, printf.precision) {
+        (Conversion::String, _) | (_, None) => 0,
+        (_, Some(precision)) => precision.saturating_sub(body.len()),
+    };
+    let len = prefix.len() + precision_zeros + body.len();
+    let fill = printf.width.unwrap_or(0).saturating_sub(len);
+    let zero_fill = printf.zero_pad
+        && !printf.left_align
+        && printf.precision.is_none()
+        && printf.conversion != Conversion::String;
+    if !printf.left_align && !zero_fill {
+        write_repeated(out, b' ', fill)?;
+    }
+    out.write_all(prefix)?;
+    if zero_fill {
+        write_repeated(out, b'0', fill)?;
+    }
+    write_repeated(out, b'0', precision_zeros)?;
+    out.write_all(body)?;
+    if printf.left_align {
+        write_repeated(out, b' ', fill)?;
+    }
+    Ok(())
+}
+
+fn write_repeated<W: Write + ?Sized>(out: &mut W, byte: u8, count: usize) -> io::Result<()> {
+    let chunk = [byte; 16];
+    let mut left = count;
+    while left > 0 {
+        let n = left.min(chunk.len());
+        out.write_all(&chunk[..n])?;
+        left -= n;
+    }
+    Ok(())
+}
+
+impl Term {
+    /// Compiles the parameterized string capability, so that it can be executed repeatedly
+    /// without parsing it every time. Returns None if the terminal doesn't define the
+    /// capability.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::format::Param;
+    /// use unibilium::{StringCap, Term};
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// let cup = term.compile(StringCap::CursorAddress)?.unwrap();
+    /// let mut out = vec![];
+    /// for row in 0..24 {
+    ///     out.clear();
+    ///     cup.run(&[Param::num(row), Param::num(0)], &mut out)?;
+    /// }
+    /// assert_eq!(out, b"\x1b[24;1H");
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the value of the capability is malformed.
+    pub fn compile(&self, cap: StringCap) -> Result<Option<CompiledCap>, ParseError> {
+        match self.string_cstr(cap) {
+            None => Ok(None),
+            Some(value) => {
+                let result = CompiledCap::new(value.to_bytes()).map(Some);
+                #[cfg(feature = "tracing")]
+                if let Err(ref err) = result {
+                    crate::trace::event!(
+                        DEBUG,
+                        cap = cap.short_name(),
+                        error = %err,
+                        "parsing capability failed"
+                    );
+                }
+                result
+            }
+        }
+    }
+}
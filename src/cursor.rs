@@ -0,0 +1,101 @@
+//! Helpers for moving and showing the cursor.
+
+use crate::cap::StringCap;
+use crate::format::Param;
+use crate::term::Term;
+
+impl Term {
+    /// Returns the sequence moving the cursor to the given zero based row and column, or None if
+    /// the terminal can't address the cursor.
+    ///
+    /// Uses `cup` when available. Otherwise the row is set with `vpa`, or `home` followed by
+    /// `cud`, and then the column with `hpa`, or `cr` followed by `cuf`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert_eq!(term.goto(5, 10), Some(b"\x1b[6;11H".to_vec()));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn goto(&self, row: u16, col: u16) -> Option<Vec<u8>> {
+        let row = i32::from(row);
+        let col = i32::from(col);
+        if let Some(cup) = self.expand(
+            StringCap::CursorAddress,
+            &[Param::num(row), Param::num(col)],
+        ) {
+            return Some(cup);
+        }
+        let mut out = match self.expand(StringCap::RowAddress, &[Param::num(row)]) {
+            Some(vpa) => vpa,
+            None => self.relative_move(StringCap::CursorHome, StringCap::ParmDownCursor, row)?,
+        };
+        match self.expand(StringCap::ColumnAddress, &[Param::num(col)]) {
+            Some(hpa) => out.extend(hpa),
+            None => out.extend(self.relative_move(
+                StringCap::CarriageReturn,
+                StringCap::ParmRightCursor,
+                col,
+            )?),
+        }
+        Some(out)
+    }
+
+    /// Returns the sequence making the cursor invisible, or None if the terminal can't hide it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("xterm")?;
+    /// assert_eq!(term.hide_cursor(), Some(b"\x1b[?25l".to_vec()));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn hide_cursor(&self) -> Option<Vec<u8>> {
+        self.expand(StringCap::CursorInvisible, &[])
+    }
+
+    /// Returns the sequence making the cursor visible again, or None if the terminal can't hide
+    /// it in the first place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert_eq!(term.show_cursor(), None);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn show_cursor(&self) -> Option<Vec<u8>> {
+        self.expand(StringCap::CursorNormal, &[])
+    }
+
+    /// Returns origin followed by moving count times with the parameterized capability.
+    fn relative_move(&self, origin: StringCap, parm: StringCap, count: i32) -> Option<Vec<u8>> {
+        let mut out = self.expand(origin, &[])?;
+        if count > 0 {
+            out.extend(self.expand(parm, &[Param::num(count)])?);
+        }
+        Some(out)
+    }
+}
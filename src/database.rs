@@ -0,0 +1,104 @@
+//! Helpers working on several entries of the terminfo database.
+
+use crate::cache::CachedTerm;
+use crate::term::Term;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// A candidate terminal with its similarity to the terminal it was matched against.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Match<'a> {
+    pub term: &'a Term,
+    /// Similarity between 0 and 1, where 1 means the terminals define the same capabilities
+    /// with the same values.
+    pub score: f64,
+}
+
+/// Scores how much each candidate resembles the terminal and returns them ranked, with the
+/// closest first. Candidates with the same score keep their order.
+///
+/// Every capability, standard or extended, defined by either terminal counts. A capability
+/// counts fully towards the score if both terminals define it with the same value, and half
+/// if both define it with different values, so terminals supporting the same features with
+/// slightly different sequences still rank close.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use unibilium::{database, Term};
+///
+/// let term = Term::from_term_name("xterm-256color")?;
+/// let candidates = [
+///     Term::from_term_name("vt100")?,
+///     Term::from_term_name("xterm")?,
+///     Term::from_term_name("linux")?,
+/// ];
+/// let matches = database::closest_match(&term, &candidates);
+/// assert_eq!(matches.len(), 3);
+/// assert!(std::ptr::eq(matches[0].term, &candidates[1]));
+/// assert!(matches[0].score > matches[1].score);
+///
+/// let same = database::closest_match(&term, std::slice::from_ref(&term));
+/// assert_eq!(same[0].score, 1.0);
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+pub fn closest_match<'a>(term: &Term, candidates: &'a [Term]) -> Vec<Match<'a>> {
+    let cache = term.cache();
+    let mut matches: Vec<Match<'a>> = candidates
+        .iter()
+        .map(|candidate| Match {
+            term: candidate,
+            score: similarity(&cache, &candidate.cache()),
+        })
+        .collect();
+    matches.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    matches
+}
+
+/// Returns the similarity of the capabilities of a and b.
+fn similarity(a: &CachedTerm, b: &CachedTerm) -> f64 {
+    let scores = [
+        set_score(&a.booleans, &b.booleans),
+        map_score(&a.numerics, &b.numerics),
+        map_score(&a.strings, &b.strings),
+        set_score(&a.ext_booleans, &b.ext_booleans),
+        map_score(&a.ext_numerics, &b.ext_numerics),
+        map_score(&a.ext_strings, &b.ext_strings),
+    ];
+    let (matching, total) = scores.iter().fold((0.0, 0), |(matching, total), (m, t)| {
+        (matching + m, total + t)
+    });
+    if total == 0 {
+        1.0
+    } else {
+        matching / total as f64
+    }
+}
+
+/// Returns the score of the elements in both sets and the number of elements in either.
+fn set_score<K: Eq + Hash>(a: &HashSet<K>, b: &HashSet<K>) -> (f64, usize) {
+    let common = a.intersection(b).count();
+    (common as f64, a.len() + b.len() - common)
+}
+
+/// Returns the score of the keys in both maps and the number of keys in either.
+fn map_score<K: Eq + Hash, V: PartialEq>(a: &HashMap<K, V>, b: &HashMap<K, V>) -> (f64, usize) {
+    let mut score = 0.0;
+    let mut common = 0;
+    for (key, value) in a {
+        if let Some(other) = b.get(key) {
+            common += 1;
+            score += if value == other { 1.0 } else { 0.5 };
+        }
+    }
+    (score, a.len() + b.len() - common)
+}
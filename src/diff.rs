@@ -0,0 +1,148 @@
+//! Structured differences between the capabilities of two terminals.
+
+use crate::cap::{BooleanCap, NumericCap, StringCap};
+use crate::term::Term;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// The difference between the boolean capabilities set in two terminals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetDiff<K> {
+    /// The capabilities set only in the first terminal.
+    pub only_in_a: Vec<K>,
+    /// The capabilities set only in the second terminal.
+    pub only_in_b: Vec<K>,
+}
+
+impl<K> SetDiff<K> {
+    /// Returns true if both terminals set the same capabilities.
+    pub fn is_empty(&self) -> bool {
+        self.only_in_a.is_empty() && self.only_in_b.is_empty()
+    }
+}
+
+/// The difference between the numeric or string capabilities defined by two terminals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapDiff<K, V> {
+    /// The capabilities defined only by the first terminal, with their values.
+    pub only_in_a: Vec<(K, V)>,
+    /// The capabilities defined only by the second terminal, with their values.
+    pub only_in_b: Vec<(K, V)>,
+    /// The capabilities defined by both terminals with different values, with the value of the
+    /// first and then of the second terminal.
+    pub differing: Vec<(K, V, V)>,
+}
+
+impl<K, V> CapDiff<K, V> {
+    /// Returns true if both terminals define the same capabilities with the same values.
+    pub fn is_empty(&self) -> bool {
+        self.only_in_a.is_empty() && self.only_in_b.is_empty() && self.differing.is_empty()
+    }
+}
+
+/// The differences between the capabilities of two terminals, for each kind of standard and
+/// extended capabilities. Absent and cancelled capabilities are treated the same, and every
+/// list is sorted by capability.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TermDiff {
+    pub booleans: SetDiff<BooleanCap>,
+    pub numerics: CapDiff<NumericCap, i32>,
+    pub strings: CapDiff<StringCap, Vec<u8>>,
+    pub ext_booleans: SetDiff<String>,
+    pub ext_numerics: CapDiff<String, i32>,
+    pub ext_strings: CapDiff<String, Vec<u8>>,
+}
+
+impl TermDiff {
+    /// Computes the differences between the capabilities of a and b.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::diff::TermDiff;
+    /// use unibilium::{NumericCap, StringCap, Term};
+    ///
+    /// let vt100 = Term::from_term_name("vt100")?;
+    /// let xterm = Term::from_term_name("xterm")?;
+    /// let diff = TermDiff::compute(&vt100, &xterm);
+    /// assert!(diff.numerics.only_in_b.contains(&(NumericCap::MaxColors, 8)));
+    /// assert!(diff
+    ///     .strings
+    ///     .differing
+    ///     .iter()
+    ///     .any(|(cap, _, _)| *cap == StringCap::ClearScreen));
+    /// assert!(TermDiff::compute(&vt100, &vt100).is_empty());
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn compute(a: &Term, b: &Term) -> TermDiff {
+        let (a, b) = (a.cache(), b.cache());
+        TermDiff {
+            booleans: set_diff(&a.booleans, &b.booleans),
+            numerics: cap_diff(&a.numerics, &b.numerics),
+            strings: cap_diff(&a.strings, &b.strings),
+            ext_booleans: set_diff(&a.ext_booleans, &b.ext_booleans),
+            ext_numerics: cap_diff(&a.ext_numerics, &b.ext_numerics),
+            ext_strings: cap_diff(&a.ext_strings, &b.ext_strings),
+        }
+    }
+
+    /// Returns true if the terminals have the same capabilities with the same values.
+    pub fn is_empty(&self) -> bool {
+        self.booleans.is_empty()
+            && self.numerics.is_empty()
+            && self.strings.is_empty()
+            && self.ext_booleans.is_empty()
+            && self.ext_numerics.is_empty()
+            && self.ext_strings.is_empty()
+    }
+}
+
+/// Returns the elements of each set missing from the other one, sorted.
+fn set_diff<K: Clone + Eq + Hash + Ord>(a: &HashSet<K>, b: &HashSet<K>) -> SetDiff<K> {
+    let mut only_in_a: Vec<K> = a.difference(b).cloned().collect();
+    let mut only_in_b: Vec<K> = b.difference(a).cloned().collect();
+    only_in_a.sort();
+    only_in_b.sort();
+    SetDiff {
+        only_in_a,
+        only_in_b,
+    }
+}
+
+/// Returns the entries of each map missing from the other one and the keys with different
+/// values, sorted by key.
+fn cap_diff<K, V>(a: &HashMap<K, V>, b: &HashMap<K, V>) -> CapDiff<K, V>
+where
+    K: Clone + Eq + Hash + Ord,
+    V: Clone + Eq,
+{
+    let mut diff = CapDiff {
+        only_in_a: vec![],
+        only_in_b: vec![],
+        differing: vec![],
+    };
+    for (key, value) in a {
+        match b.get(key) {
+            None => diff.only_in_a.push((key.clone(), value.clone())),
+            Some(other) if other != value => {
+                diff.differing
+                    .push((key.clone(), value.clone(), other.clone()))
+            }
+            Some(_) => {}
+        }
+    }
+    for (key, value) in b {
+        if !a.contains_key(key) {
+            diff.only_in_b.push((key.clone(), value.clone()));
+        }
+    }
+    diff.only_in_a.sort_by(|x, y| x.0.cmp(&y.0));
+    diff.only_in_b.sort_by(|x, y| x.0.cmp(&y.0));
+    diff.differing.sort_by(|x, y| x.0.cmp(&y.0));
+    diff
+}
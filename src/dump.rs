@@ -0,0 +1,357 @@
+//! Writing the capabilities of a terminal in human and machine readable formats.
+
+use crate::cap::{BooleanCap, NumericCap, StringCap};
+use crate::string::{DisplayEscaped, Escape};
+use crate::term::Term;
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+/// The formats Term::dump_to writes.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum DumpFormat {
+    /// A table with the long name, the short name and the value of each capability, in aligned
+    /// columns. String values are escaped like in terminfo source files.
+    Table,
+    /// A terminfo source entry, as written by infocmp and read by tic.
+    Source,
+    /// A JSON object with the description, the aliases, and objects mapping the short names of
+    /// the boolean, numeric and string capabilities to their values. String values which
+    /// aren't valid UTF-8 are converted lossily.
+    Json,
+}
+
+/// Options for Term::dump_with.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct DumpOptions {
+    pub format: DumpFormat,
+    /// The number of columns the lines of tables and terminfo source entries are truncated to,
+    /// by cutting string values short and ending them with an ellipsis, or None to write the
+    /// values in full. Truncated source entries can't be read back by tic. JSON is never
+    /// truncated.
+    pub width: Option<u16>,
+}
+
+impl DumpOptions {
+    /// Returns options writing values in full.
+    pub fn new(format: DumpFormat) -> Self {
+        DumpOptions {
+            format,
+            width: None,
+        }
+    }
+
+    /// Returns options truncating lines to the width of the screen, as found by
+    /// Term::current_size, or writing values in full if it isn't known.
+    pub fn fit_screen(format: DumpFormat, term: &Term) -> Self {
+        DumpOptions {
+            format,
+            width: term.current_size().map(|size| size.columns),
+        }
+    }
+}
+
+/// The value of a capability defined by the terminal.
+enum Value<'a> {
+    Boolean,
+    Numeric(i32),
+    String(&'a [u8]),
+}
+
+/// A capability defined by the terminal. Extended capabilities have the same long and short
+/// name.
+struct Field<'a> {
+    name: Cow<'a, str>,
+    short_name: Cow<'a, str>,
+    value: Value<'a>,
+}
+
+impl Term {
+    /// Writes the capabilities the terminal defines to out in the given format: booleans,
+    /// numerics and strings, standard ones before extended ones of each kind. Each capability
+    /// is written as it is reached, without building the whole output in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::dump::DumpFormat;
+    /// use unibilium::testing::MockTerm;
+    ///
+    /// let term = MockTerm::new("fake")
+    ///     .bool("am", true)
+    ///     .num("cols", 80)
+    ///     .string("bel", "\x07")
+    ///     .build()?;
+    ///
+    /// let mut out = vec![];
+    /// term.dump_to(&mut out, DumpFormat::Source)?;
+    /// assert_eq!(out, b"fake,\n\tam,\n\tcols#80,\n\tbel=^G,\n");
+    ///
+    /// let mut out = vec![];
+    /// term.dump_to(&mut out, DumpFormat::Table)?;
+    /// assert_eq!(
+    ///     String::from_utf8(out)?,
+    ///     "auto_right_margin  am    true\n\
+    ///      columns            cols  80\n\
+    ///      bell               bel   ^G\n"
+    /// );
+    ///
+    /// let mut out = vec![];
+    /// term.dump_to(&mut out, DumpFormat::Json)?;
+    /// let json: serde_json::Value = serde_json::from_slice(&out)?;
+    /// assert_eq!(json["numerics"]["cols"], 80);
+    /// assert_eq!(json["strings"]["bel"], "\x07");
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of writing to out.
+    pub fn dump_to<W: Write + ?Sized>(&self, out: &mut W, format: DumpFormat) -> io::Result<()> {
+        self.dump_with(out, DumpOptions::new(format))
+    }
+
+    /// Writes the capabilities the terminal defines to out like dump_to, truncating long
+    /// string values when the options give a width.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::dump::{DumpFormat, DumpOptions};
+    /// use unibilium::testing::MockTerm;
+    ///
+    /// let term = MockTerm::new("fake")
+    ///     .string("cup", "\x1b[%i%p1%d;%p2%dH")
+    ///     .build()?;
+    ///
+    /// let options = DumpOptions {
+    ///     width: Some(24),
+    ///     ..DumpOptions::new(DumpFormat::Source)
+    /// };
+    /// let mut out = vec![];
+    /// term.dump_with(&mut out, options)?;
+    /// assert_eq!(String::from_utf8(out)?, "fake,\n\tcup=\\E[%i%p1%d…,\n");
+    ///
+    /// let mut out = vec![];
+    /// term.dump_with(&mut out, DumpOptions::new(DumpFormat::Source))?;
+    /// assert_eq!(String::from_utf8(out)?, "fake,\n\tcup=\\E[%i%p1%d;%p2%dH,\n");
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of writing to out.
+    pub fn dump_with<W: Write + ?Sized>(
+        &self,
+        out: &mut W,
+        options: DumpOptions,
+    ) -> io::Result<()> {
+        let fields = self.fields();
+        let width = options.width.map(usize::from);
+        match options.format {
+            DumpFormat::Table => write_table(out, &fields, width),
+            DumpFormat::Source => self.write_source(out, &fields, width),
+            DumpFormat::Json => self.write_json(out, &fields),
+        }
+    }
+
+    /// Returns the capabilities the terminal defines, in the order dump_to writes them.
+    fn fields(&self) -> Vec<Field<'_>> {
+        let mut fields = vec![];
+        for cap in BooleanCap::ALL.iter().filter(|cap| self.has_boolean(**cap)) {
+            fields.push(Field {
+                name: Cow::Borrowed(cap.name()),
+                short_name: Cow::Borrowed(cap.short_name()),
+                value: Value::Boolean,
+            });
+        }
+        for boolean in self.ext_booleans().filter(|boolean| boolean.supported()) {
+            let name = boolean.name_lossy().into_owned();
+            fields.push(Field {
+                name: Cow::Owned(name.clone()),
+                short_name: Cow::Owned(name),
+                value: Value::Boolean,
+            });
+        }
+        for cap in NumericCap::ALL.iter() {
+            if let Some(value) = self.numeric_value(*cap) {
+                fields.push(Field {
+                    name: Cow::Borrowed(cap.name()),
+                    short_name: Cow::Borrowed(cap.short_name()),
+                    value: Value::Numeric(value),
+                });
+            }
+        }
+        for numeric in self.ext_numerics().filter(|numeric| numeric.value() >= 0) {
+            let name = numeric.name_lossy().into_owned();
+            fields.push(Field {
+                name: Cow::Owned(name.clone()),
+                short_name: Cow::Owned(name),
+                value: Value::Numeric(numeric.value()),
+            });
+        }
+        for cap in StringCap::ALL.iter() {
+            if let Some(value) = self.string_cstr(*cap) {
+                fields.push(Field {
+                    name: Cow::Borrowed(cap.name()),
+                    short_name: Cow::Borrowed(cap.short_name()),
+                    value: Value::String(value.to_bytes()),
+                });
+            }
+        }
+        for string in self.ext_strings() {
+            if let Some(value) = string.value_bytes() {
+                let name = string.name_lossy().into_owned();
+                fields.push(Field {
+                    name: Cow::Owned(name.clone()),
+                    short_name: Cow::Owned(name),
+                    value: Value::String(value),
+                });
+            }
+        }
+        fields
+    }
+
+    fn write_source<W: Write + ?Sized>(
+        &self,
+        out: &mut W,
+        fields: &[Field],
+        width: Option<usize>,
+    ) -> io::Result<()> {
+        let mut names = self.aliases();
+        let name = self.name();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+        writeln!(out, "{},", names.join("|"))?;
+        for field in fields {
+            match field.value {
+                Value::Boolean => writeln!(out, "\t{},", field.short_name)?,
+                Value::Numeric(value) => writeln!(out, "\t{}#{},", field.short_name, value)?,
+                Value::String(value) => {
+                    let value = DisplayEscaped::new(value, Escape::Terminfo).to_string();
+                    // The tab takes 8 columns, the equals sign and the comma one each.
+                    let used = TAB_WIDTH + field.short_name.chars().count() + 2;
+                    let value = truncate(&value, width.map(|width| width.saturating_sub(used)));
+                    writeln!(out, "\t{}={},", field.short_name, value)?
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn write_json<W: Write + ?Sized>(&self, out: &mut W, fields: &[Field]) -> io::Result<()> {
+        write!(out, "{{\n  \"name\": ")?;
+        write_json_string(out, &self.name())?;
+        write!(out, ",\n  \"aliases\": [")?;
+        for (i, alias) in self.aliases().iter().enumerate() {
+            if i > 0 {
+                write!(out, ", ")?;
+            }
+            write_json_string(out, alias)?;
+        }
+        write!(out, "]")?;
+        for kind in ["booleans", "numerics", "strings"].iter() {
+            write!(out, ",\n  \"{}\": {{", kind)?;
+            let mut first = true;
+            for field in fields.iter().filter(|field| field.value.kind() == *kind) {
+                write!(out, "{}\n    ", if first { "" } else { "," })?;
+                first = false;
+                write_json_string(out, &field.short_name)?;
+                match field.value {
+                    Value::Boolean => write!(out, ": true")?,
+                    Value::Numeric(value) => write!(out, ": {}", value)?,
+                    Value::String(value) => {
+                        write!(out, ": ")?;
+                        write_json_string(out, &String::from_utf8_lossy(value))?;
+                    }
+                }
+            }
+            write!(out, "{}}}", if first { "" } else { "\n  " })?;
+        }
+        writeln!(out, "\n}}")
+    }
+}
+
+impl Value<'_> {
+    /// Returns the key of the JSON object holding the capabilities of this kind.
+    fn kind(&self) -> &'static str {
+        match self {
+            Value::Boolean => "booleans",
+            Value::Numeric(_) => "numerics",
+            Value::String(_) => "strings",
+        }
+    }
+}
+
+fn write_table<W: Write + ?Sized>(
+    out: &mut W,
+    fields: &[Field],
+    width: Option<usize>,
+) -> io::Result<()> {
+    let name_width = fields.iter().map(|field| field.name.len()).max();
+    let short_width = fields.iter().map(|field| field.short_name.len()).max();
+    let used = name_width.unwrap_or(0) + short_width.unwrap_or(0) + 4;
+    let value_width = width.map(|width| width.saturating_sub(used));
+    for field in fields {
+        write!(
+            out,
+            "{:<name$}  {:<short$}  ",
+            field.name,
+            field.short_name,
+            name = name_width.unwrap_or(0),
+            short = short_width.unwrap_or(0)
+        )?;
+        match field.value {
+            Value::Boolean => writeln!(out, "true")?,
+            Value::Numeric(value) => writeln!(out, "{}", value)?,
+            Value::String(value) => {
+                let value = DisplayEscaped::new(value, Escape::Terminfo).to_string();
+                writeln!(out, "{}", truncate(&value, value_width))?
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The number of columns a tab advances at the start of a line.
+const TAB_WIDTH: usize = 8;
+
+/// Returns the value cut to width characters, the last being an ellipsis, if it's longer. At
+/// least the ellipsis is kept.
+fn truncate(value: &str, width: Option<usize>) -> Cow<'_, str> {
+    match width {
+        Some(width) if value.chars().count() > width => {
+            let mut truncated: String = value.chars().take(width.saturating_sub(1)).collect();
+            truncated.push('…');
+            Cow::Owned(truncated)
+        }
+        _ => Cow::Borrowed(value),
+    }
+}
+
+/// Writes s as a JSON string, escaping quotes, backslashes and control characters.
+fn write_json_string<W: Write + ?Sized>(out: &mut W, s: &str) -> io::Result<()> {
+    write!(out, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(out, "\\\"")?,
+            '\\' => write!(out, "\\\\")?,
+            '\n' => write!(out, "\\n")?,
+            '\r' => write!(out, "\\r")?,
+            '\t' => write!(out, "\\t")?,
+            c if c.is_control() => write!(out, "\\u{:04x}", u32::from(c))?,
+            c => write!(out, "{}", c)?,
+        }
+    }
+    write!(out, "\"")
+}
@@ -0,0 +1,635 @@
+//! Terminal entries held in Rust-owned storage, written in and, for the pure Rust backend, read
+//! from the compiled terminfo format. An Entry is a Backend in every build.
+//!
+//! The layout follows term(5): a header of six little endian shorts, the names, the booleans,
+//! the numbers, the offsets of the strings and the string table, optionally followed by the
+//! extended section ncurses uses for user-defined capabilities. Entries with a number beyond
+//! MAX_NUMERIC_16BIT use the extended number format, which stores numbers in 32 bits.
+
+use crate::backend::Backend;
+use crate::binary::{MAGIC_16BIT, MAGIC_32BIT};
+use crate::cap::{BooleanCap, NumericCap, StringCap};
+use crate::version::MAX_NUMERIC_16BIT;
+use std::convert::TryFrom;
+use std::ffi::{CStr, CString};
+#[cfg(any(test, feature = "pure-rust", target_family = "wasm"))]
+use std::io;
+
+/// The names and the capabilities of a terminal. Numeric values are -1 for absent and -2 for
+/// cancelled capabilities, like unibilium reports them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Entry {
+    name: CString,
+    aliases: Vec<CString>,
+    booleans: Vec<bool>,
+    numerics: Vec<i32>,
+    strings: Vec<Option<CString>>,
+    ext_booleans: Vec<(CString, bool)>,
+    ext_numerics: Vec<(CString, i32)>,
+    ext_strings: Vec<(CString, Option<CString>)>,
+}
+
+impl Entry {
+    /// Creates an entry without capabilities.
+    pub(crate) fn new(name: CString, aliases: Vec<CString>) -> Entry {
+        Entry {
+            name,
+            aliases,
+            booleans: vec![false; BooleanCap::ALL.len()],
+            numerics: vec![-1; NumericCap::ALL.len()],
+            strings: vec![None; StringCap::ALL.len()],
+            ext_booleans: vec![],
+            ext_numerics: vec![],
+            ext_strings: vec![],
+        }
+    }
+
+    pub(crate) fn set_boolean(&mut self, cap: BooleanCap, value: bool) {
+        self.booleans[cap as usize] = value;
+    }
+
+    pub(crate) fn set_numeric(&mut self, cap: NumericCap, value: i32) {
+        self.numerics[cap as usize] = value;
+    }
+
+    pub(crate) fn set_string(&mut self, cap: StringCap, value: CString) {
+        self.strings[cap as usize] = Some(value);
+    }
+
+    pub(crate) fn add_ext_boolean(&mut self, name: CString, value: bool) {
+        self.ext_booleans.push((name, value));
+    }
+
+    pub(crate) fn add_ext_numeric(&mut self, name: CString, value: i32) {
+        self.ext_numerics.push((name, value));
+    }
+
+    pub(crate) fn add_ext_string(&mut self, name: CString, value: CString) {
+        self.ext_strings.push((name, Some(value)));
+    }
+
+    /// Writes the entry in the compiled terminfo format like unibi_dump, or returns None if it
+    /// doesn't fit into the format, e.g. because the string table outgrows the 16-bit offsets.
+    pub(crate) fn to_bytes(&self) -> Option<Vec<u8>> {
+        let wide = self
+            .numerics
+            .iter()
+            .chain(self.ext_numerics.iter().map(|(_, value)| value))
+            .any(|value| *value > MAX_NUMERIC_16BIT);
+        let mut names = vec![];
+        for alias in &self.aliases {
+            names.extend_from_slice(alias.to_bytes());
+            names.push(b'|');
+        }
+        names.extend_from_slice(self.name.to_bytes_with_nul());
+        // Trailing absent capabilities are left out.
+        let booleans =
+            &self.booleans[..self.booleans.iter().rposition(|b| *b).map_or(0, |i| i + 1)];
+        let numerics = &self.numerics[..self
+            .numerics
+            .iter()
+            .rposition(|n| *n != -1)
+            .map_or(0, |i| i + 1)];
+        let strings = &self.strings[..self
+            .strings
+            .iter()
+            .rposition(Option::is_some)
+            .map_or(0, |i| i + 1)];
+        let (offsets, table) = string_table(strings.iter().map(Option::as_deref))?;
+
+        let mut out = Writer { data: vec![], wide };
+        out.short(usize::from(if wide { MAGIC_32BIT } else { MAGIC_16BIT }))?;
+        for len in [
+            names.len(),
+            booleans.len(),
+            numerics.len(),
+            strings.len(),
+            table.len(),
+        ] {
+            out.short(len)?;
+        }
+        out.data.extend_from_slice(&names);
+        out.data.extend(booleans.iter().map(|b| u8::from(*b)));
+        out.align();
+        numerics.iter().for_each(|n| out.number(*n));
+        offsets.iter().for_each(|offset| out.offset(*offset));
+        out.data.extend_from_slice(&table);
+
+        let ext_count = self.ext_booleans.len() + self.ext_numerics.len() + self.ext_strings.len();
+        if ext_count > 0 {
+            out.align();
+            let values = self.ext_strings.iter().map(|(_, value)| value.as_deref());
+            let (mut offsets, mut table) = string_table(values)?;
+            // Name offsets count from the end of the values.
+            let base = table.len();
+            let names = self
+                .ext_booleans
+                .iter()
+                .map(|(name, _)| name)
+                .chain(self.ext_numerics.iter().map(|(name, _)| name))
+                .chain(self.ext_strings.iter().map(|(name, _)| name));
+            for name in names {
+                offsets.push(i16::try_from(table.len() - base).ok()?);
+                table.extend_from_slice(name.to_bytes_with_nul());
+            }
+            for len in [
+                self.ext_booleans.len(),
+                self.ext_numerics.len(),
+                self.ext_strings.len(),
+                offsets.len(),
+                table.len(),
+            ] {
+                out.short(len)?;
+            }
+            out.data
+                .extend(self.ext_booleans.iter().map(|(_, b)| u8::from(*b)));
+            out.align();
+            self.ext_numerics.iter().for_each(|(_, n)| out.number(*n));
+            offsets.iter().for_each(|offset| out.offset(*offset));
+            out.data.extend_from_slice(&table);
+        }
+        Some(out.data)
+    }
+}
+
+/// Lays out the strings in a table, returning the offset of each string, -1 for absent ones,
+/// and the table. Returns None if an offset doesn't fit into 16 bits.
+fn string_table<'a, I>(strings: I) -> Option<(Vec<i16>, Vec<u8>)>
+where
+    I: Iterator<Item = Option<&'a CStr>>,
+{
+    let mut offsets = vec![];
+    let mut table = vec![];
+    for string in strings {
+        match string {
+            Some(string) => {
+                offsets.push(i16::try_from(table.len()).ok()?);
+                table.extend_from_slice(string.to_bytes_with_nul());
+            }
+            None => offsets.push(-1),
+        }
+    }
+    Some((offsets, table))
+}
+
+/// Appends the fields of the compiled format to the data.
+struct Writer {
+    data: Vec<u8>,
+    wide: bool,
+}
+
+impl Writer {
+    /// Appends a count or a size, which must fit into a non-negative short.
+    fn short(&mut self, value: usize) -> Option<()> {
+        let value = u16::try_from(value).ok()?;
+        self.data.extend_from_slice(&value.to_le_bytes());
+        Some(())
+    }
+
+    fn offset(&mut self, offset: i16) {
+        self.data.extend_from_slice(&offset.to_le_bytes());
+    }
+
+    fn number(&mut self, value: i32) {
+        if self.wide {
+            self.data.extend_from_slice(&value.to_le_bytes());
+        } else {
+            self.data.extend_from_slice(&(value as i16).to_le_bytes());
+        }
+    }
+
+    /// Pads the data to an even length, which the sections after the booleans start at.
+    fn align(&mut self) {
+        if self.data.len() % 2 == 1 {
+            self.data.push(0);
+        }
+    }
+}
+
+impl Backend for Entry {
+    fn name(&self) -> &CStr {
+        &self.name
+    }
+
+    fn aliases(&self) -> Vec<&CStr> {
+        self.aliases.iter().map(CString::as_c_str).collect()
+    }
+
+    fn boolean(&self, cap: BooleanCap) -> bool {
+        self.booleans[cap as usize]
+    }
+
+    fn numeric(&self, cap: NumericCap) -> i32 {
+        self.numerics[cap as usize]
+    }
+
+    fn string(&self, cap: StringCap) -> Option<&CStr> {
+        self.strings[cap as usize].as_deref()
+    }
+
+    fn ext_boolean_count(&self) -> u64 {
+        self.ext_booleans.len() as u64
+    }
+
+    fn ext_boolean_name(&self, index: u64) -> Option<&CStr> {
+        get(&self.ext_booleans, index).map(|(name, _)| name.as_c_str())
+    }
+
+    fn ext_boolean(&self, index: u64) -> bool {
+        get(&self.ext_booleans, index).is_some_and(|(_, value)| *value)
+    }
+
+    fn ext_numeric_count(&self) -> u64 {
+        self.ext_numerics.len() as u64
+    }
+
+    fn ext_numeric_name(&self, index: u64) -> Option<&CStr> {
+        get(&self.ext_numerics, index).map(|(name, _)| name.as_c_str())
+    }
+
+    fn ext_numeric(&self, index: u64) -> i32 {
+        get(&self.ext_numerics, index).map_or(-1, |(_, value)| *value)
+    }
+
+    fn ext_string_count(&self) -> u64 {
+        self.ext_strings.len() as u64
+    }
+
+    fn ext_string_name(&self, index: u64) -> Option<&CStr> {
+        get(&self.ext_strings, index).map(|(name, _)| name.as_c_str())
+    }
+
+    fn ext_string(&self, index: u64) -> Option<&CStr> {
+        get(&self.ext_strings, index).and_then(|(_, value)| value.as_deref())
+    }
+
+    fn dump(&self) -> Option<Vec<u8>> {
+        self.to_bytes()
+    }
+}
+
+#[cfg(any(test, feature = "pure-rust", target_family = "wasm"))]
+impl Entry {
+    /// Parses an entry in the compiled terminfo format, accepting what unibi_from_mem accepts.
+    /// Capabilities beyond the standard ones are ignored.
+    pub(crate) fn parse(data: &[u8]) -> io::Result<Entry> {
+        let mut reader = Reader { data, pos: 0 };
+        let wide = match reader.short()? {
+            MAGIC_16BIT => false,
+            MAGIC_32BIT => true,
+            magic => return Err(invalid(format!("bad magic number {:#o}", magic))),
+        };
+        let mut header = [0usize; 5];
+        for len in header.iter_mut() {
+            *len = usize::from(reader.short()?);
+        }
+        let [names_len, booleans_len, numerics_len, strings_len, table_len] = header;
+        let names = reader.bytes(names_len)?;
+        if names.last() != Some(&0) {
+            return Err(invalid("names aren't terminated by NUL"));
+        }
+        // The last field is the description of the terminal, the others are its names.
+        let end = names.iter().position(|b| *b == 0).unwrap_or(names.len());
+        let mut fields: Vec<CString> = names[..end]
+            .split(|b| *b == b'|')
+            .map(|field| CString::new(field).unwrap_or_default())
+            .collect();
+        let name = fields.pop().unwrap_or_default();
+        let mut entry = Entry::new(name, fields);
+        for (value, byte) in entry.booleans.iter_mut().zip(reader.bytes(booleans_len)?) {
+            *value = *byte as i8 > 0;
+        }
+        reader.align();
+        for i in 0..numerics_len {
+            let value = reader.number(wide)?;
+            if let Some(numeric) = entry.numerics.get_mut(i) {
+                *numeric = value;
+            }
+        }
+        let offsets = reader.offsets(strings_len)?;
+        let table = reader.bytes(table_len)?;
+        for (value, offset) in entry.strings.iter_mut().zip(offsets) {
+            *value = string_at(table, offset)?;
+        }
+        reader.align();
+        if data.len() - reader.pos >= 10 {
+            entry.parse_extended(&mut reader, wide)?;
+        }
+        Ok(entry)
+    }
+
+    /// Parses the extended section, which lists the names of the capabilities along with the
+    /// values.
+    fn parse_extended(&mut self, reader: &mut Reader<'_>, wide: bool) -> io::Result<()> {
+        let booleans_len = usize::from(reader.short()?);
+        let numerics_len = usize::from(reader.short()?);
+        let strings_len = usize::from(reader.short()?);
+        // The number of offsets is implied by the counts.
+        reader.short()?;
+        let table_len = usize::from(reader.short()?);
+        let booleans: Vec<bool> = reader
+            .bytes(booleans_len)?
+            .iter()
+            .map(|byte| *byte as i8 > 0)
+            .collect();
+        reader.align();
+        let numerics = (0..numerics_len)
+            .map(|_| reader.number(wide))
+            .collect::<io::Result<Vec<i32>>>()?;
+        let names_len = booleans_len + numerics_len + strings_len;
+        let offsets = reader.offsets(strings_len + names_len)?;
+        let table = reader.bytes(table_len)?;
+        let (value_offsets, name_offsets) = offsets.split_at(strings_len);
+        let mut strings = vec![];
+        // Names start after the last value.
+        let mut base = 0;
+        for offset in value_offsets {
+            let value = string_at(table, *offset)?;
+            if let Some(value) = &value {
+                base = base.max(*offset as usize + value.as_bytes_with_nul().len());
+            }
+            strings.push(value);
+        }
+        let mut names = name_offsets.iter().map(|offset| {
+            usize::try_from(*offset)
+                .ok()
+                .and_then(|offset| table.get(base + offset..))
+                .filter(|name| !name.is_empty())
+                .map(|name| {
+                    let end = name.iter().position(|b| *b == 0).unwrap_or(name.len());
+                    CString::new(&name[..end]).unwrap_or_default()
+                })
+                .unwrap_or_default()
+        });
+        self.ext_booleans = booleans
+            .into_iter()
+            .map(|value| (names.next().unwrap_or_default(), value))
+            .collect();
+        self.ext_numerics = numerics
+            .into_iter()
+            .map(|value| (names.next().unwrap_or_default(), value))
+            .collect();
+        self.ext_strings = strings
+            .into_iter()
+            .map(|value| (names.next().unwrap_or_default(), value))
+            .collect();
+        Ok(())
+    }
+}
+
+fn get<T>(items: &[T], index: u64) -> Option<&T> {
+    usize::try_from(index)
+        .ok()
+        .and_then(|index| items.get(index))
+}
+
+#[cfg(any(test, feature = "pure-rust", target_family = "wasm"))]
+fn invalid<E: Into<Box<dyn std::error::Error + Send + Sync>>>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+/// Returns the NUL terminated string at the offset of the table, or None for negative offsets,
+/// which mark absent and cancelled strings.
+#[cfg(any(test, feature = "pure-rust", target_family = "wasm"))]
+fn string_at(table: &[u8], offset: i16) -> io::Result<Option<CString>> {
+    let offset = match usize::try_from(offset) {
+        Ok(offset) => offset,
+        Err(_) => return Ok(None),
+    };
+    let string = match table.get(offset..) {
+        Some(string) if !string.is_empty() => string,
+        _ => {
+            return Err(invalid(format!(
+                "string offset {} out of the table",
+                offset
+            )))
+        }
+    };
+    let end = string.iter().position(|b| *b == 0).unwrap_or(string.len());
+    Ok(Some(CString::new(&string[..end]).unwrap_or_default()))
+}
+
+/// Reads the fields of the compiled format from the data.
+#[cfg(any(test, feature = "pure-rust", target_family = "wasm"))]
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+#[cfg(any(test, feature = "pure-rust", target_family = "wasm"))]
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        let bytes = self
+            .data
+            .get(self.pos..)
+            .and_then(|rest| rest.get(..len))
+            .ok_or_else(|| invalid("truncated terminfo data"))?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn short(&mut self) -> io::Result<u16> {
+        let bytes = self.bytes(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn offsets(&mut self, count: usize) -> io::Result<Vec<i16>> {
+        let bytes = self.bytes(count * 2)?;
+        Ok(bytes
+            .chunks(2)
+            .map(|pair| i16::from_le_bytes([pair[0], pair[1]]))
+            .collect())
+    }
+
+    fn number(&mut self, wide: bool) -> io::Result<i32> {
+        if wide {
+            let bytes = self.bytes(4)?;
+            Ok(i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        } else {
+            Ok(i32::from(self.short()? as i16))
+        }
+    }
+
+    /// Skips the padding byte at an odd position, tolerating its absence at the end.
+    fn align(&mut self) {
+        if self.pos % 2 == 1 && self.pos < self.data.len() {
+            self.pos += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary::BinaryInfo;
+
+    const FIXTURES: &[(&str, &[u8])] = &[
+        ("dumb", include_bytes!("../fixtures/dumb")),
+        ("screen", include_bytes!("../fixtures/screen")),
+        ("tmux-256color", include_bytes!("../fixtures/tmux-256color")),
+        ("vt100", include_bytes!("../fixtures/vt100")),
+        (
+            "xterm-256color",
+            include_bytes!("../fixtures/xterm-256color"),
+        ),
+    ];
+
+    fn cstring(s: &str) -> CString {
+        CString::new(s).unwrap()
+    }
+
+    /// Asserts that the backends report the same names and capabilities.
+    fn assert_same(expected: &dyn Backend, actual: &dyn Backend, fixture: &str) {
+        assert_eq!(expected.name(), actual.name(), "{}", fixture);
+        assert_eq!(expected.aliases(), actual.aliases(), "{}", fixture);
+        for cap in BooleanCap::ALL.iter() {
+            let (expected, actual) = (expected.boolean(*cap), actual.boolean(*cap));
+            assert_eq!(expected, actual, "{} {:?}", fixture, cap);
+        }
+        for cap in NumericCap::ALL.iter() {
+            let (expected, actual) = (expected.numeric(*cap), actual.numeric(*cap));
+            assert_eq!(expected, actual, "{} {:?}", fixture, cap);
+        }
+        for cap in StringCap::ALL.iter() {
+            let (expected, actual) = (expected.string(*cap), actual.string(*cap));
+            assert_eq!(expected, actual, "{} {:?}", fixture, cap);
+        }
+        assert_eq!(expected.ext_boolean_count(), actual.ext_boolean_count());
+        for i in 0..expected.ext_boolean_count() {
+            assert_eq!(expected.ext_boolean_name(i), actual.ext_boolean_name(i));
+            assert_eq!(expected.ext_boolean(i), actual.ext_boolean(i));
+        }
+        assert_eq!(expected.ext_numeric_count(), actual.ext_numeric_count());
+        for i in 0..expected.ext_numeric_count() {
+            assert_eq!(expected.ext_numeric_name(i), actual.ext_numeric_name(i));
+            assert_eq!(expected.ext_numeric(i), actual.ext_numeric(i));
+        }
+        assert_eq!(expected.ext_string_count(), actual.ext_string_count());
+        for i in 0..expected.ext_string_count() {
+            assert_eq!(expected.ext_string_name(i), actual.ext_string_name(i));
+            assert_eq!(expected.ext_string(i), actual.ext_string(i));
+        }
+    }
+
+    /// Returns the position of the first string offset in data written by to_bytes.
+    fn string_offsets_start(data: &[u8]) -> usize {
+        let info = BinaryInfo::parse(data).unwrap();
+        let start = 12 + info.names_size + info.booleans;
+        start + start % 2 + info.numerics * info.numeric_width()
+    }
+
+    #[test]
+    #[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+    fn parse_matches_unibilium() {
+        for (fixture, data) in FIXTURES {
+            let entry = Entry::parse(data).unwrap();
+            let unibi = crate::unibi::Unibi::from_mem(data).unwrap();
+            assert_same(&unibi, &entry, fixture);
+        }
+    }
+
+    #[test]
+    fn parse_reads_fixtures() {
+        let entry = Entry::parse(FIXTURES[3].1).unwrap();
+        assert_eq!(entry.name(), &*cstring("DEC VT100 (w/advanced video)"));
+        assert_eq!(entry.aliases(), [&*cstring("vt100"), &*cstring("vt100-am")]);
+        assert!(entry.boolean(BooleanCap::AutoRightMargin));
+        assert_eq!(entry.numeric(NumericCap::Columns), 80);
+        assert_eq!(
+            entry.string(StringCap::CursorUp),
+            Some(&*cstring("\x1b[A$<2>"))
+        );
+        let entry = Entry::parse(FIXTURES[4].1).unwrap();
+        assert_eq!(entry.numeric(NumericCap::MaxColors), 256);
+        assert_eq!(entry.numeric(NumericCap::MaxPairs), 0x10000);
+        assert!(entry.ext_boolean_count() > 0);
+        assert!(entry.ext_string_count() > 0);
+    }
+
+    #[test]
+    fn parse_round_trips_through_to_bytes() {
+        for (fixture, data) in FIXTURES {
+            let entry = Entry::parse(data).unwrap();
+            let again = Entry::parse(&entry.to_bytes().unwrap()).unwrap();
+            assert_same(&entry, &again, fixture);
+            assert_eq!(entry, again, "{}", fixture);
+        }
+    }
+
+    #[test]
+    fn parse_reads_32bit_numbers() {
+        let mut entry = Entry::new(cstring("wide"), vec![]);
+        entry.set_numeric(NumericCap::MaxPairs, 0x10000);
+        entry.add_ext_numeric(cstring("U8"), 0x20000);
+        let data = entry.to_bytes().unwrap();
+        assert_eq!(BinaryInfo::parse(&data).unwrap().magic, MAGIC_32BIT);
+        assert_eq!(Entry::parse(&data).unwrap(), entry);
+    }
+
+    #[test]
+    fn parse_finds_extended_names_after_the_values() {
+        let mut entry = Entry::new(cstring("ext"), vec![cstring("alias")]);
+        entry.add_ext_boolean(cstring("AX"), true);
+        entry.add_ext_numeric(cstring("RGB"), 8);
+        entry.ext_strings.push((cstring("Cr"), None));
+        entry.add_ext_string(cstring("Ms"), cstring("\x1b]52;%p1%s;%p2%s\x07"));
+        entry.ext_strings.push((cstring("Se"), None));
+        entry.add_ext_string(cstring("Ss"), cstring("\x1b[%p1%d q"));
+        let parsed = Entry::parse(&entry.to_bytes().unwrap()).unwrap();
+        assert_eq!(parsed, entry);
+    }
+
+    #[test]
+    fn parse_rejects_truncated_data() {
+        for (fixture, data) in FIXTURES {
+            let legacy_size = BinaryInfo::parse(data).unwrap().legacy_size();
+            for len in 0..data.len() {
+                let result = Entry::parse(&data[..len]);
+                if len < legacy_size {
+                    assert!(result.is_err(), "{} truncated to {}", fixture, len);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn parse_rejects_malformed_headers() {
+        let data = FIXTURES[3].1;
+        let mut bad_magic = data.to_vec();
+        bad_magic[0] = 0;
+        assert!(Entry::parse(&bad_magic).is_err());
+
+        // The names section ends with the last byte of the description instead of NUL.
+        let mut unterminated = data.to_vec();
+        let names_len = usize::from(u16::from_le_bytes([data[2], data[3]]));
+        unterminated[12 + names_len - 1] = b'x';
+        assert!(Entry::parse(&unterminated).is_err());
+    }
+
+    #[test]
+    fn parse_rejects_string_offsets_beyond_the_table() {
+        let mut entry = Entry::new(cstring("offsets"), vec![]);
+        entry.set_string(StringCap::Bell, cstring("\x07"));
+        let mut data = entry.to_bytes().unwrap();
+        assert!(Entry::parse(&data).is_ok());
+        let table_size = BinaryInfo::parse(&data).unwrap().string_table_size as u16;
+        let start = string_offsets_start(&data);
+        data[start..start + 2].copy_from_slice(&table_size.to_le_bytes());
+        assert!(Entry::parse(&data).is_err());
+    }
+
+    #[test]
+    fn parse_rejects_extended_string_offsets_beyond_the_table() {
+        let mut entry = Entry::new(cstring("offsets"), vec![]);
+        entry.add_ext_string(cstring("Ss"), cstring("\x1b[%p1%d q"));
+        let mut data = entry.to_bytes().unwrap();
+        let info = BinaryInfo::parse(&data).unwrap();
+        let ext = info.extended.unwrap();
+        // The offset of the value follows the extended header, as there are no booleans or
+        // numerics.
+        let start = info.legacy_size() + info.legacy_size() % 2 + 10;
+        let table_size = ext.string_table_size as u16;
+        data[start..start + 2].copy_from_slice(&table_size.to_le_bytes());
+        assert!(Entry::parse(&data).is_err());
+    }
+}
@@ -0,0 +1,64 @@
+//! Snapshots of the environment variables which affect how a terminal should be used.
+
+use std::collections::HashMap;
+use std::env;
+use std::ffi::{OsStr, OsString};
+
+/// A copy of environment variables, read by the methods of Term which follow conventions like
+/// NO_COLOR. Taking the variables as a value keeps those methods deterministic and lets tests
+/// describe environments without changing the one of the process.
+///
+/// # Examples
+///
+/// ```
+/// use unibilium::env::EnvSnapshot;
+///
+/// let env = EnvSnapshot::new().set("COLORTERM", "truecolor");
+/// assert_eq!(env.get_str("COLORTERM"), Some("truecolor"));
+/// assert_eq!(env.get("NO_COLOR"), None);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct EnvSnapshot {
+    vars: HashMap<OsString, OsString>,
+}
+
+impl EnvSnapshot {
+    /// Creates a snapshot without any variables.
+    pub fn new() -> Self {
+        EnvSnapshot::default()
+    }
+
+    /// Copies the environment variables of the process.
+    pub fn capture() -> Self {
+        EnvSnapshot {
+            vars: env::vars_os().collect(),
+        }
+    }
+
+    /// Sets the variable.
+    pub fn set<K: Into<OsString>, V: Into<OsString>>(mut self, name: K, value: V) -> Self {
+        self.vars.insert(name.into(), value.into());
+        self
+    }
+
+    /// Removes the variable.
+    pub fn remove<K: AsRef<OsStr>>(mut self, name: K) -> Self {
+        self.vars.remove(name.as_ref());
+        self
+    }
+
+    /// Returns the value of the variable, or None if it isn't set.
+    pub fn get<K: AsRef<OsStr>>(&self, name: K) -> Option<&OsStr> {
+        self.vars.get(name.as_ref()).map(OsString::as_os_str)
+    }
+
+    /// Returns the value of the variable, or None if it isn't set or isn't valid Unicode.
+    pub fn get_str<K: AsRef<OsStr>>(&self, name: K) -> Option<&str> {
+        self.get(name).and_then(OsStr::to_str)
+    }
+
+    /// Returns whether the variable is set to a non-empty value, as NO_COLOR expects.
+    pub(crate) fn is_non_empty(&self, name: &str) -> bool {
+        self.get(name).is_some_and(|value| !value.is_empty())
+    }
+}
@@ -0,0 +1,66 @@
+//! How a terminal erases and wraps, which full-screen redrawing depends on.
+
+use crate::cap::BooleanCap;
+use crate::term::Term;
+
+/// The flags describing how erasing and writing at the right margin behave.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct EraseSemantics {
+    /// Erasing fills with the current background color instead of the default one (`bce`), so
+    /// colors should be reset before clearing to get the default background.
+    pub back_color_erase: bool,
+    /// Writing in the last column moves the cursor to the next line (`am`).
+    pub auto_right_margin: bool,
+    /// After writing in the last column, the wrap is delayed until the next character, and a
+    /// newline right after it is ignored (`xenl`).
+    pub eat_newline_glitch: bool,
+    /// Writing in the lower right corner doesn't scroll the screen, from the `LP` extended
+    /// boolean of ncurses.
+    pub lower_right_safe: bool,
+}
+
+impl EraseSemantics {
+    /// Returns whether the cursor moves to the next line as soon as the last column is written,
+    /// so its position is only known after wrapping.
+    pub fn wraps_immediately(&self) -> bool {
+        self.auto_right_margin && !self.eat_newline_glitch
+    }
+
+    /// Returns whether a character can be written in the lower right corner without scrolling
+    /// the screen. Terminals with `xenl` aren't trusted to delay the wrap there, as in curses.
+    pub fn can_write_lower_right(&self) -> bool {
+        !self.auto_right_margin || self.lower_right_safe
+    }
+}
+
+impl Term {
+    /// Returns how the terminal erases and wraps.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let erase = Term::from_term_name("xterm-256color")?.erase_semantics();
+    /// assert!(erase.back_color_erase);
+    /// assert!(!erase.wraps_immediately());
+    /// assert!(!erase.can_write_lower_right());
+    ///
+    /// let erase = Term::from_term_name("vt100")?.erase_semantics();
+    /// assert!(!erase.back_color_erase);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn erase_semantics(&self) -> EraseSemantics {
+        EraseSemantics {
+            back_color_erase: self.has_boolean(BooleanCap::BackColorErase),
+            auto_right_margin: self.has_boolean(BooleanCap::AutoRightMargin),
+            eat_newline_glitch: self.has_boolean(BooleanCap::EatNewlineGlitch),
+            lower_right_safe: self.ext_boolean_value("LP"),
+        }
+    }
+}
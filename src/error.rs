@@ -1,37 +1,254 @@
+use crate::binary::BinaryInfo;
+use crate::path;
+use crate::trace::event;
 use std::error::Error;
+use std::ffi::NulError;
 use std::fmt::{Display, Formatter, Result};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::str::Utf8Error;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+/// An error creating a Term. It converts into Box<dyn Error> with the `?` operator, like the
+/// errors of the constructors used to be.
+#[derive(Debug)]
+#[non_exhaustive]
 pub enum TermError {
-    NotFound(String),
+    /// No terminfo entry exists for the terminal name.
+    NotFound {
+        name: String,
+        /// The errno unibilium reported.
+        errno: i32,
+        /// The files which were tried, in order.
+        searched: Vec<PathBuf>,
+    },
+    /// The TERM environment variable isn't valid Unicode.
+    NotUnicode,
+    /// The terminal name contains a NUL character at the given byte position.
+    InvalidName {
+        name: String,
+        position: usize,
+        source: NulError,
+    },
+    /// The terminfo entry exists, but isn't a valid compiled terminfo file.
+    Malformed { path: PathBuf, detail: String },
+    /// Reading the terminfo entry failed.
+    Io(io::Error),
+}
+
+/// The category of a TermError, for branching on the failure without matching on the fields
+/// of the error or its message. New categories may be added, so matches need a wildcard arm.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum ErrorCode {
+    /// No terminfo entry exists for the terminal name.
+    NotFound,
+    /// The TERM environment variable isn't valid Unicode.
     NotUnicode,
+    /// The terminal name contains a NUL character.
+    InvalidName,
+    /// The terminfo entry isn't a valid compiled terminfo file.
+    Malformed,
+    /// Reading the terminfo entry failed.
+    Io,
 }
 
 impl TermError {
-    pub(crate) fn from_name(name: &str) -> Self {
-        TermError::NotFound(String::from(name))
+    /// Returns the category of the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unibilium::error::ErrorCode;
+    /// use unibilium::Term;
+    ///
+    /// let err = Term::from_term_name("blahblah2234").unwrap_err();
+    /// assert_eq!(err.code(), ErrorCode::NotFound);
+    ///
+    /// let err = Term::from_term_name("vt\0100").unwrap_err();
+    /// assert_eq!(err.code(), ErrorCode::InvalidName);
+    /// ```
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            TermError::NotFound { .. } => ErrorCode::NotFound,
+            TermError::NotUnicode => ErrorCode::NotUnicode,
+            TermError::InvalidName { .. } => ErrorCode::InvalidName,
+            TermError::Malformed { .. } => ErrorCode::Malformed,
+            TermError::Io(_) => ErrorCode::Io,
+        }
+    }
+
+    /// Creates the error for a failed lookup of the terminal name, given the error reported by
+    /// unibilium through errno.
+    pub(crate) fn from_os_error(name: &str, err: io::Error) -> Self {
+        if err.kind() == io::ErrorKind::NotFound {
+            let searched = path::candidate_paths(name);
+            #[cfg(feature = "hashed-db")]
+            let searched = [searched, path::hashed_databases()].concat();
+            event!(DEBUG, name, ?searched, "terminfo entry not found");
+            return TermError::NotFound {
+                name: String::from(name),
+                errno: err.raw_os_error().unwrap_or(0),
+                searched,
+            };
+        }
+        // unibilium only gets to parsing when it could open the file, so a readable entry
+        // means that parsing failed.
+        if let Some(path) = path::find_entry(name) {
+            if let Ok(data) = fs::read(&path) {
+                let detail = malformed_detail(&data, &err);
+                event!(
+                    DEBUG,
+                    path = %path.display(),
+                    bytes = data.len(),
+                    %detail,
+                    "terminfo entry is malformed"
+                );
+                return TermError::Malformed { detail, path };
+            }
+        }
+        TermError::Io(err)
+    }
+
+    /// Creates the error for an entry which was found at the path but failed to parse.
+    #[cfg(feature = "hashed-db")]
+    pub(crate) fn malformed(path: PathBuf, data: &[u8], err: &io::Error) -> Self {
+        let detail = malformed_detail(data, err);
+        event!(DEBUG, path = %path.display(), %detail, "terminfo entry is malformed");
+        TermError::Malformed { detail, path }
     }
 
-    pub(crate) fn from_term_var() -> Self {
+    #[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+    pub(crate) fn from_term_var(err: io::Error) -> Self {
         use std::env::{var, VarError};
 
         match var("TERM") {
-            Ok(value) => TermError::from_name(&value),
-            Err(err) => match err {
-                VarError::NotPresent => TermError::from_name(""),
-                VarError::NotUnicode(_) => TermError::NotUnicode,
-            },
+            Ok(value) => TermError::from_os_error(&value, err),
+            Err(VarError::NotPresent) => TermError::from_os_error("", err),
+            Err(VarError::NotUnicode(_)) => TermError::NotUnicode,
         }
     }
 }
 
-impl Error for TermError {}
+/// Describes what is wrong with the compiled terminfo data unibilium failed to parse.
+fn malformed_detail(data: &[u8], err: &io::Error) -> String {
+    if data.len() < 12 {
+        return format!("truncated header of {} bytes", data.len());
+    }
+    let info = match BinaryInfo::parse_header(data) {
+        Some(info) => info,
+        None => {
+            let magic = u16::from_le_bytes([data[0], data[1]]);
+            return format!("bad magic number {:#o}", magic);
+        }
+    };
+    let expected = info.legacy_size();
+    if data.len() < expected {
+        return format!("truncated to {} of {} bytes", data.len(), expected);
+    }
+    format!("invalid terminfo data: {}", err)
+}
+
+impl Error for TermError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TermError::InvalidName { ref source, .. } => Some(source),
+            TermError::Io(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
 
 impl Display for TermError {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self {
-            TermError::NotFound(ref s) => write!(f, "terminfo not found by name '{}'", s),
+            TermError::NotFound {
+                ref name,
+                ref searched,
+                ..
+            } => {
+                write!(f, "terminfo not found by name '{}'", name)?;
+                for (i, path) in searched.iter().enumerate() {
+                    let separator = if i == 0 { " (searched " } else { ", " };
+                    write!(f, "{}{}", separator, path.display())?;
+                }
+                if !searched.is_empty() {
+                    write!(f, ")")?;
+                }
+                Ok(())
+            }
             TermError::NotUnicode => write!(f, "non unicode string encountered"),
+            TermError::InvalidName {
+                ref name, position, ..
+            } => write!(
+                f,
+                "terminal name '{}' contains NUL at position {}",
+                name.escape_default(),
+                position
+            ),
+            TermError::Malformed {
+                ref path,
+                ref detail,
+            } => write!(f, "malformed terminfo {}: {}", path.display(), detail),
+            TermError::Io(ref err) => write!(f, "failed to load terminfo: {}", err),
+        }
+    }
+}
+
+/// An error reading the name or the value of a capability.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CapError {
+    /// The lower level value doesn't correspond to a capability.
+    InvalidCapability,
+    /// The index of the extended capability is out of range.
+    InvalidIndex(u64),
+    /// The name or the value isn't valid UTF-8.
+    NotUnicode(Utf8Error),
+}
+
+impl Error for CapError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CapError::NotUnicode(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for CapError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            CapError::InvalidCapability => write!(f, "invalid capability value"),
+            CapError::InvalidIndex(index) => {
+                write!(f, "invalid index for extended capability: {}", index)
+            }
+            CapError::NotUnicode(ref err) => write!(f, "non unicode string encountered: {}", err),
+        }
+    }
+}
+
+/// An error parsing a capability value written in the notation of terminfo source files, with
+/// the byte offset of the offending escape.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum UnescapeError {
+    /// The value ends with a backslash or a caret.
+    Incomplete(usize),
+    /// The character after a backslash or a caret doesn't form an escape.
+    InvalidEscape(usize),
+    /// The octal escape is larger than a byte.
+    InvalidOctal(usize),
+}
+
+impl Error for UnescapeError {}
+
+impl Display for UnescapeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            UnescapeError::Incomplete(offset) => write!(f, "incomplete escape at {}", offset),
+            UnescapeError::InvalidEscape(offset) => write!(f, "invalid escape at {}", offset),
+            UnescapeError::InvalidOctal(offset) => {
+                write!(f, "octal escape out of range at {}", offset)
+            }
         }
     }
 }
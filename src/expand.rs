@@ -0,0 +1,127 @@
+//! Expansion of parameterized strings in Rust, which the pure Rust backend uses in place of
+//! `unibi_format`. Unlike CompiledCap it accepts malformed strings, skipping the operations it
+//! can't make sense of like unibilium does.
+
+use crate::compiled::{binary, write_printf, Value};
+use crate::format::{Param, Var, MAX_PARAMS, VAR_COUNT};
+use crate::padding::Segment;
+use crate::param::{self, Token, UnaryOp};
+
+/// Number of values the stack holds. Values pushed onto a full stack are dropped.
+const STACK_SIZE: usize = 123;
+
+/// Expands fmt with the given parameters and variables, passing the output in chunks and the
+/// padding directives as delays to out.
+pub(crate) fn expand(
+    fmt: &[u8],
+    params: &[Param],
+    dynamic: &mut [Var; VAR_COUNT],
+    fixed: &mut [Var; VAR_COUNT],
+    out: &mut dyn FnMut(Segment<'_>),
+) {
+    let mut tokens = vec![];
+    param::tokenize(fmt, |_, token| tokens.push(token), |_| {});
+    let mut regs: [Value; MAX_PARAMS] = Default::default();
+    for (reg, param) in regs.iter_mut().zip(params) {
+        *reg = match param {
+            Param::Num(n) => Value::Num(*n),
+            Param::Str(s) => Value::Str(s.to_bytes().to_vec()),
+        };
+    }
+    let mut stack = vec![];
+    let mut pc = 0;
+    while let Some(token) = tokens.get(pc) {
+        pc += 1;
+        // Popping from an empty stack yields zero, like unibilium does.
+        let mut pop = || stack.pop().unwrap_or_default();
+        match *token {
+            Token::Literal(bytes) => out(Segment::Bytes(bytes)),
+            Token::Delay(delay) => out(Segment::Delay(delay)),
+            Token::Printf(printf) => {
+                let mut buf = vec![];
+                write_printf(&printf, &pop(), &mut buf).expect("Writing to Vec failed");
+                out(Segment::Bytes(&buf));
+            }
+            Token::Char => out(Segment::Bytes(&[pop().num() as u8])),
+            Token::Param(n) => push(&mut stack, regs[usize::from(n) - 1].clone()),
+            Token::SetVar(name) => *var(dynamic, fixed, name) = pop().into(),
+            Token::GetVar(name) => {
+                let value = Value::from(&*var(dynamic, fixed, name));
+                push(&mut stack, value);
+            }
+            Token::CharConst(c) => push(&mut stack, Value::Num(i32::from(c))),
+            Token::IntConst(n) => push(&mut stack, Value::Num(n)),
+            Token::StrLen => {
+                let len = match pop() {
+                    Value::Str(s) => s.len() as i32,
+                    Value::Num(_) => 0,
+                };
+                push(&mut stack, Value::Num(len));
+            }
+            Token::Binary(op) => {
+                let b = pop().num();
+                let a = pop().num();
+                push(&mut stack, Value::Num(binary(op, a, b)));
+            }
+            Token::Unary(op) => {
+                let a = pop().num();
+                let result = match op {
+                    UnaryOp::Not => (a == 0) as i32,
+                    UnaryOp::Complement => !a,
+                };
+                push(&mut stack, Value::Num(result));
+            }
+            Token::Increment => {
+                for reg in regs.iter_mut().take(2) {
+                    if let Value::Num(n) = reg {
+                        *n = n.wrapping_add(1);
+                    }
+                }
+            }
+            Token::If | Token::EndIf => {}
+            Token::Then => {
+                if pop().num() == 0 {
+                    pc = skip_branch(&tokens, pc, true);
+                }
+            }
+            Token::Else => pc = skip_branch(&tokens, pc, false),
+        }
+    }
+}
+
+fn push(stack: &mut Vec<Value>, value: Value) {
+    if stack.len() < STACK_SIZE {
+        stack.push(value);
+    }
+}
+
+/// Returns the variable with the given name, which the lexer only accepts if it is a letter.
+fn var<'v>(
+    dynamic: &'v mut [Var; VAR_COUNT],
+    fixed: &'v mut [Var; VAR_COUNT],
+    name: u8,
+) -> &'v mut Var {
+    if name.is_ascii_lowercase() {
+        &mut dynamic[usize::from(name - b'a')]
+    } else {
+        &mut fixed[usize::from(name - b'A')]
+    }
+}
+
+/// Returns the position after the `%;` ending the current branch, skipping nested
+/// conditionals. With to_else set it stops after a `%e` on the way, which starts the next
+/// branch of a false condition.
+fn skip_branch(tokens: &[Token<'_>], mut pc: usize, to_else: bool) -> usize {
+    let mut depth = 0;
+    while let Some(token) = tokens.get(pc) {
+        pc += 1;
+        match token {
+            Token::If => depth += 1,
+            Token::EndIf if depth == 0 => break,
+            Token::EndIf => depth -= 1,
+            Token::Else if depth == 0 && to_else => break,
+            _ => {}
+        }
+    }
+    pc
+}
@@ -0,0 +1,66 @@
+//! A summary of the features of a terminal.
+
+use crate::cap::StringCap;
+use crate::term::Term;
+
+/// The features of a terminal most applications care about, computed from its standard and
+/// extended capabilities.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct TermFeatures {
+    /// The number of colors, 0 if the terminal doesn't support colors.
+    pub max_colors: u32,
+    /// Whether RGB colors can be used.
+    pub truecolor: bool,
+    /// Whether the terminal reports mouse events.
+    pub mouse: bool,
+    /// Whether the terminal supports bracketed paste.
+    pub bracketed_paste: bool,
+    /// Whether the terminal has an alternate screen.
+    pub alt_screen: bool,
+    /// Whether the terminal supports italics.
+    pub italics: bool,
+    /// Whether the terminal supports curly, dotted and other underline styles.
+    pub styled_underline: bool,
+    /// Whether the window title can be set.
+    pub title: bool,
+    /// Whether the clipboard can be set with the `Ms` extended capability.
+    pub clipboard: bool,
+}
+
+impl Term {
+    /// Returns a summary of the features of the terminal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let features = Term::from_term_name("xterm-256color")?.features();
+    /// assert_eq!(features.max_colors, 256);
+    /// assert!(features.alt_screen);
+    ///
+    /// let features = Term::from_term_name("vt100")?.features();
+    /// assert_eq!(features.max_colors, 0);
+    /// assert!(!features.alt_screen);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn features(&self) -> TermFeatures {
+        TermFeatures {
+            max_colors: self.max_colors().unwrap_or(0),
+            truecolor: self.supports_truecolor(),
+            mouse: self.supports_mouse(),
+            bracketed_paste: self.bracketed_paste().is_some(),
+            alt_screen: self.has_string(StringCap::EnterCaMode),
+            italics: self.has_string(StringCap::EnterItalicsMode),
+            styled_underline: self.ext_string_cstr("Smulx").is_some()
+                || self.ext_boolean_value("Su"),
+            title: self.set_title_sequence("").is_some(),
+            clipboard: self.ext_string_cstr("Ms").is_some(),
+        }
+    }
+}
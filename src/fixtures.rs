@@ -0,0 +1,79 @@
+//! Compiled terminfo entries embedded in the library, enabled by the `fixtures` feature. They
+//! let tests load well-known terminals without depending on the terminfo database of the host,
+//! which differs between systems.
+//!
+//! The entries were compiled from the terminfo database of ncurses 6.4.
+
+use crate::error::TermError;
+use crate::term::Term;
+
+/// An embedded terminfo entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Fixture {
+    Vt100,
+    Xterm256,
+    Screen,
+    Tmux256,
+    Dumb,
+}
+
+impl Fixture {
+    /// All embedded entries.
+    pub const ALL: [Fixture; 5] = [
+        Fixture::Vt100,
+        Fixture::Xterm256,
+        Fixture::Screen,
+        Fixture::Tmux256,
+        Fixture::Dumb,
+    ];
+
+    /// Returns the terminal name of the entry, as used in `TERM`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Fixture::Vt100 => "vt100",
+            Fixture::Xterm256 => "xterm-256color",
+            Fixture::Screen => "screen",
+            Fixture::Tmux256 => "tmux-256color",
+            Fixture::Dumb => "dumb",
+        }
+    }
+
+    /// Returns the entry in the compiled terminfo format.
+    pub fn data(self) -> &'static [u8] {
+        match self {
+            Fixture::Vt100 => include_bytes!("../fixtures/vt100"),
+            Fixture::Xterm256 => include_bytes!("../fixtures/xterm-256color"),
+            Fixture::Screen => include_bytes!("../fixtures/screen"),
+            Fixture::Tmux256 => include_bytes!("../fixtures/tmux-256color"),
+            Fixture::Dumb => include_bytes!("../fixtures/dumb"),
+        }
+    }
+}
+
+/// Loads the embedded entry.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use unibilium::fixtures::{self, Fixture};
+///
+/// let term = fixtures::load(Fixture::Xterm256)?;
+/// assert_eq!(term.max_colors(), Some(256));
+///
+/// for fixture in Fixture::ALL.iter() {
+///     fixtures::load(*fixture)?;
+/// }
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns TermError::Io if unibilium fails to allocate the terminal.
+pub fn load(fixture: Fixture) -> Result<Term, TermError> {
+    Term::from_compiled(fixture.data())
+}
@@ -0,0 +1,945 @@
+//! Expansion of parameterized string capabilities, such as `cup`, into the bytes to send to the
+//! terminal.
+
+use crate::cap::StringCap;
+#[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+use crate::padding::Delay;
+use crate::padding::Segment;
+use crate::term::Term;
+#[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+use std::any::Any;
+use std::ffi::{CStr, CString};
+use std::io::{self, Write};
+use std::ops::Deref;
+#[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+use std::os::raw::c_int;
+use std::os::raw::{c_char, c_void};
+#[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+use std::panic::{self, AssertUnwindSafe};
+#[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+use std::slice;
+#[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+use unibilium_sys::{unibi_out_func, unibi_var_t};
+
+/// The C output callback of `unibi_format`, for builds without unibilium.
+#[cfg(any(feature = "pure-rust", target_family = "wasm"))]
+#[allow(non_camel_case_types)]
+type unibi_out_func = Option<unsafe extern "C" fn(*mut c_void, *const c_char, u64)>;
+
+/// Maximum number of parameters a capability can take.
+pub const MAX_PARAMS: usize = 9;
+
+/// Number of static and of dynamic variables available to a capability.
+pub(crate) const VAR_COUNT: usize = 26;
+
+/// Represents a parameter passed to a parameterized string capability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Param<'a> {
+    Num(i32),
+    Str(&'a CStr),
+}
+
+impl<'a> Param<'a> {
+    /// Creates a numeric parameter.
+    pub fn num(n: i32) -> Self {
+        Param::Num(n)
+    }
+
+    /// Creates a string parameter. Only a few capabilities, like `pfkey`, take those.
+    pub fn str(s: &'a CStr) -> Self {
+        Param::Str(s)
+    }
+
+    #[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+    fn to_unibi(self) -> unibi_var_t {
+        match self {
+            Param::Num(n) => unsafe { unibilium_sys::unibi_var_from_num(n) },
+            // unibilium never writes through string parameters.
+            Param::Str(s) => unsafe {
+                unibilium_sys::unibi_var_from_str(s.as_ptr() as *mut c_char)
+            },
+        }
+    }
+}
+
+/// Represents the value of a variable set by `%P` and read by `%g` in a parameterized string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Var {
+    Num(i32),
+    Str(CString),
+}
+
+impl Var {
+    /// Borrows the variable for passing to unibilium. The result must not outlive self.
+    #[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+    fn to_unibi(&self) -> unibi_var_t {
+        match self {
+            Var::Num(n) => unsafe { unibilium_sys::unibi_var_from_num(*n) },
+            Var::Str(s) => unsafe { unibilium_sys::unibi_var_from_str(s.as_ptr() as *mut c_char) },
+        }
+    }
+
+    /// Copies a variable out of unibilium. The string it points to, if any, must still be alive.
+    #[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+    unsafe fn from_unibi(var: unibi_var_t) -> Self {
+        let s = unibilium_sys::unibi_var_str(var);
+        if s.is_null() {
+            Var::Num(unibilium_sys::unibi_var_num(var))
+        } else {
+            Var::Str(CStr::from_ptr(s).to_owned())
+        }
+    }
+}
+
+impl Default for Var {
+    fn default() -> Self {
+        Var::Num(0)
+    }
+}
+
+/// Holds the static (`A`-`Z`) and dynamic (`a`-`z`) variables of parameterized strings. Reusing
+/// a context across calls preserves the variables, which some entries rely on, e.g. `sgr`
+/// definitions which remember the previous state in static variables.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct VarContext {
+    dynamic: [Var; VAR_COUNT],
+    fixed: [Var; VAR_COUNT],
+}
+
+impl VarContext {
+    /// Creates a context with all variables set to zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the variable with the given name, `A`-`Z` for static and `a`-`z` for dynamic
+    /// variables, or None for any other name.
+    pub fn var(&self, name: char) -> Option<&Var> {
+        match name {
+            'a'..='z' => Some(&self.dynamic[name as usize - 'a' as usize]),
+            'A'..='Z' => Some(&self.fixed[name as usize - 'A' as usize]),
+            _ => None,
+        }
+    }
+
+    /// Sets the variable with the given name, `A`-`Z` for static and `a`-`z` for dynamic
+    /// variables. Returns false if the name is not valid.
+    pub fn set_var(&mut self, name: char, value: Var) -> bool {
+        let var = match name {
+            'a'..='z' => &mut self.dynamic[name as usize - 'a' as usize],
+            'A'..='Z' => &mut self.fixed[name as usize - 'A' as usize],
+            _ => return false,
+        };
+        *var = value;
+        true
+    }
+
+    /// Resets the dynamic variables to zero, keeping the static ones.
+    pub fn clear_dynamic(&mut self) {
+        self.dynamic = Default::default();
+    }
+
+    /// Expands a parameterized string and streams the result into out like format::run, but
+    /// reads and updates the variables of this context.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::ffi::CString;
+    /// use unibilium::format::{Param, Var, VarContext};
+    ///
+    /// let mut vars = VarContext::new();
+    /// let set = CString::new("%p1%PA")?;
+    /// let get = CString::new("%gA%d")?;
+    /// let mut out = vec![];
+    /// vars.run(&set, &[Param::num(7)], &mut out)?;
+    /// vars.run(&get, &[], &mut out)?;
+    /// assert_eq!(out, b"7");
+    /// assert_eq!(vars.var('A'), Some(&Var::Num(7)));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error reported by out. Variables are updated nonetheless.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than MAX_PARAMS parameters are given.
+    pub fn run<W: Write + ?Sized>(
+        &mut self,
+        fmt: &CStr,
+        params: &[Param],
+        out: &mut W,
+    ) -> io::Result<()> {
+        let mut result = Ok(());
+        let mut write = |chunk: &[u8]| {
+            if result.is_ok() {
+                result = out.write_all(chunk);
+            }
+        };
+        self.format_raw(fmt, params, false, &mut write);
+        traced(result)
+    }
+
+    /// Calls format_raw with the variables of this context, which it updates.
+    pub(crate) fn format_raw(
+        &mut self,
+        fmt: &CStr,
+        params: &[Param],
+        keep_padding: bool,
+        out: &mut dyn FnMut(&[u8]),
+    ) {
+        format_raw(
+            fmt,
+            params,
+            &mut self.dynamic,
+            &mut self.fixed,
+            keep_padding,
+            out,
+        );
+    }
+}
+
+/// Returns a zero initialized set of variables.
+pub(crate) fn new_vars() -> [Var; VAR_COUNT] {
+    Default::default()
+}
+
+#[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+/// Context passed through unibilium to the output callbacks.
+struct OutContext<'o> {
+    out: &'o mut dyn FnMut(Segment<'_>),
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+#[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+impl<'o> OutContext<'o> {
+    /// Passes the segment to the Rust closure. Panics must not unwind into C, so they are caught
+    /// here and resumed once unibilium returns.
+    fn forward(&mut self, segment: Segment<'_>) {
+        if self.panic.is_some() {
+            return;
+        }
+        let out = &mut self.out;
+        if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| out(segment))) {
+            self.panic = Some(payload);
+        }
+    }
+}
+
+#[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+/// Forwards output chunks from unibilium to the Rust closure.
+unsafe extern "C" fn out_trampoline(ctx: *mut c_void, buf: *const c_char, len: u64) {
+    if len == 0 {
+        return;
+    }
+    let ctx = &mut *(ctx as *mut OutContext);
+    let chunk = slice::from_raw_parts(buf as *const u8, len as usize);
+    ctx.forward(Segment::Bytes(chunk));
+}
+
+#[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+/// Forwards padding directives from unibilium to the Rust closure as delays, apart from the
+/// output, so that output which looks like a directive isn't taken for one.
+unsafe extern "C" fn pad_trampoline(ctx: *mut c_void, delay: u64, scale: c_int, force: c_int) {
+    let ctx = &mut *(ctx as *mut OutContext);
+    ctx.forward(Segment::Delay(Delay::from_tenths(
+        delay as u32,
+        scale != 0,
+        force != 0,
+    )));
+}
+
+/// Passes through the result of writing expanded output, reporting failures.
+fn traced(result: io::Result<()>) -> io::Result<()> {
+    #[cfg(feature = "tracing")]
+    if let Err(ref err) = result {
+        crate::trace::event!(WARN, error = %err, "writing expanded capability failed");
+    }
+    result
+}
+
+#[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+/// Converts the parameters to the lower level representation, padded with zeros to MAX_PARAMS.
+///
+/// # Panics
+///
+/// Panics if more than MAX_PARAMS parameters are given.
+fn unibi_params(params: &[Param]) -> [unibi_var_t; MAX_PARAMS] {
+    assert!(
+        params.len() <= MAX_PARAMS,
+        "At most {} parameters are supported, got {}",
+        MAX_PARAMS,
+        params.len()
+    );
+    let mut unibi_params = [unsafe { unibilium_sys::unibi_var_from_num(0) }; MAX_PARAMS];
+    for (unibi_param, param) in unibi_params.iter_mut().zip(params) {
+        *unibi_param = param.to_unibi();
+    }
+    unibi_params
+}
+
+/// Expands fmt with the given parameters and variables, passing the output in chunks and the
+/// padding directives as delays to out.
+///
+/// # Panics
+///
+/// Panics if more than MAX_PARAMS parameters are given or if out panics.
+#[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+pub(crate) fn format_segments(
+    fmt: &CStr,
+    params: &[Param],
+    dynamic: &mut [Var; VAR_COUNT],
+    fixed: &mut [Var; VAR_COUNT],
+    out: &mut dyn FnMut(Segment<'_>),
+) {
+    let mut unibi_params = unibi_params(params);
+    let mut unibi_dynamic = [unsafe { unibilium_sys::unibi_var_from_num(0) }; VAR_COUNT];
+    let mut unibi_fixed = unibi_dynamic;
+    for (unibi_var, var) in unibi_dynamic.iter_mut().zip(dynamic.iter()) {
+        *unibi_var = var.to_unibi();
+    }
+    for (unibi_var, var) in unibi_fixed.iter_mut().zip(fixed.iter()) {
+        *unibi_var = var.to_unibi();
+    }
+    let mut ctx = OutContext { out, panic: None };
+    let ctx_ptr = &mut ctx as *mut OutContext as *mut c_void;
+    unsafe {
+        unibilium_sys::unibi_format(
+            unibi_dynamic.as_mut_ptr(),
+            unibi_fixed.as_mut_ptr(),
+            fmt.as_ptr(),
+            unibi_params.as_mut_ptr(),
+            Some(out_trampoline),
+            ctx_ptr,
+            Some(pad_trampoline),
+            ctx_ptr,
+        );
+    }
+    if let Some(payload) = ctx.panic {
+        panic::resume_unwind(payload);
+    }
+    // Strings point either into params or into the current variables, which are all still
+    // alive, so they are copied before the old values are replaced.
+    let new_dynamic: Vec<Var> = unibi_dynamic
+        .iter()
+        .map(|v| unsafe { Var::from_unibi(*v) })
+        .collect();
+    let new_fixed: Vec<Var> = unibi_fixed
+        .iter()
+        .map(|v| unsafe { Var::from_unibi(*v) })
+        .collect();
+    for (var, value) in dynamic.iter_mut().zip(new_dynamic) {
+        *var = value;
+    }
+    for (var, value) in fixed.iter_mut().zip(new_fixed) {
+        *var = value;
+    }
+}
+
+/// Expands fmt with the given parameters and variables, passing the output in chunks and the
+/// padding directives as delays to out.
+///
+/// # Panics
+///
+/// Panics if more than MAX_PARAMS parameters are given or if out panics.
+#[cfg(any(feature = "pure-rust", target_family = "wasm"))]
+pub(crate) fn format_segments(
+    fmt: &CStr,
+    params: &[Param],
+    dynamic: &mut [Var; VAR_COUNT],
+    fixed: &mut [Var; VAR_COUNT],
+    out: &mut dyn FnMut(Segment<'_>),
+) {
+    assert!(
+        params.len() <= MAX_PARAMS,
+        "At most {} parameters are supported, got {}",
+        MAX_PARAMS,
+        params.len()
+    );
+    crate::expand::expand(fmt.to_bytes(), params, dynamic, fixed, out);
+}
+
+/// Expands fmt with the given parameters and variables, passing the output in chunks to out.
+/// Padding directives are written in their textual form if keep_padding is set and dropped
+/// otherwise.
+///
+/// # Panics
+///
+/// Panics if more than MAX_PARAMS parameters are given or if out panics.
+pub(crate) fn format_raw(
+    fmt: &CStr,
+    params: &[Param],
+    dynamic: &mut [Var; VAR_COUNT],
+    fixed: &mut [Var; VAR_COUNT],
+    keep_padding: bool,
+    out: &mut dyn FnMut(&[u8]),
+) {
+    format_segments(fmt, params, dynamic, fixed, &mut |segment| match segment {
+        Segment::Bytes(bytes) => out(bytes),
+        Segment::Delay(delay) if keep_padding => out(delay.to_string().as_bytes()),
+        Segment::Delay(_) => {}
+    });
+}
+
+/// Expands a parameterized string and streams the result into out. Variables set by the string
+/// are discarded afterwards. Padding directives are dropped.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::ffi::CString;
+/// use unibilium::format::{self, Param};
+///
+/// let cup = CString::new("\x1b[%i%p1%d;%p2%dH")?;
+/// let mut out = vec![];
+/// format::run(&cup, &[Param::num(5), Param::num(10)], &mut out)?;
+/// assert_eq!(out, b"\x1b[6;11H");
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns the first error reported by out. Nothing is written after it.
+///
+/// # Panics
+///
+/// Panics if more than MAX_PARAMS parameters are given.
+pub fn run<W: Write + ?Sized>(fmt: &CStr, params: &[Param], out: &mut W) -> io::Result<()> {
+    let mut result = Ok(());
+    let mut write = |chunk: &[u8]| {
+        if result.is_ok() {
+            result = out.write_all(chunk);
+        }
+    };
+    format_raw(
+        fmt,
+        params,
+        &mut new_vars(),
+        &mut new_vars(),
+        false,
+        &mut write,
+    );
+    traced(result)
+}
+
+/// Expands a parameterized string like run, but keeps padding directives in the output so they
+/// can be interpreted later, e.g. by Term::pad. A string parameter which looks like a directive
+/// can't be told apart from one in the output; Term::run_events keeps delays apart instead.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::ffi::CString;
+/// use unibilium::format;
+///
+/// let flash = CString::new("\x1b[?5h$<100/>\x1b[?5l")?;
+/// let mut out = vec![];
+/// format::run_with_padding(&flash, &[], &mut out)?;
+/// assert_eq!(out, b"\x1b[?5h$<100/>\x1b[?5l");
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns the first error reported by out. Nothing is written after it.
+///
+/// # Panics
+///
+/// Panics if more than MAX_PARAMS parameters are given.
+pub fn run_with_padding<W: Write + ?Sized>(
+    fmt: &CStr,
+    params: &[Param],
+    out: &mut W,
+) -> io::Result<()> {
+    let mut result = Ok(());
+    let mut write = |chunk: &[u8]| {
+        if result.is_ok() {
+            result = out.write_all(chunk);
+        }
+    };
+    format_raw(
+        fmt,
+        params,
+        &mut new_vars(),
+        &mut new_vars(),
+        true,
+        &mut write,
+    );
+    traced(result)
+}
+
+/// Expands a parameterized string like run, but passes the output directly to a C callback,
+/// like `unibi_format` does, for emitting into existing C output code. Padding directives are
+/// dropped.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::ffi::CString;
+/// use std::os::raw::{c_char, c_void};
+/// use std::slice;
+/// use unibilium::format::{self, Param};
+///
+/// unsafe extern "C" fn append(ctx: *mut c_void, buf: *const c_char, len: u64) {
+///     let out = &mut *(ctx as *mut Vec<u8>);
+///     out.extend_from_slice(slice::from_raw_parts(buf as *const u8, len as usize));
+/// }
+///
+/// let cup = CString::new("\x1b[%i%p1%d;%p2%dH")?;
+/// let mut out: Vec<u8> = vec![];
+/// let ctx = &mut out as *mut Vec<u8> as *mut c_void;
+/// let params = [Param::num(5), Param::num(10)];
+/// unsafe { format::run_with_callback(&cup, &params, Some(append), ctx) };
+/// assert_eq!(out, b"\x1b[6;11H");
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+///
+/// # Safety
+///
+/// out is called with ctx and the chunks of the output, so it must be safe to call with them.
+/// It must not unwind.
+///
+/// # Panics
+///
+/// Panics if more than MAX_PARAMS parameters are given.
+pub unsafe fn run_with_callback(
+    fmt: &CStr,
+    params: &[Param],
+    out: unibi_out_func,
+    ctx: *mut c_void,
+) {
+    let mut write = |chunk: &[u8]| {
+        if let Some(out) = out {
+            out(ctx, chunk.as_ptr() as *const c_char, chunk.len() as u64);
+        }
+    };
+    format_raw(
+        fmt,
+        params,
+        &mut new_vars(),
+        &mut new_vars(),
+        false,
+        &mut write,
+    );
+}
+
+/// Expands a parameterized string like run, but appends the result to a Vec, which can be reused
+/// between calls to avoid allocating.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::ffi::CString;
+/// use unibilium::format::{self, Param};
+///
+/// let cuu = CString::new("\x1b[%p1%dA")?;
+/// let mut out = Vec::with_capacity(16);
+/// for n in 1..4 {
+///     out.clear();
+///     format::format_into(&cuu, &[Param::num(n)], &mut out);
+/// }
+/// assert_eq!(out, b"\x1b[3A");
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// Panics if more than MAX_PARAMS parameters are given.
+pub fn format_into(fmt: &CStr, params: &[Param], out: &mut Vec<u8>) {
+    format_raw(
+        fmt,
+        params,
+        &mut new_vars(),
+        &mut new_vars(),
+        false,
+        &mut |chunk| out.extend_from_slice(chunk),
+    );
+}
+
+/// Expands a parameterized string like run into a fixed size buffer on the stack. Returns None
+/// if the result doesn't fit into N bytes.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::ffi::CString;
+/// use unibilium::format::{self, Param};
+///
+/// let cup = CString::new("\x1b[%i%p1%d;%p2%dH")?;
+/// let params = [Param::num(5), Param::num(10)];
+/// let out = format::format_to_array::<16>(&cup, &params).unwrap();
+/// assert_eq!(&*out, b"\x1b[6;11H");
+/// assert!(format::format_to_array::<4>(&cup, &params).is_none());
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// Panics if more than MAX_PARAMS parameters are given.
+pub fn format_to_array<const N: usize>(fmt: &CStr, params: &[Param]) -> Option<ArrayOutput<N>> {
+    let mut out = ArrayOutput {
+        bytes: [0; N],
+        len: 0,
+    };
+    let mut overflow = false;
+    let mut write = |chunk: &[u8]| {
+        let end = out.len + chunk.len();
+        if overflow || end > N {
+            overflow = true;
+            return;
+        }
+        out.bytes[out.len..end].copy_from_slice(chunk);
+        out.len = end;
+    };
+    format_raw(
+        fmt,
+        params,
+        &mut new_vars(),
+        &mut new_vars(),
+        false,
+        &mut write,
+    );
+    if overflow {
+        None
+    } else {
+        Some(out)
+    }
+}
+
+/// The output of format_to_array, stored inline. It dereferences to the bytes written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ArrayOutput<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> Deref for ArrayOutput<N> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for ArrayOutput<N> {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl Term {
+    /// Expands the parameterized string capability and passes the output to a C callback, see
+    /// format::run_with_callback. Returns false without calling out if the terminal doesn't
+    /// define the capability.
+    ///
+    /// # Safety
+    ///
+    /// out is called with ctx and the chunks of the output, so it must be safe to call with them.
+    /// It must not unwind.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than MAX_PARAMS parameters are given.
+    pub unsafe fn run_with_callback(
+        &self,
+        cap: StringCap,
+        params: &[Param],
+        out: unibi_out_func,
+        ctx: *mut c_void,
+    ) -> bool {
+        match self.string_cstr(cap) {
+            None => false,
+            Some(fmt) => {
+                run_with_callback(fmt, params, out, ctx);
+                true
+            }
+        }
+    }
+
+    /// Expands the parameterized string capability and appends the result to out, see
+    /// format::format_into. Returns false without writing anything if the terminal doesn't
+    /// define the capability.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::format::Param;
+    /// use unibilium::{StringCap, Term};
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// let mut out = vec![];
+    /// assert!(term.format_into(StringCap::ParmUpCursor, &[Param::num(3)], &mut out));
+    /// assert_eq!(out, b"\x1b[3A");
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than MAX_PARAMS parameters are given.
+    pub fn format_into(&self, cap: StringCap, params: &[Param], out: &mut Vec<u8>) -> bool {
+        match self.string_cstr(cap) {
+            None => false,
+            Some(fmt) => {
+                format_into(fmt, params, out);
+                true
+            }
+        }
+    }
+
+    /// Expands the parameterized string capability into a fixed size buffer, see
+    /// format::format_to_array. Returns None if the terminal doesn't define the capability or
+    /// the result doesn't fit into N bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::format::Param;
+    /// use unibilium::{StringCap, Term};
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// let up = term.format_to_array::<16>(StringCap::ParmUpCursor, &[Param::num(3)]);
+    /// assert_eq!(up.as_deref(), Some(&b"\x1b[3A"[..]));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than MAX_PARAMS parameters are given.
+    pub fn format_to_array<const N: usize>(
+        &self,
+        cap: StringCap,
+        params: &[Param],
+    ) -> Option<ArrayOutput<N>> {
+        format_to_array(self.string_cstr(cap)?, params)
+    }
+
+    /// Expands the parameterized string capability and streams the result into out without
+    /// intermediate allocations. Returns false without writing anything if the terminal doesn't
+    /// define the capability.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::format::Param;
+    /// use unibilium::{StringCap, Term};
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// let mut out = vec![];
+    /// term.run(StringCap::CursorAddress, &[Param::num(5), Param::num(10)], &mut out)?;
+    /// assert!(out.starts_with(b"\x1b[6;11H"));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error reported by out.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than MAX_PARAMS parameters are given.
+    pub fn run<W: Write + ?Sized>(
+        &self,
+        cap: StringCap,
+        params: &[Param],
+        out: &mut W,
+    ) -> io::Result<bool> {
+        match self.string_cstr(cap) {
+            None => Ok(false),
+            Some(fmt) => run(fmt, params, out).map(|_| true),
+        }
+    }
+
+    /// Expands the parameterized string capability and returns the result, or None if the
+    /// terminal doesn't define the capability. Padding directives are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::format::Param;
+    /// use unibilium::{StringCap, Term};
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// let up = term.expand(StringCap::ParmUpCursor, &[Param::num(3)]);
+    /// assert_eq!(up, Some(b"\x1b[3A".to_vec()));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than MAX_PARAMS parameters are given.
+    pub fn expand(&self, cap: StringCap, params: &[Param]) -> Option<Vec<u8>> {
+        let mut out = vec![];
+        let defined = self
+            .run(cap, params, &mut out)
+            .expect("Writing to Vec failed");
+        if defined {
+            Some(out)
+        } else {
+            None
+        }
+    }
+
+    /// Expands the extended string capability with the given name and returns the result, or
+    /// None if the terminal doesn't define it. Padding directives are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::format::Param;
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert_eq!(term.expand_ext("Ss", &[Param::num(2)]), None);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than MAX_PARAMS parameters are given.
+    pub fn expand_ext(&self, name: &str, params: &[Param]) -> Option<Vec<u8>> {
+        let fmt = self.ext_string_cstr(name)?;
+        let mut out = vec![];
+        run(fmt, params, &mut out).expect("Writing to Vec failed");
+        Some(out)
+    }
+
+    /// Expands the parameterized string capability like Term::run, but keeps padding directives
+    /// in the output so they can be interpreted by Term::pad, see format::run_with_padding.
+    /// Returns false without writing anything if the terminal doesn't define the capability.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::{StringCap, Term};
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// let mut out = vec![];
+    /// term.run_with_padding(StringCap::ClearScreen, &[], &mut out)?;
+    /// assert!(out.ends_with(b"$<50>"));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error reported by out.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than MAX_PARAMS parameters are given.
+    pub fn run_with_padding<W: Write + ?Sized>(
+        &self,
+        cap: StringCap,
+        params: &[Param],
+        out: &mut W,
+    ) -> io::Result<bool> {
+        match self.string_cstr(cap) {
+            None => Ok(false),
+            Some(fmt) => run_with_padding(fmt, params, out).map(|_| true),
+        }
+    }
+
+    /// Expands the parameterized string capability like Term::run, but reads and updates the
+    /// variables of the given context. Returns false without writing anything if the terminal
+    /// doesn't define the capability.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::format::{Param, VarContext};
+    /// use unibilium::{StringCap, Term};
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// let mut vars = VarContext::new();
+    /// let mut out = vec![];
+    /// let params = [Param::num(0), Param::num(1)];
+    /// term.run_with(StringCap::SetAttributes, &params, &mut vars, &mut out)?;
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error reported by out.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than MAX_PARAMS parameters are given.
+    pub fn run_with<W: Write + ?Sized>(
+        &self,
+        cap: StringCap,
+        params: &[Param],
+        vars: &mut VarContext,
+        out: &mut W,
+    ) -> io::Result<bool> {
+        match self.string_cstr(cap) {
+            None => Ok(false),
+            Some(fmt) => vars.run(fmt, params, out).map(|_| true),
+        }
+    }
+}
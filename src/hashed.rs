@@ -0,0 +1,389 @@
+//! Lookup of entries in the hashed databases ncurses writes when built with
+//! `--enable-hashed-db`. The `terminfo.cdb` of NetBSD is a different format and isn't read.
+//!
+//! A database is a file named after a search directory with a `.db` suffix, in the hash format
+//! of Berkeley DB 1.85, the dbopen of the BSD libc. Its header is big endian, while the pages
+//! use the byte order the header records. Instead of hashing the name, the lookup reads the
+//! pairs of every bucket, so it doesn't depend on the hash function the database was built with.
+//!
+//! ncurses stores the compiled entry under the names field of the entry, prefixed with a 0 byte,
+//! and stores the names field under each name, prefixed with a 2 byte.
+
+use crate::path;
+use std::cell::Cell;
+use std::convert::TryFrom;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+const MAGIC: u32 = 0x061561;
+
+/// Length of the header fields, up to and including the bitmaps.
+const HEADER_LEN: usize = 260;
+
+/// Overflow addresses keep the page number in the low bits and the split point above them.
+const SPLIT_SHIFT: u32 = 11;
+const SPLIT_MASK: u16 = 0x7ff;
+
+/// Codes in the place of a data offset, which real data offsets are never below.
+const OVFL_PAGE: u16 = 0;
+const PARTIAL_KEY: u16 = 1;
+const FULL_KEY: u16 = 2;
+const FULL_KEY_DATA: u16 = 3;
+const REAL_KEY: u16 = 4;
+
+/// Number of records ncurses follows from a name to the entry.
+const MAX_RECORDS: usize = 3;
+
+/// A key and its data.
+type Pair = (Vec<u8>, Vec<u8>);
+
+/// Returns the first hashed database which has the terminal, along with its compiled entry.
+/// Databases which can't be read are skipped, like ncurses skips them.
+pub(crate) fn find_entry(name: &str) -> Option<(PathBuf, Vec<u8>)> {
+    path::hashed_databases().into_iter().find_map(|path| {
+        let data = fs::read(&path).ok()?;
+        let pairs = Database::parse(&data).and_then(|db| db.pairs()).ok()?;
+        let entry = lookup(&pairs, name)?;
+        Some((path, entry))
+    })
+}
+
+/// Follows the records from the name to the compiled entry.
+fn lookup(pairs: &[Pair], name: &str) -> Option<Vec<u8>> {
+    let mut key = name.as_bytes();
+    for _ in 0..MAX_RECORDS {
+        let (_, data) = pairs.iter().find(|(k, _)| k.as_slice() == key)?;
+        match data.split_first()? {
+            (0, entry) => return Some(entry.to_vec()),
+            (_, names) => key = names,
+        }
+    }
+    None
+}
+
+struct Database<'a> {
+    data: &'a [u8],
+    little_endian: bool,
+    bsize: usize,
+    max_bucket: u32,
+    hdr_pages: u32,
+    spares: [u32; 32],
+    /// Number of pages left to visit. Well-formed databases visit each bucket and each page at
+    /// most once, so running out means that the pages link in a cycle.
+    budget: Cell<usize>,
+}
+
+impl<'a> Database<'a> {
+    fn parse(data: &'a [u8]) -> io::Result<Database<'a>> {
+        if data.len() < HEADER_LEN {
+            return Err(invalid("truncated header"));
+        }
+        let field = |index: usize| {
+            let bytes = [
+                data[index * 4],
+                data[index * 4 + 1],
+                data[index * 4 + 2],
+                data[index * 4 + 3],
+            ];
+            u32::from_be_bytes(bytes)
+        };
+        if field(0) != MAGIC {
+            return Err(invalid("bad magic number"));
+        }
+        if !matches!(field(1), 1 | 2) {
+            return Err(invalid("unsupported version"));
+        }
+        let little_endian = match field(2) {
+            1234 => true,
+            4321 => false,
+            _ => return Err(invalid("unknown byte order")),
+        };
+        let bshift = field(4);
+        if !(4..=16).contains(&bshift) || field(3) != 1 << bshift {
+            return Err(invalid("bad bucket size"));
+        }
+        let bsize = 1 << bshift;
+        let pages = data.len() / bsize;
+        if field(10) as usize > pages {
+            return Err(invalid("more buckets than pages"));
+        }
+        let mut spares = [0; 32];
+        for (i, spare) in spares.iter_mut().enumerate() {
+            *spare = field(17 + i);
+        }
+        Ok(Database {
+            data,
+            little_endian,
+            bsize,
+            max_bucket: field(10),
+            hdr_pages: field(15),
+            spares,
+            budget: Cell::new(2 * (pages + 1)),
+        })
+    }
+
+    /// Returns the key and data pairs of all buckets.
+    fn pairs(&self) -> io::Result<Vec<Pair>> {
+        let mut pairs = vec![];
+        for bucket in 0..=self.max_bucket {
+            let mut page = Some(self.page(self.bucket_page(bucket)?)?);
+            while let Some(current) = page.take() {
+                let count = current.count()?;
+                let mut end = self.bsize;
+                let mut index = 1;
+                while index < count {
+                    let offset = current.get(index)?;
+                    let code = current.get(index + 1)?;
+                    if code >= REAL_KEY {
+                        // The key ends where the previous data starts and the data ends where
+                        // the key starts.
+                        let key = current.slice(offset, end)?.to_vec();
+                        let data = current.slice(code, offset.into())?.to_vec();
+                        pairs.push((key, data));
+                        end = code.into();
+                        index += 2;
+                    } else if code == OVFL_PAGE {
+                        page = Some(self.overflow(offset)?);
+                        break;
+                    } else if index == 1 {
+                        let (pair, next) = self.big_pair(current)?;
+                        pairs.push(pair);
+                        page = next;
+                        break;
+                    } else {
+                        return Err(invalid("big pair after other pairs"));
+                    }
+                }
+            }
+        }
+        Ok(pairs)
+    }
+
+    /// Reads a pair which didn't fit on a page, starting at its first page. Each page holds a
+    /// piece of the pair which extends to the end of the page, and links to the next page.
+    /// Returns the pair and the page after it, if any.
+    fn big_pair(&self, first: Page<'a>) -> io::Result<(Pair, Option<Page<'a>>)> {
+        let mut key = vec![];
+        let mut page = first;
+        while page.get(2)? == PARTIAL_KEY {
+            key.extend_from_slice(page.piece()?);
+            page = self.next(&page)?;
+        }
+        key.extend_from_slice(page.piece()?);
+        let mut data = vec![];
+        let rest = match page.get(2)? {
+            FULL_KEY => true,
+            FULL_KEY_DATA => {
+                // The data starts before the end of the key. It continues on the next page if it
+                // filled this one.
+                let start = page.get(page.count()?)?;
+                data.extend_from_slice(page.slice(start, page.get(1)?.into())?);
+                page.get(page.count()? + 1)? == 0
+            }
+            _ => return Err(invalid("bad big pair code")),
+        };
+        if rest {
+            page = self.next(&page)?;
+            data.extend_from_slice(page.piece()?);
+            while page.get(2)? != FULL_KEY_DATA {
+                page = self.next(&page)?;
+                data.extend_from_slice(page.piece()?);
+            }
+        }
+        let next = if page.count()? > 2 {
+            Some(self.overflow(page.get(3)?)?)
+        } else {
+            None
+        };
+        Ok(((key, data), next))
+    }
+
+    /// Returns the page the page of a big pair links to.
+    fn next(&self, page: &Page<'a>) -> io::Result<Page<'a>> {
+        match page.count()? {
+            count if count >= 2 => self.overflow(page.get(count - 1)?),
+            _ => Err(invalid("big pair without next page")),
+        }
+    }
+
+    fn bucket_page(&self, bucket: u32) -> io::Result<u64> {
+        let spares = if bucket == 0 {
+            0
+        } else {
+            // The spares count the overflow pages allocated before the split point of the bucket.
+            let log2 = 64 - u64::from(bucket).leading_zeros();
+            let spare = self.spares.get(log2 as usize - 1);
+            spare
+                .copied()
+                .ok_or_else(|| invalid("bucket beyond split points"))?
+        };
+        Ok(u64::from(bucket) + u64::from(self.hdr_pages) + u64::from(spares))
+    }
+
+    fn overflow(&self, addr: u16) -> io::Result<Page<'a>> {
+        let split = u32::from(addr) >> SPLIT_SHIFT;
+        let bucket = (1u32 << split) - 1;
+        let page = self.bucket_page(bucket)? + u64::from(addr & SPLIT_MASK);
+        self.page(page)
+    }
+
+    /// Returns the page with the given number. Pages beyond the end of the file were never
+    /// written and are empty.
+    fn page(&self, number: u64) -> io::Result<Page<'a>> {
+        let budget = self.budget.get();
+        if budget == 0 {
+            return Err(invalid("pages link in a cycle"));
+        }
+        self.budget.set(budget - 1);
+        let start = number
+            .checked_mul(self.bsize as u64)
+            .and_then(|start| usize::try_from(start).ok())
+            .unwrap_or(usize::MAX);
+        let bytes = match self.data.get(start..) {
+            Some(rest) if rest.len() >= self.bsize => &rest[..self.bsize],
+            Some(rest) if !rest.is_empty() => return Err(invalid("truncated page")),
+            _ => &[],
+        };
+        Ok(Page {
+            bytes,
+            little_endian: self.little_endian,
+        })
+    }
+}
+
+/// A page, which starts with the number of entries, followed by the entries, the free space
+/// and the offset of the last stored byte. Keys and data are stored from the end of the page.
+struct Page<'a> {
+    bytes: &'a [u8],
+    little_endian: bool,
+}
+
+impl<'a> Page<'a> {
+    fn count(&self) -> io::Result<usize> {
+        if self.bytes.is_empty() {
+            Ok(0)
+        } else {
+            self.get(0).map(usize::from)
+        }
+    }
+
+    fn get(&self, index: usize) -> io::Result<u16> {
+        let bytes = self
+            .bytes
+            .get(index * 2..index * 2 + 2)
+            .ok_or_else(|| invalid("entry beyond the page"))?;
+        let bytes = [bytes[0], bytes[1]];
+        Ok(if self.little_endian {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    }
+
+    fn slice(&self, start: u16, end: usize) -> io::Result<&'a [u8]> {
+        self.bytes
+            .get(usize::from(start)..end)
+            .ok_or_else(|| invalid("offset beyond the page"))
+    }
+
+    /// Returns the piece of a big pair on the page.
+    fn piece(&self) -> io::Result<&'a [u8]> {
+        self.slice(self.get(1)?, self.bytes.len())
+    }
+}
+
+fn invalid(error: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::Backend;
+    use crate::cap::NumericCap;
+    use crate::entry::Entry;
+    use std::ffi::CString;
+
+    /// A database with vt100, dumb and a copy of dumb with a 100 byte names field, written by
+    /// fixtures/terminfo-db.py. Its pages are 64 bytes, so each entry is a big pair, and the
+    /// names field of the copy is a key spanning two pages.
+    const DATABASE: &[u8] = include_bytes!("../fixtures/terminfo.db");
+    const VT100: &[u8] = include_bytes!("../fixtures/vt100");
+    const DUMB: &[u8] = include_bytes!("../fixtures/dumb");
+
+    /// Page of bucket 0, which only links to an overflow page.
+    const BUCKET_0: usize = 8 * 64;
+
+    fn pairs(data: &[u8]) -> io::Result<Vec<Pair>> {
+        Database::parse(data).and_then(|db| db.pairs())
+    }
+
+    #[test]
+    fn bucket_page_skips_the_overflow_pages_of_earlier_split_points() {
+        let db = Database::parse(DATABASE).unwrap();
+        // The bitmap page at split point 1 sits between buckets 1 and 2.
+        let pages: Vec<u64> = (0..4).map(|b| db.bucket_page(b).unwrap()).collect();
+        assert_eq!(pages, [8, 9, 11, 12]);
+    }
+
+    #[test]
+    fn pairs_reads_big_pairs_and_overflow_pages() {
+        let pairs = pairs(DATABASE).unwrap();
+        // vt100 has two names, dumb one and the copy two, besides their names fields.
+        assert_eq!(pairs.len(), 8);
+        let key = b"vt100|vt100-am|DEC VT100 (w/advanced video)";
+        let (_, data) = pairs.iter().find(|(k, _)| k == key).unwrap();
+        assert_eq!(data[0], 0);
+        assert_eq!(&data[1..], VT100);
+    }
+
+    #[test]
+    fn lookup_follows_names_to_entries() {
+        let pairs = pairs(DATABASE).unwrap();
+        assert_eq!(lookup(&pairs, "vt100").as_deref(), Some(VT100));
+        assert_eq!(lookup(&pairs, "vt100-am").as_deref(), Some(VT100));
+        assert_eq!(lookup(&pairs, "dumb").as_deref(), Some(DUMB));
+        let entry = Entry::parse(&lookup(&pairs, "longname-alias").unwrap()).unwrap();
+        let aliases = [
+            CString::new("longname").unwrap(),
+            CString::new("longname-alias").unwrap(),
+        ];
+        assert_eq!(entry.aliases(), [&*aliases[0], &*aliases[1]]);
+        assert_eq!(entry.numeric(NumericCap::Columns), 80);
+        assert_eq!(lookup(&pairs, "vt220"), None);
+    }
+
+    #[test]
+    fn lookup_stops_after_max_records() {
+        let pairs = vec![(b"loop".to_vec(), b"\x02loop".to_vec())];
+        assert_eq!(lookup(&pairs, "loop"), None);
+    }
+
+    #[test]
+    fn parse_rejects_malformed_headers() {
+        let error = |data: &[u8]| Database::parse(data).err().unwrap().to_string();
+        assert_eq!(error(&DATABASE[..HEADER_LEN - 1]), "truncated header");
+        let mut data = DATABASE.to_vec();
+        data[3] ^= 1;
+        assert_eq!(error(&data), "bad magic number");
+        let mut data = DATABASE.to_vec();
+        data[19] += 1;
+        assert_eq!(error(&data), "bad bucket size");
+    }
+
+    #[test]
+    fn pairs_rejects_truncated_pages() {
+        let data = &DATABASE[..DATABASE.len() - 1];
+        assert_eq!(pairs(data).unwrap_err().to_string(), "truncated page");
+    }
+
+    #[test]
+    fn pairs_rejects_cyclic_pages() {
+        // Address 0 is page 0 after bucket 0 at split point 0, which is bucket 0 itself.
+        let mut data = DATABASE.to_vec();
+        data[BUCKET_0 + 2..BUCKET_0 + 4].copy_from_slice(&0u16.to_le_bytes());
+        let error = pairs(&data).unwrap_err();
+        assert_eq!(error.to_string(), "pages link in a cycle");
+    }
+}
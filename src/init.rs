@@ -0,0 +1,93 @@
+//! The sequences initializing and resetting a terminal, as `tput init` and `tput reset` send.
+
+use crate::cap::StringCap;
+use crate::term::Term;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+impl Term {
+    /// Returns the sequence initializing the terminal, as `tput init` sends it: `is1`, `is2`,
+    /// tab stops every 8 columns (see set_tabs), the contents of the file named by `if`, and
+    /// `is3`, each skipped if the terminal doesn't define it. Tab stops are set for the number of
+    /// columns of the entry, or 80.
+    ///
+    /// The program named by `iprog` isn't run; callers which want it can run it first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("xterm")?;
+    /// let init = term.init_sequences()?;
+    /// assert!(init.starts_with(b"\x1b[!p\x1b[?3;4l\x1b[4l\x1b>\r\x1b[3g        \x1bH"));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of reading the file named by `if`.
+    pub fn init_sequences(&self) -> io::Result<Vec<u8>> {
+        self.init_or_reset(false)
+    }
+
+    /// Returns the sequence resetting the terminal to a sane state, as `tput reset` sends it:
+    /// `rs1`, `rs2`, tab stops, the contents of the file named by `rf`, and `rs3`. Each missing
+    /// reset capability is replaced by the corresponding init capability, like `is1` for `rs1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("xterm")?;
+    /// let reset = term.reset_sequences()?;
+    /// assert!(reset.starts_with(b"\x1bc\x1b[!p\x1b[?3;4l\x1b[4l\x1b>\r"));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of reading the file named by `rf` or `if`.
+    pub fn reset_sequences(&self) -> io::Result<Vec<u8>> {
+        self.init_or_reset(true)
+    }
+
+    fn init_or_reset(&self, reset: bool) -> io::Result<Vec<u8>> {
+        let pick = |init: StringCap, reset_cap: StringCap| {
+            if reset && self.string_cstr(reset_cap).is_some() {
+                reset_cap
+            } else {
+                init
+            }
+        };
+        let mut out = vec![];
+        for cap in [
+            pick(StringCap::Init1string, StringCap::Reset1string),
+            pick(StringCap::Init2string, StringCap::Reset2string),
+        ]
+        .iter()
+        {
+            out.extend(self.expand(*cap, &[]).unwrap_or_default());
+        }
+        let columns = self.columns().unwrap_or(80);
+        out.extend(self.set_tabs(8, columns).unwrap_or_default());
+        if let Some(file) = self.string_cstr(pick(StringCap::InitFile, StringCap::ResetFile)) {
+            out.extend(fs::read(Path::new(&*file.to_string_lossy()))?);
+        }
+        let cap = pick(StringCap::Init3string, StringCap::Reset3string);
+        out.extend(self.expand(cap, &[]).unwrap_or_default());
+        Ok(out)
+    }
+}
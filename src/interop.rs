@@ -0,0 +1,142 @@
+//! Conversions between Term and the Database of the pure Rust `terminfo` crate, enabled by the
+//! `terminfo` feature.
+//!
+//! Both name the terminal differently: unibilium keeps the last field of the terminfo names
+//! section as the name and the others as aliases, while `terminfo` keeps the first field as the
+//! name, the last one as the description and the others as aliases. The conversions map the
+//! fields so the names section stays the same.
+
+use crate::cap::{BooleanCap, NumericCap, StringCap};
+use crate::entry::Entry;
+use crate::error::TermError;
+use crate::term::Term;
+use ::terminfo::capability::Value;
+use ::terminfo::Database;
+use std::convert::TryFrom;
+use std::ffi::CString;
+
+/// Copies all defined standard and extended capabilities into a Database.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use terminfo::capability::Value;
+/// use terminfo::Database;
+/// use unibilium::Term;
+///
+/// let term = Term::from_term_name("vt100")?;
+/// let db = Database::from(&term);
+/// assert_eq!(db.name(), "vt100");
+/// assert_eq!(db.raw("cols"), Some(&Value::Number(80)));
+/// assert_eq!(db.raw("am"), Some(&Value::True));
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+impl<'a> From<&'a Term> for Database {
+    fn from(term: &'a Term) -> Self {
+        let (description, aliases) = term.name_and_aliases();
+        let names: Vec<String> = aliases
+            .into_iter()
+            .map(|alias| alias.to_string_lossy().into_owned())
+            .collect();
+        let description = description.to_string_lossy().into_owned();
+        let mut builder = Database::new();
+        match names.split_first() {
+            Some((name, aliases)) => {
+                builder.name(name.as_str()).aliases(aliases.iter().cloned());
+            }
+            None => {
+                builder.name(description.as_str());
+            }
+        }
+        builder.description(description);
+        for cap in BooleanCap::ALL.iter() {
+            if term.has_boolean(*cap) {
+                builder.raw(cap.short_name(), ());
+            }
+        }
+        for cap in NumericCap::ALL.iter() {
+            if let Some(value) = term.numeric_value(*cap) {
+                builder.raw(cap.short_name(), value);
+            }
+        }
+        for cap in StringCap::ALL.iter() {
+            if let Some(value) = term.string_cstr(*cap) {
+                builder.raw(cap.short_name(), value.to_bytes());
+            }
+        }
+        for b in term.ext_booleans().filter(|b| b.supported()) {
+            builder.raw(b.name_lossy(), ());
+        }
+        for n in term.ext_numerics().filter(|n| n.value() >= 0) {
+            builder.raw(n.name_lossy(), n.value());
+        }
+        for s in term.ext_strings() {
+            if let Some(value) = s.value_bytes() {
+                builder.raw(s.name_lossy(), value);
+            }
+        }
+        builder.build().expect("The name is always set")
+    }
+}
+
+/// Creates a Term with the standard capabilities of the Database. The Database can't list its
+/// extended capabilities, so they aren't copied. String values containing NUL are left out,
+/// since unibilium can't represent them.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::convert::TryFrom;
+/// use terminfo::Database;
+/// use unibilium::Term;
+///
+/// let mut builder = Database::new();
+/// builder.name("foo").description("foo terminal");
+/// builder.raw("colors", 16).raw("cuu1", "\x1b[A");
+/// let db = builder.build().unwrap();
+///
+/// let term = Term::try_from(&db)?;
+/// assert_eq!(term.max_colors(), Some(16));
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+impl<'a> TryFrom<&'a Database> for Term {
+    type Error = TermError;
+
+    fn try_from(db: &'a Database) -> Result<Self, TermError> {
+        let to_cstring = |s: &str| CString::new(s).unwrap_or_default();
+        let names = Some(db.name())
+            .into_iter()
+            .chain(db.aliases().iter().map(String::as_str))
+            .map(to_cstring)
+            .collect();
+        let mut entry = Entry::new(to_cstring(db.description()), names);
+        for cap in BooleanCap::ALL.iter() {
+            if let Some(Value::True) = db.raw(cap.short_name()) {
+                entry.set_boolean(*cap, true);
+            }
+        }
+        for cap in NumericCap::ALL.iter() {
+            if let Some(Value::Number(value)) = db.raw(cap.short_name()) {
+                entry.set_numeric(*cap, *value);
+            }
+        }
+        for cap in StringCap::ALL.iter() {
+            if let Some(Value::String(value)) = db.raw(cap.short_name()) {
+                if let Ok(value) = CString::new(value.clone()) {
+                    entry.set_string(*cap, value);
+                }
+            }
+        }
+        Term::from_entry(&entry)
+    }
+}
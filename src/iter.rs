@@ -0,0 +1,183 @@
+//! Iterators over the capabilities of a terminal.
+//!
+//! They produce the capabilities lazily from a range of indexes, so stopping at the first match
+//! doesn't pay for the rest of the list.
+
+use crate::boolean::{Boolean, ExtBoolean};
+use crate::cap::{BooleanCap, NumericCap, StringCap};
+use crate::capability::Capability;
+use crate::numeric::{ExtNumeric, Numeric};
+use crate::string::{ExtString, String};
+use crate::term::Term;
+use std::iter::FusedIterator;
+
+macro_rules! cap_iter {
+    (
+        $(#[$meta:meta])*
+        $iter:ident => $item:ident, $index:ty, |$i:ident, $term:ident| $make:expr;
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone)]
+        pub struct $iter<'a> {
+            front: $index,
+            back: $index,
+            term: &'a Term,
+        }
+
+        impl<'a> $iter<'a> {
+            pub(crate) fn new(front: $index, back: $index, term: &'a Term) -> Self {
+                $iter { front, back, term }
+            }
+        }
+
+        impl<'a> Iterator for $iter<'a> {
+            type Item = $item<'a>;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                if self.front == self.back {
+                    return None;
+                }
+                let ($i, $term) = (self.front, self.term);
+                self.front += 1;
+                Some($make)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = (self.back - self.front) as usize;
+                (len, Some(len))
+            }
+
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                let len = (self.back - self.front) as usize;
+                self.front += n.min(len) as $index;
+                self.next()
+            }
+        }
+
+        impl<'a> DoubleEndedIterator for $iter<'a> {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                if self.front == self.back {
+                    return None;
+                }
+                self.back -= 1;
+                let ($i, $term) = (self.back, self.term);
+                Some($make)
+            }
+        }
+
+        impl<'a> ExactSizeIterator for $iter<'a> {}
+
+        impl<'a> FusedIterator for $iter<'a> {}
+    };
+}
+
+cap_iter! {
+    /// An iterator over the standard boolean capabilities of a terminal, see Term::booleans.
+    Booleans => Boolean, usize,
+        |i, term| Boolean::from_cap(BooleanCap::ALL[i], term);
+}
+
+cap_iter! {
+    /// An iterator over the extended boolean capabilities of a terminal, see
+    /// Term::ext_booleans.
+    ExtBooleans => ExtBoolean, u64, |i, term| ExtBoolean::from_index_unchecked(i, term);
+}
+
+cap_iter! {
+    /// An iterator over the standard numeric capabilities of a terminal, see Term::numerics.
+    Numerics => Numeric, usize,
+        |i, term| Numeric::from_cap(NumericCap::ALL[i], term);
+}
+
+cap_iter! {
+    /// An iterator over the extended numeric capabilities of a terminal, see
+    /// Term::ext_numerics.
+    ExtNumerics => ExtNumeric, u64, |i, term| ExtNumeric::from_index_unchecked(i, term);
+}
+
+cap_iter! {
+    /// An iterator over the standard string capabilities of a terminal, see Term::strings.
+    Strings => String, usize,
+        |i, term| String::from_cap(StringCap::ALL[i], term);
+}
+
+cap_iter! {
+    /// An iterator over the extended string capabilities of a terminal, see Term::ext_strings.
+    ExtStrings => ExtString, u64, |i, term| ExtString::from_index_unchecked(i, term);
+}
+
+/// An iterator over all capabilities of a terminal, see Term::capabilities. It yields the
+/// standard and then the extended capabilities of each kind, booleans first and strings last.
+#[derive(Debug, Clone)]
+pub struct Capabilities<'a> {
+    booleans: Booleans<'a>,
+    ext_booleans: ExtBooleans<'a>,
+    numerics: Numerics<'a>,
+    ext_numerics: ExtNumerics<'a>,
+    strings: Strings<'a>,
+    ext_strings: ExtStrings<'a>,
+}
+
+impl<'a> Capabilities<'a> {
+    pub(crate) fn new(term: &'a Term) -> Self {
+        Capabilities {
+            booleans: term.booleans(),
+            ext_booleans: term.ext_booleans(),
+            numerics: term.numerics(),
+            ext_numerics: term.ext_numerics(),
+            strings: term.strings(),
+            ext_strings: term.ext_strings(),
+        }
+    }
+}
+
+impl<'a> Iterator for Capabilities<'a> {
+    type Item = Capability<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.booleans
+            .next()
+            .map(Capability::Boolean)
+            .or_else(|| self.ext_booleans.next().map(Capability::ExtBoolean))
+            .or_else(|| self.numerics.next().map(Capability::Numeric))
+            .or_else(|| self.ext_numerics.next().map(Capability::ExtNumeric))
+            .or_else(|| self.strings.next().map(Capability::String))
+            .or_else(|| self.ext_strings.next().map(Capability::ExtString))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.booleans.len()
+            + self.ext_booleans.len()
+            + self.numerics.len()
+            + self.ext_numerics.len()
+            + self.strings.len()
+            + self.ext_strings.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a> DoubleEndedIterator for Capabilities<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.ext_strings
+            .next_back()
+            .map(Capability::ExtString)
+            .or_else(|| self.strings.next_back().map(Capability::String))
+            .or_else(|| self.ext_numerics.next_back().map(Capability::ExtNumeric))
+            .or_else(|| self.numerics.next_back().map(Capability::Numeric))
+            .or_else(|| self.ext_booleans.next_back().map(Capability::ExtBoolean))
+            .or_else(|| self.booleans.next_back().map(Capability::Boolean))
+    }
+}
+
+impl<'a> ExactSizeIterator for Capabilities<'a> {}
+
+impl<'a> FusedIterator for Capabilities<'a> {}
+
+impl<'a> IntoIterator for &'a Term {
+    type Item = Capability<'a>;
+    type IntoIter = Capabilities<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Capabilities::new(self)
+    }
+}
@@ -0,0 +1,496 @@
+//! The keys of a terminal and the sequences they send.
+
+use crate::cap::{BooleanCap, StringCap};
+use crate::term::Term;
+use std::ops::{BitAnd, BitOr, BitOrAssign};
+
+/// The highest function key number terminfo has a capability for.
+pub const MAX_FUNCTION_KEY: u8 = 63;
+
+/// A key with a terminfo capability describing the sequence it sends.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Insert,
+    Delete,
+    Backspace,
+    BackTab,
+    Enter,
+    Begin,
+    /// The prefix of mouse events.
+    Mouse,
+    /// A function key, from 0 to MAX_FUNCTION_KEY. Function keys above 12 are usually the first
+    /// 12 with modifiers, e.g. 13 is shift+F1 on xterm.
+    F(u8),
+}
+
+/// The keys other than function keys, with their capabilities.
+const NAMED_KEYS: [(Key, StringCap); 15] = [
+    (Key::Up, StringCap::KeyUp),
+    (Key::Down, StringCap::KeyDown),
+    (Key::Left, StringCap::KeyLeft),
+    (Key::Right, StringCap::KeyRight),
+    (Key::Home, StringCap::KeyHome),
+    (Key::End, StringCap::KeyEnd),
+    (Key::PageUp, StringCap::KeyPpage),
+    (Key::PageDown, StringCap::KeyNpage),
+    (Key::Insert, StringCap::KeyIc),
+    (Key::Delete, StringCap::KeyDc),
+    (Key::Backspace, StringCap::KeyBackspace),
+    (Key::BackTab, StringCap::KeyBtab),
+    (Key::Enter, StringCap::KeyEnter),
+    (Key::Begin, StringCap::KeyBeg),
+    (Key::Mouse, StringCap::KeyMouse),
+];
+
+impl Key {
+    /// Returns the capability describing the sequence the key sends, or None for function keys
+    /// above MAX_FUNCTION_KEY.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unibilium::keys::Key;
+    /// use unibilium::StringCap;
+    ///
+    /// assert_eq!(Key::Up.capability(), Some(StringCap::KeyUp));
+    /// assert_eq!(Key::F(13).capability(), Some(StringCap::KeyF13));
+    /// assert_eq!(Key::F(64).capability(), None);
+    /// ```
+    pub fn capability(self) -> Option<StringCap> {
+        match self {
+            Key::F(n) if n <= MAX_FUNCTION_KEY => StringCap::from_name(&format!("kf{}", n)),
+            Key::F(_) => None,
+            key => NAMED_KEYS
+                .iter()
+                .find(|(named, _)| *named == key)
+                .map(|(_, cap)| *cap),
+        }
+    }
+
+    /// Returns an iterator over all keys, the function keys last.
+    pub(crate) fn all() -> impl Iterator<Item = Key> {
+        NAMED_KEYS
+            .iter()
+            .map(|(key, _)| *key)
+            .chain((0..=MAX_FUNCTION_KEY).map(Key::F))
+    }
+}
+
+/// A set of modifiers held down with a key.
+///
+/// # Examples
+///
+/// ```
+/// use unibilium::keys::Modifiers;
+///
+/// let mods = Modifiers::CTRL | Modifiers::SHIFT;
+/// assert!(mods.contains(Modifiers::CTRL));
+/// assert!(!mods.contains(Modifiers::ALT));
+/// ```
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Modifiers(u8);
+
+impl Modifiers {
+    pub const SHIFT: Modifiers = Modifiers(1);
+    pub const ALT: Modifiers = Modifiers(1 << 1);
+    pub const CTRL: Modifiers = Modifiers(1 << 2);
+    pub const META: Modifiers = Modifiers(1 << 3);
+
+    /// Returns the empty set of modifiers.
+    pub fn empty() -> Self {
+        Modifiers(0)
+    }
+
+    /// Returns true if no modifier is set.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns true if all modifiers of other are set.
+    pub fn contains(self, other: Modifiers) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns the modifiers encoded in the parameter of xterm key sequences, which is one plus
+    /// the sum of 1 for shift, 2 for alt, 4 for control and 8 for meta. Returns None for values
+    /// outside of 1 to 16.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unibilium::keys::Modifiers;
+    ///
+    /// assert_eq!(Modifiers::from_xterm(6), Some(Modifiers::CTRL | Modifiers::SHIFT));
+    /// assert_eq!(Modifiers::from_xterm(17), None);
+    /// ```
+    pub fn from_xterm(value: u8) -> Option<Self> {
+        if (1..=16).contains(&value) {
+            Some(Modifiers(value - 1))
+        } else {
+            None
+        }
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Modifiers(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for Modifiers {
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl BitAnd for Modifiers {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        Modifiers(self.0 & other.0)
+    }
+}
+
+/// The keys of the xterm style extended capabilities for modified keys, by name prefix.
+const MODIFIED_KEYS: [(&str, Key); 10] = [
+    ("kUP", Key::Up),
+    ("kDN", Key::Down),
+    ("kLFT", Key::Left),
+    ("kRIT", Key::Right),
+    ("kHOM", Key::Home),
+    ("kEND", Key::End),
+    ("kPRV", Key::PageUp),
+    ("kNXT", Key::PageDown),
+    ("kIC", Key::Insert),
+    ("kDC", Key::Delete),
+];
+
+/// Parses the name of an xterm style extended capability for a modified key, like `kUP5` for
+/// control+up, into the key and the modifiers. Returns None for other names, including those
+/// without modifiers.
+///
+/// # Examples
+///
+/// ```
+/// use unibilium::keys::{parse_modified_key, Key, Modifiers};
+///
+/// assert_eq!(parse_modified_key("kRIT3"), Some((Key::Right, Modifiers::ALT)));
+/// assert_eq!(parse_modified_key("kHOM7"), Some((Key::Home, Modifiers::CTRL | Modifiers::ALT)));
+/// assert_eq!(parse_modified_key("kUP"), None);
+/// ```
+pub fn parse_modified_key(name: &str) -> Option<(Key, Modifiers)> {
+    MODIFIED_KEYS.iter().find_map(|(prefix, key)| {
+        let digits = name.strip_prefix(prefix)?;
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let modifiers = Modifiers::from_xterm(digits.parse().ok()?)?;
+        if modifiers.is_empty() {
+            return None;
+        }
+        Some((*key, modifiers))
+    })
+}
+
+impl Term {
+    /// Returns the sequence the key sends, or None if the terminal doesn't define it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::keys::Key;
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert_eq!(term.key_sequence(Key::Up), Some(&b"\x1bOA"[..]));
+    /// assert_eq!(term.key_sequence(Key::Home), None);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn key_sequence(&self, key: Key) -> Option<&[u8]> {
+        self.string_cstr(key.capability()?)
+            .map(|value| value.to_bytes())
+    }
+
+    /// Returns an iterator over the keys the terminal defines, with the sequences they send.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::keys::Key;
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// for (key, sequence) in term.keys() {
+    ///     println!("{:?}: {:?}", key, sequence);
+    /// }
+    /// assert!(term.keys().any(|(key, _)| key == Key::F(1)));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn keys(&self) -> impl Iterator<Item = (Key, &[u8])> + '_ {
+        Key::all().filter_map(move |key| Some((key, self.key_sequence(key)?)))
+    }
+
+    /// Returns an iterator over the function keys the terminal defines, from `kf0` to `kf63`,
+    /// with their numbers and the sequences they send.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("xterm")?;
+    /// let keys: Vec<_> = term.function_keys().collect();
+    /// assert_eq!(keys[0], (1, &b"\x1bOP"[..]));
+    /// assert!(keys.iter().any(|(number, _)| *number == 13));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn function_keys(&self) -> impl Iterator<Item = (u8, &[u8])> + '_ {
+        (0..=MAX_FUNCTION_KEY)
+            .filter_map(move |number| Some((number, self.key_sequence(Key::F(number))?)))
+    }
+
+    /// Returns the keys with modifiers the terminal defines through xterm style extended
+    /// capabilities, like `kUP5`, with the sequences they send.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::keys::{Key, Modifiers};
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("xterm")?;
+    /// let keys = term.modified_keys();
+    /// assert!(keys.contains(&(Key::Up, Modifiers::CTRL, &b"\x1b[1;5A"[..])));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn modified_keys(&self) -> Vec<(Key, Modifiers, &[u8])> {
+        self.ext_strings()
+            .filter_map(|s| {
+                let (key, modifiers) = parse_modified_key(&s.name_lossy())?;
+                Some((key, modifiers, s.value_bytes()?))
+            })
+            .collect()
+    }
+
+    /// Looks up the input bytes among the key sequences of the terminal, see KeyTrie::lookup.
+    ///
+    /// It builds a KeyTrie on every call, so parsers should build one with KeyTrie::new and
+    /// keep it instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::keys::{Key, KeyMatch};
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert_eq!(term.key_lookup(b"\x1bOP"), KeyMatch::Exact(Key::F(1)));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn key_lookup(&self, input: &[u8]) -> KeyMatch {
+        KeyTrie::new(self).lookup(input)
+    }
+}
+
+/// The result of looking up input bytes among the key sequences.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum KeyMatch {
+    /// The bytes are the sequence of the key.
+    Exact(Key),
+    /// The bytes are the beginning of the sequence of at least one key, so more input is needed.
+    Prefix,
+    /// The bytes neither are nor begin a key sequence.
+    None,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    key: Option<Key>,
+    children: Vec<(u8, usize)>,
+}
+
+/// A prefix tree of the key sequences of a terminal, for matching input against them.
+#[derive(Debug, Clone)]
+pub struct KeyTrie {
+    nodes: Vec<Node>,
+}
+
+impl KeyTrie {
+    /// Builds the prefix tree from the keys the terminal defines. When several keys send the
+    /// same sequence, the first one returned by Term::keys wins.
+    pub fn new(term: &Term) -> Self {
+        let mut trie = KeyTrie {
+            nodes: vec![Node::default()],
+        };
+        for (key, sequence) in term.keys() {
+            trie.insert(key, sequence);
+        }
+        trie
+    }
+
+    fn insert(&mut self, key: Key, sequence: &[u8]) {
+        if sequence.is_empty() {
+            return;
+        }
+        let mut node = 0;
+        for byte in sequence {
+            node = match self.child(node, *byte) {
+                Some(child) => child,
+                None => {
+                    self.nodes.push(Node::default());
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children.push((*byte, child));
+                    child
+                }
+            };
+        }
+        if self.nodes[node].key.is_none() {
+            self.nodes[node].key = Some(key);
+        }
+    }
+
+    fn child(&self, node: usize, byte: u8) -> Option<usize> {
+        self.nodes[node]
+            .children
+            .iter()
+            .find(|(b, _)| *b == byte)
+            .map(|(_, child)| *child)
+    }
+
+    /// Looks up the input bytes among the key sequences. A sequence which is also the beginning
+    /// of a longer one, like a lone escape, is an exact match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::keys::{Key, KeyMatch, KeyTrie};
+    /// use unibilium::Term;
+    ///
+    /// let trie = KeyTrie::new(&Term::from_term_name("vt100")?);
+    /// assert_eq!(trie.lookup(b"\x1bOA"), KeyMatch::Exact(Key::Up));
+    /// assert_eq!(trie.lookup(b"\x1bO"), KeyMatch::Prefix);
+    /// assert_eq!(trie.lookup(b"a"), KeyMatch::None);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn lookup(&self, input: &[u8]) -> KeyMatch {
+        if input.is_empty() {
+            return KeyMatch::Prefix;
+        }
+        let mut node = 0;
+        for byte in input {
+            node = match self.child(node, *byte) {
+                Some(child) => child,
+                None => return KeyMatch::None,
+            };
+        }
+        match self.nodes[node].key {
+            Some(key) => KeyMatch::Exact(key),
+            None => KeyMatch::Prefix,
+        }
+    }
+}
+
+/// How keys pressed with the meta (alt) modifier arrive as input.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum AltEncoding {
+    /// The key is sent after an ESC byte, e.g. ESC `a` for alt+a.
+    EscPrefix,
+    /// The key is sent with its 8th bit set, e.g. 0xe1 for alt+a.
+    EighthBit,
+}
+
+/// The meta key capabilities of a terminal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MetaMode {
+    /// Whether the terminal has a meta key setting the 8th bit (`km`).
+    pub has_meta_key: bool,
+    /// The sequence turning meta mode on (`smm`), in which the meta key sets the 8th bit.
+    pub on: Option<Vec<u8>>,
+    /// The sequence turning meta mode off (`rmm`).
+    pub off: Option<Vec<u8>>,
+}
+
+impl MetaMode {
+    /// Returns how the meta modifier is encoded, given whether meta mode was turned on with
+    /// the `on` sequence. A terminal with a meta key but without `smm` always sets the 8th bit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::keys::AltEncoding;
+    /// use unibilium::Term;
+    ///
+    /// let meta = Term::from_term_name("xterm")?.meta_mode();
+    /// assert_eq!(meta.on, Some(b"\x1b[?1034h".to_vec()));
+    /// assert_eq!(meta.encoding(true), AltEncoding::EighthBit);
+    /// assert_eq!(meta.encoding(false), AltEncoding::EscPrefix);
+    ///
+    /// let meta = Term::from_term_name("vt100")?.meta_mode();
+    /// assert_eq!(meta.encoding(true), AltEncoding::EscPrefix);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn encoding(&self, meta_mode_on: bool) -> AltEncoding {
+        if self.has_meta_key && (meta_mode_on || self.on.is_none()) {
+            AltEncoding::EighthBit
+        } else {
+            AltEncoding::EscPrefix
+        }
+    }
+}
+
+impl Term {
+    /// Returns the meta key capabilities of the terminal.
+    pub fn meta_mode(&self) -> MetaMode {
+        MetaMode {
+            has_meta_key: self.has_boolean(BooleanCap::HasMetaKey),
+            on: self.expand(StringCap::MetaOn, &[]),
+            off: self.expand(StringCap::MetaOff, &[]),
+        }
+    }
+}
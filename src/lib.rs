@@ -70,14 +70,111 @@
 //! #     Ok(())
 //! # }
 //! ```
+//!
+//! # Features
+//!
+//! * `ffi` (default): links the unibilium C library to read entries and expand capabilities.
+//! * `fixtures`: embeds a few compiled entries for hermetic tests, see the fixtures module.
+//! * `hashed-db`: also looks up entries in the hashed `.db` databases ncurses can be built to
+//!   write with `--enable-hashed-db`. The `terminfo.cdb` of NetBSD isn't supported.
+//! * `ioctl`: reads the size of the tty in Term::current_size.
+//! * `proptest`: strategies generating random terminals, see the arbitrary module.
+//! * `pure-rust`: reads entries and expands capabilities in Rust, without the C library. It
+//!   replaces `ffi`, which has to be turned off with `default-features = false`, since a build
+//!   has exactly one backend. Term::as_ptr, Term::from_raw and Term::into_raw are unavailable.
+//!   WebAssembly targets always use this backend, as unibilium isn't built for them.
+//! * `serde`: implements `Serialize` for the capabilities.
+//! * `termios`: reads the output speed of a tty in Term::output_speed.
+//! * `terminfo`: converts between Term and the Database of the `terminfo` crate.
+//! * `tracing`: emits tracing events about loading entries and expanding capabilities.
 
+pub mod acs;
+pub mod alert;
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
+pub mod attributes;
+pub mod binary;
 pub mod boolean;
+pub mod cache;
+pub mod cap;
+pub mod capability;
+pub mod clipboard;
+pub mod color;
+pub mod compat;
+pub mod compiled;
+pub mod database;
+pub mod diff;
+pub mod dump;
+pub mod env;
+pub mod erase;
 pub mod error;
+pub mod features;
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
+pub mod format;
+pub mod iter;
+pub mod keys;
+pub mod lint;
+pub mod mouse;
+pub mod multiplexer;
 pub mod numeric;
+pub mod padding;
+pub mod param;
+pub mod paste;
+pub mod provider;
+pub mod recording;
+pub mod report;
+pub mod shape;
+pub mod shared;
+pub mod size;
+pub mod stats;
+pub mod status;
 pub mod string;
 pub mod term;
+pub mod testing;
+pub mod version;
+#[cfg(windows)]
+pub mod windows;
+
+mod backend;
+mod chars;
+mod cursor;
+mod entry;
+#[cfg(any(feature = "pure-rust", target_family = "wasm"))]
+mod expand;
+#[cfg(feature = "hashed-db")]
+mod hashed;
+mod init;
+#[cfg(feature = "terminfo")]
+mod interop;
+mod merge;
+mod path;
+mod screen;
+mod scroll;
+#[cfg(feature = "serde")]
+mod ser;
+#[cfg(all(feature = "termios", unix))]
+mod speed;
+mod tabs;
+mod title;
+mod tput;
+mod trace;
+#[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+mod unibi;
+
+#[cfg(not(any(feature = "ffi", feature = "pure-rust", target_family = "wasm")))]
+compile_error!("either the `ffi` or the `pure-rust` feature must be enabled");
+
+#[cfg(all(feature = "ffi", feature = "pure-rust"))]
+compile_error!(
+    "the `ffi` and `pure-rust` features are exclusive, disable the default features to use \
+     `pure-rust`"
+);
 
-pub use boolean::{Boolean, ExtBoolean};
-pub use numeric::{ExtNumeric, Numeric};
-pub use string::{ExtString, String};
+pub use boolean::{Boolean, BooleanValue, ExtBoolean};
+pub use cap::{BooleanCap, NumericCap, StringCap};
+pub use capability::Capability;
+pub use numeric::{ExtNumeric, Numeric, NumericValue};
+pub use string::{ExtString, String, StringValue};
 pub use term::Term;
+pub use version::version;
@@ -0,0 +1,285 @@
+//! Checks for common problems in terminfo entries.
+
+use crate::cap::{BooleanCap, CapId, NumericCap, StringCap};
+use crate::format::{Param, MAX_PARAMS};
+use crate::padding::{self, Segment};
+use crate::param::{self, Diagnostic};
+use crate::term::Term;
+use std::fmt;
+
+/// Represents a problem found in a terminfo entry.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Problem {
+    /// `sgr` doesn't turn on the attribute the individual capability turns on, when given the
+    /// parameter for that attribute.
+    SgrMismatch(StringCap),
+    /// Attributes can be turned on, but not off with `sgr0`.
+    MissingSgr0,
+    /// `cols` or `lines` is 0.
+    ZeroSize(NumericCap),
+    /// A capability is defined without one it depends on, like `smcup` without `rmcup`.
+    /// unibilium only keeps cancelled numeric capabilities apart from absent ones, so
+    /// cancelled is always false for other kinds.
+    MissingDependency {
+        cap: CapId,
+        requires: CapId,
+        cancelled: bool,
+    },
+    /// The `%` program of a string capability is malformed.
+    MalformedParam {
+        cap: StringCap,
+        diagnostic: Diagnostic,
+    },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::SgrMismatch(cap) => write!(
+                f,
+                "sgr doesn't match {} for the same attribute",
+                cap.short_name()
+            ),
+            Problem::MissingSgr0 => write!(f, "attributes can't be turned off without sgr0"),
+            Problem::ZeroSize(cap) => write!(f, "{} is 0", cap.short_name()),
+            Problem::MissingDependency {
+                cap,
+                requires,
+                cancelled,
+            } => write!(
+                f,
+                "{} requires {}, which is {}",
+                short_name(*cap),
+                short_name(*requires),
+                if *cancelled { "cancelled" } else { "absent" }
+            ),
+            Problem::MalformedParam { cap, diagnostic } => {
+                write!(f, "{}: {}", cap.short_name(), diagnostic)
+            }
+        }
+    }
+}
+
+/// Returns the short name of the capability.
+fn short_name(cap: CapId) -> &'static str {
+    match cap {
+        CapId::Boolean(cap) => cap.short_name(),
+        CapId::Numeric(cap) => cap.short_name(),
+        CapId::String(cap) => cap.short_name(),
+    }
+}
+
+/// The attribute capabilities with the position of their parameter to `sgr`, starting at 0.
+const SGR_ATTRIBUTES: [(StringCap, usize); 9] = [
+    (StringCap::EnterStandoutMode, 0),
+    (StringCap::EnterUnderlineMode, 1),
+    (StringCap::EnterReverseMode, 2),
+    (StringCap::EnterBlinkMode, 3),
+    (StringCap::EnterDimMode, 4),
+    (StringCap::EnterBoldMode, 5),
+    (StringCap::EnterSecureMode, 6),
+    (StringCap::EnterProtectedMode, 7),
+    (StringCap::EnterAltCharsetMode, 8),
+];
+
+/// Pairs of a capability and one it depends on.
+const DEPENDENCIES: [(CapId, CapId); 14] = [
+    (
+        CapId::String(StringCap::EnterCaMode),
+        CapId::String(StringCap::ExitCaMode),
+    ),
+    (
+        CapId::String(StringCap::EnterStandoutMode),
+        CapId::String(StringCap::ExitStandoutMode),
+    ),
+    (
+        CapId::String(StringCap::EnterUnderlineMode),
+        CapId::String(StringCap::ExitUnderlineMode),
+    ),
+    (
+        CapId::String(StringCap::EnterAltCharsetMode),
+        CapId::String(StringCap::ExitAltCharsetMode),
+    ),
+    (
+        CapId::String(StringCap::EnterInsertMode),
+        CapId::String(StringCap::ExitInsertMode),
+    ),
+    (
+        CapId::String(StringCap::EnterAmMode),
+        CapId::String(StringCap::ExitAmMode),
+    ),
+    (
+        CapId::String(StringCap::SetAForeground),
+        CapId::Numeric(NumericCap::MaxColors),
+    ),
+    (
+        CapId::String(StringCap::SetABackground),
+        CapId::Numeric(NumericCap::MaxColors),
+    ),
+    (
+        CapId::String(StringCap::SetForeground),
+        CapId::Numeric(NumericCap::MaxColors),
+    ),
+    (
+        CapId::String(StringCap::SetBackground),
+        CapId::Numeric(NumericCap::MaxColors),
+    ),
+    (
+        CapId::String(StringCap::InitializeColor),
+        CapId::Boolean(BooleanCap::CanChange),
+    ),
+    (
+        CapId::String(StringCap::InitializePair),
+        CapId::Numeric(NumericCap::MaxPairs),
+    ),
+    (
+        CapId::String(StringCap::SetColorPair),
+        CapId::Numeric(NumericCap::MaxPairs),
+    ),
+    (
+        CapId::Numeric(NumericCap::InitTabs),
+        CapId::String(StringCap::Tab),
+    ),
+];
+
+/// Checks the terminal for common problems and returns all of them, or an empty vector if
+/// there are none.
+///
+/// The `%` programs are checked with param::lint. unibilium doesn't know how many parameters
+/// each capability takes, so references to parameters beyond the ones it takes aren't
+/// reported. The user capabilities `u0` to `u9` are skipped, since by convention some of them
+/// hold scanf formats, like `u6`, rather than `%` programs.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use unibilium::{lint, Term};
+///
+/// assert!(lint::check(&Term::from_term_name("xterm")?).is_empty());
+/// assert!(lint::check(&Term::from_term_name("vt100")?).is_empty());
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+pub fn check(term: &Term) -> Vec<Problem> {
+    let mut problems = vec![];
+    check_sgr(term, &mut problems);
+    for cap in [NumericCap::Columns, NumericCap::Lines].iter() {
+        if term.numeric_value(*cap) == Some(0) {
+            problems.push(Problem::ZeroSize(*cap));
+        }
+    }
+    for (cap, requires) in DEPENDENCIES.iter() {
+        if is_defined(term, *cap) && !is_defined(term, *requires) {
+            let cancelled = match *requires {
+                CapId::Numeric(n) => term.numeric_raw(n) == -2,
+                _ => false,
+            };
+            problems.push(Problem::MissingDependency {
+                cap: *cap,
+                requires: *requires,
+                cancelled,
+            });
+        }
+    }
+    for cap in StringCap::ALL.iter().filter(|cap| !is_user(**cap)) {
+        if let Some(value) = term.string_cstr(*cap) {
+            for diagnostic in param::lint(value.to_bytes(), MAX_PARAMS) {
+                problems.push(Problem::MalformedParam {
+                    cap: *cap,
+                    diagnostic,
+                });
+            }
+        }
+    }
+    problems
+}
+
+/// Returns whether the capability is one of `u0` to `u9`.
+fn is_user(cap: StringCap) -> bool {
+    let name = cap.short_name().as_bytes();
+    name.len() == 2 && name[0] == b'u' && name[1].is_ascii_digit()
+}
+
+/// Returns whether the terminal sets or defines the capability.
+fn is_defined(term: &Term, cap: CapId) -> bool {
+    match cap {
+        CapId::Boolean(cap) => term.has_boolean(cap),
+        CapId::Numeric(cap) => term.has_numeric(cap),
+        CapId::String(cap) => term.has_string(cap),
+    }
+}
+
+/// Checks `sgr` against the individual attribute capabilities, and that `sgr0` is defined if
+/// any of them is.
+fn check_sgr(term: &Term, problems: &mut Vec<Problem>) {
+    let has_attributes = term.has_string(StringCap::SetAttributes)
+        || SGR_ATTRIBUTES.iter().any(|(cap, _)| term.has_string(*cap));
+    if has_attributes && !term.has_string(StringCap::ExitAttributeMode) {
+        problems.push(Problem::MissingSgr0);
+    }
+    let sgr = |on: Option<usize>| {
+        let mut params = [Param::num(0); MAX_PARAMS];
+        if let Some(index) = on {
+            params[index] = Param::num(1);
+        }
+        term.expand(StringCap::SetAttributes, &params)
+            .map(|out| strip_padding(&out))
+    };
+    let base = match sgr(None) {
+        Some(base) => base,
+        None => return,
+    };
+    for (cap, index) in SGR_ATTRIBUTES.iter() {
+        let individual = match term.expand(*cap, &[]) {
+            Some(out) => strip_padding(&out),
+            None => continue,
+        };
+        let with_attr = sgr(Some(*index)).unwrap_or_default();
+        let (expected, actual) = (sgr_params(&individual), sgr_params(&with_attr));
+        let consistent = if expected.is_empty() || actual.is_empty() {
+            with_attr != base
+        } else {
+            expected.iter().all(|param| actual.contains(param))
+        };
+        if !consistent {
+            problems.push(Problem::SgrMismatch(*cap));
+        }
+    }
+}
+
+/// Returns the output without its delays.
+fn strip_padding(output: &[u8]) -> Vec<u8> {
+    padding::segments(output)
+        .filter_map(|segment| match segment {
+            Segment::Bytes(bytes) => Some(bytes),
+            _ => None,
+        })
+        .flatten()
+        .copied()
+        .collect()
+}
+
+/// Returns the parameters of all SGR control sequences, like `\x1b[0;1m`, in the output.
+fn sgr_params(output: &[u8]) -> Vec<&[u8]> {
+    let mut params = vec![];
+    let mut rest = output;
+    while let Some(start) = rest.windows(2).position(|w| w == b"\x1b[") {
+        rest = &rest[start + 2..];
+        let end = rest
+            .iter()
+            .position(|c| !(c.is_ascii_digit() || *c == b';' || *c == b':'))
+            .unwrap_or(rest.len());
+        if rest.get(end) == Some(&b'm') {
+            params.extend(rest[..end].split(|c| *c == b';').map(|p| match p {
+                b"" => &b"0"[..],
+                p => p,
+            }));
+        }
+        rest = &rest[end..];
+    }
+    params
+}
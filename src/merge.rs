@@ -0,0 +1,276 @@
+//! Three-way merging of terminfo entries, re-exported from the term module.
+
+use crate::cache::CachedTerm;
+use crate::cap::{NumericCap, StringCap};
+use crate::error::TermError;
+use crate::term::Term;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
+
+/// A capability both sides of a merge changed to different values. A value is None if the
+/// side doesn't define the capability.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Conflict {
+    Numeric {
+        cap: NumericCap,
+        base: Option<i32>,
+        ours: Option<i32>,
+        theirs: Option<i32>,
+    },
+    String {
+        cap: StringCap,
+        base: Option<Vec<u8>>,
+        ours: Option<Vec<u8>>,
+        theirs: Option<Vec<u8>>,
+    },
+    ExtNumeric {
+        name: String,
+        base: Option<i32>,
+        ours: Option<i32>,
+        theirs: Option<i32>,
+    },
+    ExtString {
+        name: String,
+        base: Option<Vec<u8>>,
+        ours: Option<Vec<u8>>,
+        theirs: Option<Vec<u8>>,
+    },
+}
+
+impl Conflict {
+    /// Returns the short name of the standard capability, or the name of the extended one.
+    pub fn name(&self) -> &str {
+        match self {
+            Conflict::Numeric { cap, .. } => cap.short_name(),
+            Conflict::String { cap, .. } => cap.short_name(),
+            Conflict::ExtNumeric { name, .. } | Conflict::ExtString { name, .. } => name,
+        }
+    }
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} was changed differently on both sides", self.name())
+    }
+}
+
+/// Error returned by merge3.
+#[derive(Debug)]
+pub enum MergeError {
+    /// Both sides changed some capabilities to different values.
+    Conflicts(Vec<Conflict>),
+    /// The merged capabilities couldn't be turned into a Term.
+    Term(TermError),
+}
+
+impl Error for MergeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MergeError::Conflicts(_) => None,
+            MergeError::Term(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeError::Conflicts(conflicts) => {
+                write!(f, "{} conflicting capabilities", conflicts.len())?;
+                for (i, conflict) in conflicts.iter().enumerate() {
+                    write!(f, "{} {}", if i == 0 { ":" } else { "," }, conflict.name())?;
+                }
+                Ok(())
+            }
+            MergeError::Term(err) => write!(f, "Failed to create merged terminal: {}", err),
+        }
+    }
+}
+
+impl From<TermError> for MergeError {
+    fn from(err: TermError) -> Self {
+        MergeError::Term(err)
+    }
+}
+
+/// Merges the changes ours and theirs made to base, e.g. local patches to a terminfo entry and
+/// an update of the entry upstream. A capability changed on one side only takes the changed
+/// value, and one changed the same way on both sides takes that value. The merged terminal
+/// keeps the name and aliases of ours.
+///
+/// Absent and cancelled capabilities are treated the same. Booleans never conflict, since a
+/// boolean changed on both sides was changed the same way.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use unibilium::term::{self, Conflict, MergeError};
+/// use unibilium::{StringCap, Term};
+///
+/// let base = Term::from_term_name("vt100")?;
+/// let ours = Term::from_term_name("vt102")?;
+/// let merged = term::merge3(&base, &ours, &base)?;
+/// assert_eq!(merged.cache(), ours.cache());
+///
+/// let theirs = Term::from_term_name("xterm")?;
+/// let merged = term::merge3(&base, &ours, &theirs)?;
+/// assert_eq!(merged.max_colors(), Some(8));
+///
+/// let ours = Term::from_term_name("linux")?;
+/// match term::merge3(&base, &ours, &theirs) {
+///     Err(MergeError::Conflicts(conflicts)) => match &conflicts[0] {
+///         Conflict::String { cap, .. } => assert_eq!(*cap, StringCap::ClearScreen),
+///         conflict => panic!("unexpected conflict {}", conflict),
+///     },
+///     _ => panic!("expected conflicts"),
+/// }
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns MergeError::Conflicts listing every capability both sides changed to different
+/// values, sorted by kind and name.
+pub fn merge3(base: &Term, ours: &Term, theirs: &Term) -> Result<Term, MergeError> {
+    let (b, o, t) = (base.cache(), ours.cache(), theirs.cache());
+    let mut conflicts = vec![];
+    let merged = CachedTerm {
+        booleans: merge_sets(&b.booleans, &o.booleans, &t.booleans),
+        numerics: merge_maps(
+            &b.numerics,
+            &o.numerics,
+            &t.numerics,
+            |cap, [base, ours, theirs]| {
+                conflicts.push(Conflict::Numeric {
+                    cap,
+                    base,
+                    ours,
+                    theirs,
+                })
+            },
+        ),
+        strings: merge_maps(
+            &b.strings,
+            &o.strings,
+            &t.strings,
+            |cap, [base, ours, theirs]| {
+                conflicts.push(Conflict::String {
+                    cap,
+                    base,
+                    ours,
+                    theirs,
+                })
+            },
+        ),
+        ext_booleans: merge_sets(&b.ext_booleans, &o.ext_booleans, &t.ext_booleans),
+        ext_numerics: merge_maps(
+            &b.ext_numerics,
+            &o.ext_numerics,
+            &t.ext_numerics,
+            |name, [base, ours, theirs]| {
+                conflicts.push(Conflict::ExtNumeric {
+                    name,
+                    base,
+                    ours,
+                    theirs,
+                })
+            },
+        ),
+        ext_strings: merge_maps(
+            &b.ext_strings,
+            &o.ext_strings,
+            &t.ext_strings,
+            |name, [base, ours, theirs]| {
+                conflicts.push(Conflict::ExtString {
+                    name,
+                    base,
+                    ours,
+                    theirs,
+                })
+            },
+        ),
+    };
+    if conflicts.is_empty() {
+        let (name, aliases) = ours.name_and_aliases();
+        Ok(merged.to_term(name, &aliases)?)
+    } else {
+        Err(MergeError::Conflicts(conflicts))
+    }
+}
+
+/// Returns the merged value, or None if both sides changed it differently.
+fn merge_value<V: Clone + PartialEq>(
+    base: Option<&V>,
+    ours: Option<&V>,
+    theirs: Option<&V>,
+) -> Option<Option<V>> {
+    if ours == theirs || theirs == base {
+        Some(ours.cloned())
+    } else if ours == base {
+        Some(theirs.cloned())
+    } else {
+        None
+    }
+}
+
+/// Merges sets of set booleans.
+fn merge_sets<K: Clone + Eq + Hash + Ord>(
+    base: &HashSet<K>,
+    ours: &HashSet<K>,
+    theirs: &HashSet<K>,
+) -> HashSet<K> {
+    let keys: BTreeSet<&K> = base.iter().chain(ours).chain(theirs).collect();
+    keys.into_iter()
+        .filter(|key| {
+            let flag = |set: &HashSet<K>| Some(set.contains(*key));
+            merge_value(
+                flag(base).as_ref(),
+                flag(ours).as_ref(),
+                flag(theirs).as_ref(),
+            ) == Some(Some(true))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Merges maps of defined values, calling conflict with the key and the values of base, ours
+/// and theirs for each key changed differently on both sides, in key order.
+fn merge_maps<K, V, F>(
+    base: &HashMap<K, V>,
+    ours: &HashMap<K, V>,
+    theirs: &HashMap<K, V>,
+    mut conflict: F,
+) -> HashMap<K, V>
+where
+    K: Clone + Eq + Hash + Ord,
+    V: Clone + PartialEq,
+    F: FnMut(K, [Option<V>; 3]),
+{
+    let keys: BTreeSet<&K> = base
+        .keys()
+        .chain(ours.keys())
+        .chain(theirs.keys())
+        .collect();
+    let mut merged = HashMap::new();
+    for key in keys {
+        let values = (base.get(key), ours.get(key), theirs.get(key));
+        match merge_value(values.0, values.1, values.2) {
+            Some(Some(value)) => {
+                merged.insert(key.clone(), value);
+            }
+            Some(None) => {}
+            None => conflict(
+                key.clone(),
+                [values.0.cloned(), values.1.cloned(), values.2.cloned()],
+            ),
+        }
+    }
+    merged
+}
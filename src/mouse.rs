@@ -0,0 +1,111 @@
+//! Mouse support of a terminal.
+
+use crate::cap::StringCap;
+use crate::format::Param;
+use crate::term::Term;
+
+/// The mouse events a terminal reports.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum MouseMode {
+    /// Button presses and releases (xterm mode 1000).
+    Click,
+    /// Button presses and releases, and motion while a button is pressed (xterm mode 1002).
+    Drag,
+    /// Button presses and releases, and all motion (xterm mode 1003).
+    Motion,
+}
+
+impl MouseMode {
+    /// Returns the number of the xterm private mode reporting the events.
+    fn private_mode(self) -> u16 {
+        match self {
+            MouseMode::Click => 1000,
+            MouseMode::Drag => 1002,
+            MouseMode::Motion => 1003,
+        }
+    }
+}
+
+/// The sequences turning mouse reporting on and off.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct MouseSequences {
+    pub enable: Vec<u8>,
+    pub disable: Vec<u8>,
+}
+
+impl Term {
+    /// Returns whether the terminal reports mouse events, i.e. whether it defines `kmous` or the
+    /// `XM` extended capability.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// assert!(Term::from_term_name("xterm")?.supports_mouse());
+    /// assert!(!Term::from_term_name("vt100")?.supports_mouse());
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn supports_mouse(&self) -> bool {
+        self.has_string(StringCap::KeyMouse) || self.ext_string_cstr("XM").is_some()
+    }
+
+    /// Returns the sequences turning reporting of the given mouse events on and off, or None if
+    /// the terminal doesn't report mouse events.
+    ///
+    /// The `XM` extended capability is used when available, since it also selects how the
+    /// terminal encodes the events. Otherwise the conventional xterm private modes are used,
+    /// along with the SGR encoding (mode 1006) when `kmous` is the prefix of that encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::mouse::MouseMode;
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("xterm-256color")?;
+    /// let sequences = term.mouse_mode_sequences(MouseMode::Drag).unwrap();
+    /// assert_eq!(sequences.enable, b"\x1b[?1006;1000h\x1b[?1002h".to_vec());
+    /// assert_eq!(sequences.disable, b"\x1b[?1002l\x1b[?1006;1000l".to_vec());
+    ///
+    /// let term = Term::from_term_name("tmux")?;
+    /// let sequences = term.mouse_mode_sequences(MouseMode::Click).unwrap();
+    /// assert_eq!(sequences.enable, b"\x1b[?1000h".to_vec());
+    /// assert_eq!(sequences.disable, b"\x1b[?1000l".to_vec());
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn mouse_mode_sequences(&self, mode: MouseMode) -> Option<MouseSequences> {
+        // XM turns on click reporting, so only other modes need to be added to it.
+        let (mut enable, mut disable, add_mode) = match (
+            self.expand_ext("XM", &[Param::num(1)]),
+            self.expand_ext("XM", &[Param::num(0)]),
+        ) {
+            (Some(enable), Some(disable)) => (enable, disable, mode != MouseMode::Click),
+            _ => {
+                let kmous = self.string_cstr(StringCap::KeyMouse)?;
+                if kmous.to_bytes() == b"\x1b[<" {
+                    (b"\x1b[?1006h".to_vec(), b"\x1b[?1006l".to_vec(), true)
+                } else {
+                    (vec![], vec![], true)
+                }
+            }
+        };
+        if add_mode {
+            enable.extend(format!("\x1b[?{}h", mode.private_mode()).into_bytes());
+            let mut off = format!("\x1b[?{}l", mode.private_mode()).into_bytes();
+            off.extend(disable);
+            disable = off;
+        }
+        Some(MouseSequences { enable, disable })
+    }
+}
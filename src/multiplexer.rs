@@ -0,0 +1,135 @@
+//! Detecting terminal multiplexers like tmux and GNU screen, and the terminal they run in.
+
+use crate::error::TermError;
+use crate::term::Term;
+use crate::trace::event;
+use std::env;
+use std::process::Command;
+
+/// A terminal multiplexer, which draws in an outer terminal and provides its own terminal to
+/// the programs running in it.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Multiplexer {
+    Tmux,
+    Screen,
+}
+
+impl Multiplexer {
+    /// Returns the name of the multiplexer program.
+    pub fn name(self) -> &'static str {
+        match self {
+            Multiplexer::Tmux => "tmux",
+            Multiplexer::Screen => "screen",
+        }
+    }
+
+    /// Returns the environment variable the multiplexer sets for the programs running in it.
+    pub fn env_var(self) -> &'static str {
+        match self {
+            Multiplexer::Tmux => "TMUX",
+            Multiplexer::Screen => "STY",
+        }
+    }
+
+    /// Returns the multiplexer whose entries are named like the terminal name, e.g.
+    /// `tmux-256color` or `screen.xterm-256color`.
+    fn from_term_name(name: &str) -> Option<Multiplexer> {
+        [Multiplexer::Tmux, Multiplexer::Screen]
+            .iter()
+            .copied()
+            .find(|multiplexer| name.starts_with(multiplexer.name()))
+    }
+
+    /// Returns the multiplexer whose environment variable is set.
+    fn from_env() -> Option<Multiplexer> {
+        [Multiplexer::Tmux, Multiplexer::Screen]
+            .iter()
+            .copied()
+            .find(|multiplexer| env::var_os(multiplexer.env_var()).is_some_and(|v| !v.is_empty()))
+    }
+}
+
+impl Term {
+    /// Returns the multiplexer the terminal belongs to, from the names of its entry or else from
+    /// the TMUX and STY environment variables.
+    ///
+    /// The environment variables describe the terminal of the current process, so they are only
+    /// meaningful for the Term created by Term::from_env. They catch the common setup of a
+    /// multiplexer configured with the TERM of the outer terminal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::multiplexer::Multiplexer;
+    /// use unibilium::Term;
+    ///
+    /// std::env::remove_var("TMUX");
+    /// std::env::remove_var("STY");
+    /// let vt100 = Term::from_term_name("vt100")?;
+    /// assert_eq!(vt100.multiplexer(), None);
+    /// let tmux = Term::from_term_name("tmux-256color")?;
+    /// assert_eq!(tmux.multiplexer(), Some(Multiplexer::Tmux));
+    ///
+    /// std::env::set_var("STY", "1234.pts-0.host");
+    /// assert_eq!(vt100.multiplexer(), Some(Multiplexer::Screen));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn multiplexer(&self) -> Option<Multiplexer> {
+        self.aliases()
+            .iter()
+            .find_map(|name| Multiplexer::from_term_name(name))
+            .or_else(Multiplexer::from_env)
+    }
+
+    /// Loads the entry of the terminal the multiplexer runs in, when it can be identified.
+    ///
+    /// Only tmux reports the outer terminal: it is asked for the TERM of its client when the
+    /// TMUX environment variable is set. Returns Ok(None) if the terminal isn't a multiplexer,
+    /// or the outer terminal can't be identified, as with GNU screen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// std::env::remove_var("TMUX");
+    /// std::env::remove_var("STY");
+    /// assert!(Term::from_term_name("vt100")?.outer_term()?.is_none());
+    /// assert!(Term::from_term_name("tmux-256color")?.outer_term()?.is_none());
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of loading the outer terminal if it was identified, see
+    /// Term::from_term_name.
+    pub fn outer_term(&self) -> Result<Option<Term>, TermError> {
+        if self.multiplexer() != Some(Multiplexer::Tmux) || env::var_os("TMUX").is_none() {
+            return Ok(None);
+        }
+        let output = match Command::new("tmux")
+            .args(["display-message", "-p", "#{client_termname}"])
+            .output()
+        {
+            Ok(output) if output.status.success() => output.stdout,
+            _ => {
+                event!(DEBUG, "tmux didn't report the terminal of its client");
+                return Ok(None);
+            }
+        };
+        match String::from_utf8_lossy(&output).trim() {
+            "" => Ok(None),
+            name => Term::from_term_name(name).map(Some),
+        }
+    }
+}
@@ -1,57 +1,102 @@
+use crate::cap::NumericCap;
+use crate::error::CapError;
 use crate::term::Term;
-use std::ffi::CStr;
+use std::borrow::Cow;
 use std::fmt;
-use unibilium_sys::unibi_numeric;
 
 /// Represents numeric terminal capability.
-#[derive(Debug)]
 pub struct Numeric<'a> {
     term: &'a Term,
-    numeric: unibi_numeric,
+    cap: NumericCap,
 }
 
 impl<'a> Numeric<'a> {
-    /// Creates a Numeric from lower level unibi_numeric struct. It's intended for internal
-    /// use.
-    ///
-    /// # Warning
-    ///
-    /// For efficiency reasons it expects the caller to make sure that the numeric parameter is
-    /// valid.
-    pub(crate) fn from_unibi_numeric_unchecked(numeric: unibi_numeric, term: &'a Term) -> Self {
-        Numeric { numeric, term }
+    /// Creates a Numeric for the standard capability. It's intended for internal use.
+    pub(crate) fn from_cap(cap: NumericCap, term: &'a Term) -> Self {
+        Numeric { cap, term }
     }
 
     /// Returns name of the capability.
-    ///
-    /// # Panics
-    ///
-    /// Panics if it internally encounters invalid UTF-8 characters.
     pub fn name(&self) -> &str {
-        // Returns static string if called with value between begin and end.
-        let name = unsafe { unibilium_sys::unibi_name_num(self.numeric) };
-        if name.is_null() {
-            panic!("Invalid unibi_numeric value: {}", self.numeric);
-        }
-        let name = unsafe { CStr::from_ptr(name) };
-        name.to_str().expect("Invalid UTF-8 string encountered")
+        self.cap.name()
+    }
+
+    /// Returns the name of the capability like name, but returns an error instead of panicking.
+    pub fn try_name(&self) -> Result<&str, CapError> {
+        Ok(self.cap.name())
+    }
+
+    /// Returns the name of the capability, replacing invalid UTF-8 sequences with
+    /// U+FFFD REPLACEMENT CHARACTER instead of panicking.
+    pub fn name_lossy(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.cap.name())
+    }
+
+    /// Returns the short terminfo name of the capability.
+    pub fn short_name(&self) -> &str {
+        self.cap.short_name()
+    }
+
+    /// Returns the short terminfo name of the capability like short_name, but returns an error
+    /// instead of panicking.
+    pub fn try_short_name(&self) -> Result<&str, CapError> {
+        Ok(self.cap.short_name())
     }
 
     /// Returns the value corresponding to the numeric terminal capability.
     pub fn value(&self) -> i32 {
-        unsafe { unibilium_sys::unibi_get_num(self.term.unibi_term(), self.numeric) }
+        self.term.numeric_raw(self.cap)
+    }
+
+    /// Copies the names and the value of the capability into a NumericValue, which doesn't
+    /// borrow the Term.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let cols = Term::from_term_name("vt100")?
+    ///     .numerics()
+    ///     .find(|n| n.short_name() == "cols")
+    ///     .unwrap()
+    ///     .to_owned();
+    /// assert_eq!(cols.name, "columns");
+    /// assert_eq!(cols.value, 80);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn to_owned(&self) -> NumericValue {
+        NumericValue {
+            name: self.name_lossy().into_owned(),
+            short_name: self.try_short_name().unwrap_or_default().to_owned(),
+            value: self.value(),
+        }
+    }
+}
+
+impl<'a> fmt::Debug for Numeric<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Numeric")
+            .field("name", &self.name_lossy())
+            .field("short_name", &self.try_short_name().unwrap_or_default())
+            .field("value", &self.value())
+            .finish()
     }
 }
 
 impl<'a> fmt::Display for Numeric<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}: {}", self.name(), self.value())
+        write!(f, "{}: {}", self.name_lossy(), self.value())
     }
 }
 
 /// Represents extended numeric capabilities. Each terminal can support custom numeric
 /// capabilities.
-#[derive(Debug)]
 pub struct ExtNumeric<'a> {
     index: u64,
     term: &'a Term,
@@ -63,7 +108,7 @@ impl<'a> ExtNumeric<'a> {
     /// # Warning
     ///
     /// For efficiency reasons it expects the caller to make sure that the index is valid, i.e.
-    /// less than unibilium_sys::unibi_count_ext_num.
+    /// less than the number of extended numeric capabilities of the terminal.
     pub(crate) fn from_index_unchecked(index: u64, term: &'a Term) -> Self {
         ExtNumeric { index, term }
     }
@@ -74,27 +119,83 @@ impl<'a> ExtNumeric<'a> {
     ///
     /// Panics if it internally encounters invalid UTF-8 characters.
     pub fn name(&self) -> &str {
-        // Returns static string if called with value between 0 and count
-        let name =
-            unsafe { unibilium_sys::unibi_get_ext_num_name(self.term.unibi_term(), self.index) };
-        if name.is_null() {
-            panic!(
-                "Invalid index for extended numeric capability: {}",
-                self.index
-            );
-        }
-        let name = unsafe { CStr::from_ptr(name) };
+        let name = self
+            .term
+            .backend()
+            .ext_numeric_name(self.index)
+            .unwrap_or_else(|| {
+                panic!(
+                    "Invalid index for extended numeric capability: {}",
+                    self.index
+                )
+            });
         name.to_str().expect("Invalid UTF-8 string encountered")
     }
 
+    /// Returns the name of the capability like name, but returns an error instead of panicking.
+    pub fn try_name(&self) -> Result<&str, CapError> {
+        let name = self
+            .term
+            .backend()
+            .ext_numeric_name(self.index)
+            .ok_or(CapError::InvalidIndex(self.index))?;
+        name.to_str().map_err(CapError::NotUnicode)
+    }
+
+    /// Returns the name of the capability, replacing invalid UTF-8 sequences with
+    /// U+FFFD REPLACEMENT CHARACTER instead of panicking.
+    pub fn name_lossy(&self) -> Cow<'_, str> {
+        match self.term.backend().ext_numeric_name(self.index) {
+            Some(name) => name.to_string_lossy(),
+            None => Cow::Borrowed(""),
+        }
+    }
+
     /// Returns the value corresponding to the extended numeric terminal capability.
     pub fn value(&self) -> i32 {
-        unsafe { unibilium_sys::unibi_get_ext_num(self.term.unibi_term(), self.index) }
+        self.term.backend().ext_numeric(self.index)
+    }
+
+    /// Copies the name and the value of the capability into a NumericValue, which doesn't
+    /// borrow the Term. Extended capabilities only have a short name, which is used for both
+    /// names.
+    pub fn to_owned(&self) -> NumericValue {
+        let name = self.name_lossy().into_owned();
+        NumericValue {
+            short_name: name.clone(),
+            name,
+            value: self.value(),
+        }
+    }
+}
+
+impl<'a> fmt::Debug for ExtNumeric<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtNumeric")
+            .field("name", &self.name_lossy())
+            .field("value", &self.value())
+            .finish()
     }
 }
 
 impl<'a> fmt::Display for ExtNumeric<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}: {}", self.name(), self.value())
+        write!(f, "{}: {}", self.name_lossy(), self.value())
+    }
+}
+
+/// The names and the value of a numeric capability, standard or extended, owned rather than
+/// borrowed from a Term. Like with Numeric::value, a negative value means that the terminal
+/// doesn't define the capability.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct NumericValue {
+    pub name: String,
+    pub short_name: String,
+    pub value: i32,
+}
+
+impl fmt::Display for NumericValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.value)
     }
 }
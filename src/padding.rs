@@ -0,0 +1,507 @@
+//! Interpretation of `$<delay>` padding directives found in capability values.
+
+use crate::cap::{BooleanCap, NumericCap, StringCap};
+use crate::format::{self, Param};
+use crate::term::Term;
+use std::fmt;
+use std::slice;
+use std::time::Duration;
+
+/// Bits sent per character when converting delays to pad characters, as curses does.
+const BITS_PER_CHAR: u64 = 9;
+
+/// Represents a padding directive, e.g. `$<5>`, `$<2.5*>` or `$<100/>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Delay {
+    tenths: u32,
+    proportional: bool,
+    mandatory: bool,
+}
+
+impl Delay {
+    /// Creates a delay given in tenths of a millisecond.
+    pub fn from_tenths(tenths: u32, proportional: bool, mandatory: bool) -> Self {
+        Delay {
+            tenths,
+            proportional,
+            mandatory,
+        }
+    }
+
+    /// Parses a padding directive at the start of s. Returns the delay and the number of bytes
+    /// it occupies, or None if s doesn't start with a valid directive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unibilium::padding::Delay;
+    ///
+    /// let (delay, len) = Delay::parse(b"$<2.5*>rest").unwrap();
+    /// assert_eq!(delay.tenths(), 25);
+    /// assert!(delay.is_proportional());
+    /// assert_eq!(len, 7);
+    /// assert!(Delay::parse(b"$<>").is_none());
+    /// ```
+    pub fn parse(s: &[u8]) -> Option<(Delay, usize)> {
+        if !s.starts_with(b"$<") {
+            return None;
+        }
+        let mut pos = 2;
+        let mut millis: u32 = 0;
+        let mut digits = 0;
+        while let Some(c) = s.get(pos).filter(|c| c.is_ascii_digit()) {
+            millis = millis.checked_mul(10)?.checked_add(u32::from(c - b'0'))?;
+            digits += 1;
+            pos += 1;
+        }
+        let mut tenths = millis.checked_mul(10)?;
+        if s.get(pos) == Some(&b'.') {
+            pos += 1;
+            // Only the first decimal is significant.
+            if let Some(c) = s.get(pos).filter(|c| c.is_ascii_digit()) {
+                tenths = tenths.checked_add(u32::from(c - b'0'))?;
+                digits += 1;
+            }
+            while s.get(pos).filter(|c| c.is_ascii_digit()).is_some() {
+                pos += 1;
+            }
+        }
+        if digits == 0 {
+            return None;
+        }
+        let (mut proportional, mut mandatory) = (false, false);
+        loop {
+            match s.get(pos) {
+                Some(b'*') => proportional = true,
+                Some(b'/') => mandatory = true,
+                Some(b'>') => break,
+                _ => return None,
+            }
+            pos += 1;
+        }
+        Some((Delay::from_tenths(tenths, proportional, mandatory), pos + 1))
+    }
+
+    /// Returns the delay in tenths of a millisecond.
+    pub fn tenths(&self) -> u32 {
+        self.tenths
+    }
+
+    /// Returns whether the delay is per affected line (`*`).
+    pub fn is_proportional(&self) -> bool {
+        self.proportional
+    }
+
+    /// Returns whether the delay must be applied even when flow control makes padding
+    /// unnecessary (`/`).
+    pub fn is_mandatory(&self) -> bool {
+        self.mandatory
+    }
+
+    /// Returns the total delay for an operation affecting the given number of lines.
+    pub fn duration(&self, affected_lines: u32) -> Duration {
+        Duration::from_micros(u64::from(self.tenths) * 100 * self.factor(affected_lines))
+    }
+
+    fn factor(&self, affected_lines: u32) -> u64 {
+        if self.proportional {
+            u64::from(affected_lines.max(1))
+        } else {
+            1
+        }
+    }
+}
+
+impl fmt::Display for Delay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "$<{}", self.tenths / 10)?;
+        let decimal = self.tenths % 10;
+        if decimal > 0 {
+            write!(f, ".{}", decimal)?;
+        }
+        if self.proportional {
+            write!(f, "*")?;
+        }
+        if self.mandatory {
+            write!(f, "/")?;
+        }
+        write!(f, ">")
+    }
+}
+
+/// Represents a piece of output split at padding directives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Segment<'a> {
+    Bytes(&'a [u8]),
+    Delay(Delay),
+}
+
+/// Iterator over the segments of output. Created by the segments function.
+#[derive(Debug, Clone)]
+pub struct Segments<'a> {
+    rest: &'a [u8],
+}
+
+impl<'a> Iterator for Segments<'a> {
+    type Item = Segment<'a>;
+
+    fn next(&mut self) -> Option<Segment<'a>> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        if let Some((delay, len)) = Delay::parse(self.rest) {
+            self.rest = &self.rest[len..];
+            return Some(Segment::Delay(delay));
+        }
+        // Invalid directives are passed through as ordinary bytes.
+        let end = (1..self.rest.len())
+            .find(|&i| self.rest[i..].starts_with(b"$<") && Delay::parse(&self.rest[i..]).is_some())
+            .unwrap_or(self.rest.len());
+        let (bytes, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Some(Segment::Bytes(bytes))
+    }
+}
+
+/// Splits output into plain bytes and padding directives.
+///
+/// # Examples
+///
+/// ```
+/// use unibilium::padding::{self, Delay, Segment};
+///
+/// let segments: Vec<Segment> = padding::segments(b"\x1b[?5h$<100/>\x1b[?5l").collect();
+/// assert_eq!(
+///     segments,
+///     vec![
+///         Segment::Bytes(b"\x1b[?5h"),
+///         Segment::Delay(Delay::from_tenths(1000, false, true)),
+///         Segment::Bytes(b"\x1b[?5l"),
+///     ]
+/// );
+/// ```
+pub fn segments(output: &[u8]) -> Segments<'_> {
+    Segments { rest: output }
+}
+
+/// Describes how a terminal wants delays to be realized at a given output speed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PadPolicy {
+    /// Output speed in bits per second.
+    pub baud: u32,
+    /// Character used for padding, or None if the terminal can't be padded with characters.
+    pub pad_char: Option<u8>,
+    /// Lowest speed at which non-mandatory padding is needed.
+    pub padding_baud_rate: Option<u32>,
+    /// Whether the terminal uses XON/XOFF flow control, making non-mandatory padding unneeded.
+    pub xon_xoff: bool,
+}
+
+/// Represents how a single delay should be realized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Padding {
+    /// No padding is needed.
+    Skip,
+    /// Send the byte count times.
+    Chars { byte: u8, count: usize },
+    /// Sleep for the duration after flushing preceding output.
+    Sleep(Duration),
+}
+
+impl PadPolicy {
+    /// Decides how to realize the delay for an operation affecting the given number of lines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unibilium::padding::{Delay, PadPolicy, Padding};
+    ///
+    /// let policy = PadPolicy {
+    ///     baud: 9600,
+    ///     pad_char: Some(0),
+    ///     padding_baud_rate: None,
+    ///     xon_xoff: false,
+    /// };
+    /// let delay = Delay::from_tenths(50, false, false);
+    /// assert_eq!(policy.apply(&delay, 1), Padding::Chars { byte: 0, count: 5 });
+    /// ```
+    pub fn apply(&self, delay: &Delay, affected_lines: u32) -> Padding {
+        let needed = self.baud >= self.padding_baud_rate.unwrap_or(0) && !self.xon_xoff;
+        if !delay.is_mandatory() && !needed {
+            return Padding::Skip;
+        }
+        let tenths = u64::from(delay.tenths()) * delay.factor(affected_lines);
+        if tenths == 0 {
+            return Padding::Skip;
+        }
+        match self.pad_char {
+            None => Padding::Sleep(delay.duration(affected_lines)),
+            Some(byte) => {
+                let divisor = BITS_PER_CHAR * 10_000;
+                let count = (tenths * u64::from(self.baud) + divisor / 2) / divisor;
+                Padding::Chars {
+                    byte,
+                    count: count as usize,
+                }
+            }
+        }
+    }
+}
+
+/// Represents output with padding realized, as returned by Term::pad.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Padded {
+    Bytes(Vec<u8>),
+    Sleep(Duration),
+}
+
+/// Represents an event in the output of a capability, as yielded by OutputEvents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutputEvent<'a> {
+    /// Bytes to write to the terminal.
+    Bytes(&'a [u8]),
+    /// Milliseconds to wait after writing the preceding bytes.
+    DelayMs(u32),
+}
+
+/// Holds the expanded output of a capability together with its delays. Created by
+/// Term::run_events.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OutputEvents {
+    output: Vec<u8>,
+    /// The delays along with the length of the output preceding them.
+    delays: Vec<(usize, Delay)>,
+    affected_lines: u32,
+    xon_xoff: bool,
+}
+
+impl OutputEvents {
+    /// Returns an iterator over the events in output order.
+    pub fn iter(&self) -> OutputEventsIter<'_> {
+        OutputEventsIter {
+            output: &self.output,
+            written: 0,
+            delays: self.delays.iter(),
+            affected_lines: self.affected_lines,
+            xon_xoff: self.xon_xoff,
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a OutputEvents {
+    type Item = OutputEvent<'a>;
+    type IntoIter = OutputEventsIter<'a>;
+
+    fn into_iter(self) -> OutputEventsIter<'a> {
+        self.iter()
+    }
+}
+
+/// Iterator over the events of OutputEvents.
+#[derive(Debug, Clone)]
+pub struct OutputEventsIter<'a> {
+    output: &'a [u8],
+    written: usize,
+    delays: slice::Iter<'a, (usize, Delay)>,
+    affected_lines: u32,
+    xon_xoff: bool,
+}
+
+impl<'a> OutputEventsIter<'a> {
+    /// Returns the output up to end which wasn't yielded yet, if any.
+    fn bytes_until(&mut self, end: usize) -> Option<OutputEvent<'a>> {
+        if end <= self.written {
+            return None;
+        }
+        let bytes = &self.output[self.written..end];
+        self.written = end;
+        Some(OutputEvent::Bytes(bytes))
+    }
+}
+
+impl<'a> Iterator for OutputEventsIter<'a> {
+    type Item = OutputEvent<'a>;
+
+    fn next(&mut self) -> Option<OutputEvent<'a>> {
+        loop {
+            let (offset, delay) = match self.delays.as_slice().first() {
+                Some(&(offset, delay)) => (offset, delay),
+                None => return self.bytes_until(self.output.len()),
+            };
+            if let Some(bytes) = self.bytes_until(offset) {
+                return Some(bytes);
+            }
+            self.delays.next();
+            // Flow control makes non-mandatory delays unnecessary.
+            if self.xon_xoff && !delay.is_mandatory() {
+                continue;
+            }
+            let tenths = u64::from(delay.tenths()) * delay.factor(self.affected_lines);
+            if tenths > 0 {
+                let millis = tenths.div_ceil(10);
+                return Some(OutputEvent::DelayMs(millis.min(u64::from(u32::MAX)) as u32));
+            }
+        }
+    }
+}
+
+impl Term {
+    /// Expands the parameterized string capability and splits the result into bytes to write
+    /// and delays to wait, so that callers can sleep in whatever way suits them, e.g. in an async
+    /// runtime. Proportional delays are multiplied by affected_lines. Returns None if the
+    /// terminal doesn't define the capability.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::padding::OutputEvent;
+    /// use unibilium::{StringCap, Term};
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// let events = term.run_events(StringCap::ClearScreen, &[], 24).unwrap();
+    /// for event in &events {
+    ///     match event {
+    ///         OutputEvent::Bytes(bytes) => println!("{:?}", bytes),
+    ///         OutputEvent::DelayMs(ms) => println!("sleep {}ms", ms),
+    ///     }
+    /// }
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Delays are kept apart from the output, so a string parameter which looks like a padding
+    /// directive is written as it is.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::ffi::CString;
+    /// use unibilium::format::Param;
+    /// use unibilium::padding::OutputEvent;
+    /// use unibilium::testing::MockTerm;
+    /// use unibilium::StringCap;
+    ///
+    /// let term = MockTerm::new("fake").string("tsl", "%p1%s$<5/>").build()?;
+    /// let title = CString::new("$<5>")?;
+    /// let params = [Param::str(&title)];
+    /// let events = term.run_events(StringCap::ToStatusLine, &params, 1).unwrap();
+    /// let events: Vec<OutputEvent> = events.iter().collect();
+    /// assert_eq!(events, [OutputEvent::Bytes(b"$<5>"), OutputEvent::DelayMs(5)]);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than MAX_PARAMS parameters are given.
+    pub fn run_events(
+        &self,
+        cap: StringCap,
+        params: &[Param],
+        affected_lines: u32,
+    ) -> Option<OutputEvents> {
+        let fmt = self.string_cstr(cap)?;
+        let mut output = vec![];
+        let mut delays = vec![];
+        format::format_segments(
+            fmt,
+            params,
+            &mut format::new_vars(),
+            &mut format::new_vars(),
+            &mut |segment| match segment {
+                Segment::Bytes(bytes) => output.extend_from_slice(bytes),
+                Segment::Delay(delay) => delays.push((output.len(), delay)),
+            },
+        );
+        Some(OutputEvents {
+            output,
+            delays,
+            affected_lines,
+            xon_xoff: self.has_boolean(BooleanCap::XonXoff),
+        })
+    }
+
+    /// Returns the padding policy of the terminal at the given output speed, based on the `pad`,
+    /// `npc`, `pb` and `xon` capabilities.
+    pub fn pad_policy(&self, baud: u32) -> PadPolicy {
+        let pad_char = if self.has_boolean(BooleanCap::NoPadChar) {
+            None
+        } else {
+            let pad = self.string_cstr(StringCap::PadChar);
+            Some(
+                pad.and_then(|pad| pad.to_bytes().first().copied())
+                    .unwrap_or(0),
+            )
+        };
+        let padding_baud_rate = self.numeric_raw(NumericCap::PaddingBaudRate);
+        PadPolicy {
+            baud,
+            pad_char,
+            padding_baud_rate: if padding_baud_rate >= 0 {
+                Some(padding_baud_rate as u32)
+            } else {
+                None
+            },
+            xon_xoff: self.has_boolean(BooleanCap::XonXoff),
+        }
+    }
+
+    /// Interprets the padding directives in output for the given output speed. Delays are
+    /// replaced by pad characters or turned into sleeps, depending on the terminal. Adjacent
+    /// bytes are merged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::padding::Padded;
+    /// use unibilium::{StringCap, Term};
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// let mut output = vec![];
+    /// term.run_with_padding(StringCap::ClearScreen, &[], &mut output)?;
+    /// for padded in term.pad(&output, 300, 24) {
+    ///     match padded {
+    ///         Padded::Bytes(bytes) => println!("{:?}", bytes),
+    ///         Padded::Sleep(duration) => std::thread::sleep(duration),
+    ///     }
+    /// }
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn pad(&self, output: &[u8], baud: u32, affected_lines: u32) -> Vec<Padded> {
+        let policy = self.pad_policy(baud);
+        let mut all = vec![];
+        let mut bytes = vec![];
+        for segment in segments(output) {
+            match segment {
+                Segment::Bytes(b) => bytes.extend_from_slice(b),
+                Segment::Delay(delay) => match policy.apply(&delay, affected_lines) {
+                    Padding::Skip => {}
+                    Padding::Chars { byte, count } => {
+                        bytes.resize(bytes.len() + count, byte);
+                    }
+                    Padding::Sleep(duration) => {
+                        if !bytes.is_empty() {
+                            all.push(Padded::Bytes(std::mem::take(&mut bytes)));
+                        }
+                        all.push(Padded::Sleep(duration));
+                    }
+                },
+            }
+        }
+        if !bytes.is_empty() {
+            all.push(Padded::Bytes(bytes));
+        }
+        all
+    }
+}
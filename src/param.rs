@@ -0,0 +1,792 @@
+//! Parsing and checking of the `%` language used by parameterized string capabilities.
+
+use crate::format::MAX_PARAMS;
+use crate::padding::Delay;
+use std::error::Error;
+use std::fmt;
+
+/// Represents the conversion of a printf-like output operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Conversion {
+    /// `d`, signed decimal.
+    Decimal,
+    /// `o`, octal.
+    Octal,
+    /// `x`, lowercase hexadecimal.
+    Hex,
+    /// `X`, uppercase hexadecimal.
+    UpperHex,
+    /// `s`, string.
+    String,
+}
+
+/// Represents a printf-like output operation, e.g. `%d`, `%02x` or `%:-10s`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Printf {
+    pub left_align: bool,
+    pub sign: bool,
+    pub alternate: bool,
+    pub space: bool,
+    pub zero_pad: bool,
+    pub width: Option<usize>,
+    pub precision: Option<usize>,
+    pub conversion: Conversion,
+}
+
+impl fmt::Display for Printf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "%")?;
+        // A colon is needed so that '-' and '+' are not taken for operators.
+        if self.left_align || self.sign {
+            write!(f, ":")?;
+        }
+        if self.left_align {
+            write!(f, "-")?;
+        }
+        if self.sign {
+            write!(f, "+")?;
+        }
+        if self.alternate {
+            write!(f, "#")?;
+        }
+        if self.space {
+            write!(f, " ")?;
+        }
+        if self.zero_pad {
+            write!(f, "0")?;
+        }
+        if let Some(width) = self.width {
+            write!(f, "{}", width)?;
+        }
+        if let Some(precision) = self.precision {
+            write!(f, ".{}", precision)?;
+        }
+        let conversion = match self.conversion {
+            Conversion::Decimal => 'd',
+            Conversion::Octal => 'o',
+            Conversion::Hex => 'x',
+            Conversion::UpperHex => 'X',
+            Conversion::String => 's',
+        };
+        write!(f, "{}", conversion)
+    }
+}
+
+/// Represents an operator taking two operands from the stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Eq,
+    Gt,
+    Lt,
+    And,
+    Or,
+}
+
+impl BinaryOp {
+    /// Returns the character following `%` for this operator.
+    pub fn symbol(self) -> char {
+        match self {
+            BinaryOp::Add => '+',
+            BinaryOp::Sub => '-',
+            BinaryOp::Mul => '*',
+            BinaryOp::Div => '/',
+            BinaryOp::Mod => 'm',
+            BinaryOp::BitAnd => '&',
+            BinaryOp::BitOr => '|',
+            BinaryOp::BitXor => '^',
+            BinaryOp::Eq => '=',
+            BinaryOp::Gt => '>',
+            BinaryOp::Lt => '<',
+            BinaryOp::And => 'A',
+            BinaryOp::Or => 'O',
+        }
+    }
+}
+
+/// Represents an operator taking one operand from the stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnaryOp {
+    /// `%!`, logical negation.
+    Not,
+    /// `%~`, bitwise complement.
+    Complement,
+}
+
+impl UnaryOp {
+    /// Returns the character following `%` for this operator.
+    pub fn symbol(self) -> char {
+        match self {
+            UnaryOp::Not => '!',
+            UnaryOp::Complement => '~',
+        }
+    }
+}
+
+/// Represents a single lexical element of a parameterized string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Token<'a> {
+    Literal(&'a [u8]),
+    Delay(Delay),
+    Printf(Printf),
+    Char,
+    Param(u8),
+    SetVar(u8),
+    GetVar(u8),
+    CharConst(u8),
+    IntConst(i32),
+    StrLen,
+    Binary(BinaryOp),
+    Unary(UnaryOp),
+    Increment,
+    If,
+    Then,
+    Else,
+    EndIf,
+}
+
+impl<'a> Token<'a> {
+    /// Returns how many values the token pops from and pushes to the stack.
+    pub(crate) fn stack_effect(&self) -> (usize, usize) {
+        match self {
+            Token::Literal(_) | Token::Delay(_) | Token::Increment => (0, 0),
+            Token::If | Token::Else | Token::EndIf => (0, 0),
+            Token::Printf(_) | Token::Char | Token::SetVar(_) | Token::Then => (1, 0),
+            Token::Param(_) | Token::GetVar(_) | Token::CharConst(_) | Token::IntConst(_) => (0, 1),
+            Token::StrLen | Token::Unary(_) => (1, 1),
+            Token::Binary(_) => (2, 1),
+        }
+    }
+}
+
+/// Describes a problem found in a parameterized string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DiagnosticKind {
+    /// `%` followed by a character which doesn't start any operation.
+    UnknownEscape(u8),
+    /// The string ends in the middle of an operation.
+    Truncated,
+    /// `%p` not followed by a digit between 1 and 9.
+    InvalidParam,
+    /// `%P` or `%g` not followed by a letter.
+    InvalidVar,
+    /// `%'` not followed by a character and a closing quote.
+    InvalidCharConst,
+    /// `%{` not followed by a number and a closing brace.
+    InvalidIntConst,
+    /// A printf-like operation without a conversion character.
+    InvalidPrintf,
+    /// A parameter beyond the number the capability takes.
+    UndefinedParam(u8),
+    /// An operation needs more values than the stack holds.
+    StackUnderflow,
+    /// `%t`, `%e` or `%;` without a matching `%?`, or `%e` before `%t`.
+    UnexpectedConditional(u8),
+    /// `%?` without a matching `%;`.
+    UnterminatedConditional,
+}
+
+/// Represents a problem found at a byte offset of a parameterized string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    pub offset: usize,
+    pub kind: DiagnosticKind,
+}
+
+impl Error for Diagnostic {}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "offset {}: ", self.offset)?;
+        match self.kind {
+            DiagnosticKind::UnknownEscape(c) => {
+                write!(f, "unknown escape '%{}'", std::ascii::escape_default(c))
+            }
+            DiagnosticKind::Truncated => write!(f, "string ends inside an operation"),
+            DiagnosticKind::InvalidParam => write!(f, "%p must be followed by a digit 1-9"),
+            DiagnosticKind::InvalidVar => write!(f, "variable name must be a letter"),
+            DiagnosticKind::InvalidCharConst => write!(f, "malformed character constant"),
+            DiagnosticKind::InvalidIntConst => write!(f, "malformed integer constant"),
+            DiagnosticKind::InvalidPrintf => write!(f, "malformed printf-like operation"),
+            DiagnosticKind::UndefinedParam(n) => write!(f, "parameter {} is not defined", n),
+            DiagnosticKind::StackUnderflow => write!(f, "stack underflow"),
+            DiagnosticKind::UnexpectedConditional(c) => {
+                write!(f, "unexpected '%{}'", char::from(c))
+            }
+            DiagnosticKind::UnterminatedConditional => write!(f, "'%?' without matching '%;'"),
+        }
+    }
+}
+
+/// Splits a parameterized string into tokens, calling token for each one together with its
+/// byte offset. Malformed operations are reported as diagnostics and skipped.
+pub(crate) fn tokenize<'a>(
+    s: &'a [u8],
+    mut token: impl FnMut(usize, Token<'a>),
+    mut error: impl FnMut(Diagnostic),
+) {
+    let mut pos = 0;
+    while pos < s.len() {
+        if let Some((delay, len)) = Delay::parse(&s[pos..]) {
+            token(pos, Token::Delay(delay));
+            pos += len;
+            continue;
+        }
+        if s[pos] != b'%' {
+            let start = pos;
+            pos += 1;
+            while pos < s.len() && s[pos] != b'%' && Delay::parse(&s[pos..]).is_none() {
+                pos += 1;
+            }
+            token(start, Token::Literal(&s[start..pos]));
+            continue;
+        }
+        match lex_escape(&s[pos..]) {
+            Ok((t, len)) => {
+                token(pos, t);
+                pos += len;
+            }
+            Err((kind, len)) => {
+                error(Diagnostic { offset: pos, kind });
+                pos += len;
+            }
+        }
+    }
+}
+
+/// Lexes the escape at the start of s, which starts with '%'. Returns the token and its length,
+/// or the problem and the number of bytes to skip.
+fn lex_escape(s: &[u8]) -> Result<(Token<'_>, usize), (DiagnosticKind, usize)> {
+    let c = match s.get(1) {
+        None => return Err((DiagnosticKind::Truncated, 1)),
+        Some(c) => *c,
+    };
+    let token = match c {
+        b'%' => Token::Literal(&s[1..2]),
+        b'c' => Token::Char,
+        b'l' => Token::StrLen,
+        b'i' => Token::Increment,
+        b'?' => Token::If,
+        b't' => Token::Then,
+        b'e' => Token::Else,
+        b';' => Token::EndIf,
+        b'!' => Token::Unary(UnaryOp::Not),
+        b'~' => Token::Unary(UnaryOp::Complement),
+        b'+' => Token::Binary(BinaryOp::Add),
+        b'-' => Token::Binary(BinaryOp::Sub),
+        b'*' => Token::Binary(BinaryOp::Mul),
+        b'/' => Token::Binary(BinaryOp::Div),
+        b'm' => Token::Binary(BinaryOp::Mod),
+        b'&' => Token::Binary(BinaryOp::BitAnd),
+        b'|' => Token::Binary(BinaryOp::BitOr),
+        b'^' => Token::Binary(BinaryOp::BitXor),
+        b'=' => Token::Binary(BinaryOp::Eq),
+        b'>' => Token::Binary(BinaryOp::Gt),
+        b'<' => Token::Binary(BinaryOp::Lt),
+        b'A' => Token::Binary(BinaryOp::And),
+        b'O' => Token::Binary(BinaryOp::Or),
+        b'p' => {
+            return match s.get(2) {
+                Some(n @ b'1'..=b'9') => Ok((Token::Param(n - b'0'), 3)),
+                Some(_) => Err((DiagnosticKind::InvalidParam, 3)),
+                None => Err((DiagnosticKind::Truncated, 2)),
+            }
+        }
+        b'P' | b'g' => {
+            return match s.get(2) {
+                Some(v) if v.is_ascii_alphabetic() && c == b'P' => Ok((Token::SetVar(*v), 3)),
+                Some(v) if v.is_ascii_alphabetic() => Ok((Token::GetVar(*v), 3)),
+                Some(_) => Err((DiagnosticKind::InvalidVar, 3)),
+                None => Err((DiagnosticKind::Truncated, 2)),
+            }
+        }
+        b'\'' => {
+            return match (s.get(2), s.get(3)) {
+                (Some(v), Some(b'\'')) => Ok((Token::CharConst(*v), 4)),
+                (None, _) | (Some(_), None) => Err((DiagnosticKind::Truncated, s.len())),
+                _ => Err((DiagnosticKind::InvalidCharConst, 2)),
+            }
+        }
+        b'{' => return lex_int_const(s),
+        b'd' | b'o' | b'x' | b'X' | b's' | b':' | b'#' | b' ' | b'.' | b'0'..=b'9' => {
+            return lex_printf(s)
+        }
+        _ => return Err((DiagnosticKind::UnknownEscape(c), 2)),
+    };
+    Ok((token, 2))
+}
+
+fn lex_int_const(s: &[u8]) -> Result<(Token<'_>, usize), (DiagnosticKind, usize)> {
+    let mut pos = 2;
+    let negative = s.get(pos) == Some(&b'-');
+    if negative {
+        pos += 1;
+    }
+    let start = pos;
+    let mut value: i32 = 0;
+    while let Some(d) = s.get(pos).filter(|d| d.is_ascii_digit()) {
+        value = value
+            .checked_mul(10)
+            .and_then(|v| v.checked_add(i32::from(d - b'0')))
+            .ok_or((DiagnosticKind::InvalidIntConst, pos + 1))?;
+        pos += 1;
+    }
+    match s.get(pos) {
+        None => Err((DiagnosticKind::Truncated, pos)),
+        Some(b'}') if pos > start => {
+            let value = if negative { -value } else { value };
+            Ok((Token::IntConst(value), pos + 1))
+        }
+        Some(_) => Err((DiagnosticKind::InvalidIntConst, pos)),
+    }
+}
+
+fn lex_printf(s: &[u8]) -> Result<(Token<'_>, usize), (DiagnosticKind, usize)> {
+    let mut pos = 1;
+    let mut printf = Printf {
+        left_align: false,
+        sign: false,
+        alternate: false,
+        space: false,
+        zero_pad: false,
+        width: None,
+        precision: None,
+        conversion: Conversion::Decimal,
+    };
+    // Without a colon '-' and '+' are operators, so only '#' and ' ' may be flags.
+    let colon = s.get(pos) == Some(&b':');
+    if colon {
+        pos += 1;
+    }
+    while let Some(c) = s.get(pos) {
+        match c {
+            b'-' if colon => printf.left_align = true,
+            b'+' if colon => printf.sign = true,
+            b'#' => printf.alternate = true,
+            b' ' => printf.space = true,
+            _ => break,
+        }
+        pos += 1;
+    }
+    let number = |pos: &mut usize| {
+        let start = *pos;
+        let mut value: usize = 0;
+        while let Some(d) = s.get(*pos).filter(|d| d.is_ascii_digit()) {
+            value = value
+                .saturating_mul(10)
+                .saturating_add(usize::from(d - b'0'));
+            *pos += 1;
+        }
+        if *pos > start {
+            Some(value)
+        } else {
+            None
+        }
+    };
+    // Leading zeros of the width request zero padding.
+    while s.get(pos) == Some(&b'0') {
+        printf.zero_pad = true;
+        pos += 1;
+    }
+    printf.width = number(&mut pos);
+    if s.get(pos) == Some(&b'.') {
+        pos += 1;
+        printf.precision = Some(number(&mut pos).unwrap_or(0));
+    }
+    printf.conversion = match s.get(pos) {
+        Some(b'd') => Conversion::Decimal,
+        Some(b'o') => Conversion::Octal,
+        Some(b'x') => Conversion::Hex,
+        Some(b'X') => Conversion::UpperHex,
+        Some(b's') => Conversion::String,
+        None => return Err((DiagnosticKind::Truncated, pos)),
+        Some(_) => return Err((DiagnosticKind::InvalidPrintf, pos)),
+    };
+    Ok((Token::Printf(printf), pos + 1))
+}
+
+/// Tracks the nesting of conditionals while checking a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// After `%?` or `%e`, before `%t`.
+    Condition,
+    /// After `%t`. Holds the stack depth at the start of the branch.
+    Then(usize),
+    /// After a final `%e`. Holds the stack depth at the start of the branch.
+    Else(usize),
+}
+
+/// Checks the syntax of a parameterized string for a capability taking the given number of
+/// parameters. Returns all problems found, or an empty vector if there are none.
+///
+/// Stack depth is tracked through conditionals using the deepest branch, so only underflows
+/// which happen regardless of the branch taken are reported.
+///
+/// # Examples
+///
+/// ```
+/// use unibilium::param::{self, DiagnosticKind};
+///
+/// assert!(param::lint(b"\x1b[%i%p1%d;%p2%dH", 2).is_empty());
+///
+/// let problems = param::lint(b"%?%p1%t1%e0", 1);
+/// assert_eq!(problems[0].kind, DiagnosticKind::UnterminatedConditional);
+///
+/// let problems = param::lint(b"%p3%d%+", 2);
+/// assert_eq!(problems[0].kind, DiagnosticKind::UndefinedParam(3));
+/// assert_eq!(problems[1].kind, DiagnosticKind::StackUnderflow);
+/// ```
+pub fn lint(s: &[u8], params: usize) -> Vec<Diagnostic> {
+    let mut problems = vec![];
+    let mut tokens = vec![];
+    tokenize(
+        s,
+        |offset, token| tokens.push((offset, token)),
+        |d| problems.push(d),
+    );
+    let mut depth = 0;
+    // Open conditionals together with the deepest stack seen at the end of their branches.
+    let mut open: Vec<(usize, State, usize)> = vec![];
+    for (offset, token) in tokens {
+        let mut report = |kind| problems.push(Diagnostic { offset, kind });
+        if let Token::Param(n) = token {
+            if usize::from(n) > params.min(MAX_PARAMS) {
+                report(DiagnosticKind::UndefinedParam(n));
+            }
+        }
+        let (pops, pushes) = token.stack_effect();
+        if depth < pops {
+            report(DiagnosticKind::StackUnderflow);
+            depth = 0;
+        } else {
+            depth -= pops;
+        }
+        depth += pushes;
+        match token {
+            Token::If => open.push((offset, State::Condition, 0)),
+            Token::Then => match open.last_mut() {
+                // A condition after an else branch belongs to an else-if chain.
+                Some((_, branch @ State::Condition, _)) | Some((_, branch @ State::Else(_), _)) => {
+                    *branch = State::Then(depth)
+                }
+                _ => report(DiagnosticKind::UnexpectedConditional(b't')),
+            },
+            Token::Else => match open.last_mut() {
+                Some((_, branch, deepest)) => match *branch {
+                    State::Then(start) => {
+                        *deepest = (*deepest).max(depth);
+                        // Either another condition follows or this is the final else branch.
+                        *branch = State::Else(start);
+                        depth = start;
+                    }
+                    _ => report(DiagnosticKind::UnexpectedConditional(b'e')),
+                },
+                None => report(DiagnosticKind::UnexpectedConditional(b'e')),
+            },
+            Token::EndIf => match open.pop() {
+                Some((_, State::Then(_), deepest)) | Some((_, State::Else(_), deepest)) => {
+                    depth = depth.max(deepest);
+                }
+                Some((_, State::Condition, _)) | None => {
+                    report(DiagnosticKind::UnexpectedConditional(b';'))
+                }
+            },
+            _ => {}
+        }
+    }
+    for (offset, _, _) in open {
+        problems.push(Diagnostic {
+            offset,
+            kind: DiagnosticKind::UnterminatedConditional,
+        });
+    }
+    problems.sort_by_key(|d| d.offset);
+    problems
+}
+
+/// Error returned when parsing a malformed parameterized string.
+pub type ParseError = Diagnostic;
+
+/// Represents an operation of a parameterized string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Op {
+    /// Outputs the bytes as they are.
+    Literal(Vec<u8>),
+    /// Padding directive, `$<..>`.
+    Delay(Delay),
+    /// Pops a value and outputs it formatted, `%d`, `%s`, etc.
+    Printf(Printf),
+    /// Pops a value and outputs it as a character, `%c`.
+    Char,
+    /// Pushes the parameter with the given number (1-9), `%p1`.
+    Param(u8),
+    /// Pops a value into the variable, `%Pa` or `%PA`.
+    SetVar(char),
+    /// Pushes the value of the variable, `%ga` or `%gA`.
+    GetVar(char),
+    /// Pushes the character constant, `%'c'`.
+    CharConst(u8),
+    /// Pushes the integer constant, `%{nn}`.
+    IntConst(i32),
+    /// Pops a string and pushes its length, `%l`.
+    StrLen,
+    /// Pops two values and pushes the result of the operator.
+    Binary(BinaryOp),
+    /// Pops a value and pushes the result of the operator.
+    Unary(UnaryOp),
+    /// Increments the first two parameters, `%i`.
+    Increment,
+    /// Conditional, `%? .. %t .. %e .. %;`.
+    Conditional(Conditional),
+}
+
+/// Represents a condition with the operations executed when it holds.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Branch {
+    pub condition: Vec<Op>,
+    pub then: Vec<Op>,
+}
+
+/// Represents a chain of conditions, like `if .. else if .. else`, with an optional final
+/// branch executed when none of them holds.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Conditional {
+    pub branches: Vec<Branch>,
+    pub otherwise: Option<Vec<Op>>,
+}
+
+/// Represents a parsed parameterized string. Display pretty-prints one operation per line with
+/// nested conditionals indented.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Program {
+    ops: Vec<Op>,
+}
+
+impl Program {
+    /// Returns the top level operations of the program.
+    pub fn ops(&self) -> &[Op] {
+        &self.ops
+    }
+}
+
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_ops(f, &self.ops, 0)
+    }
+}
+
+fn write_ops(f: &mut fmt::Formatter<'_>, ops: &[Op], indent: usize) -> fmt::Result {
+    for op in ops {
+        write_op(f, op, indent)?;
+    }
+    Ok(())
+}
+
+fn write_op(f: &mut fmt::Formatter<'_>, op: &Op, indent: usize) -> fmt::Result {
+    let pad = indent * 4;
+    match op {
+        Op::Literal(bytes) => {
+            write!(f, "{:pad$}literal \"", "", pad = pad)?;
+            for b in bytes {
+                write!(f, "{}", std::ascii::escape_default(*b))?;
+            }
+            writeln!(f, "\"")
+        }
+        Op::Delay(delay) => writeln!(f, "{:pad$}delay {}", "", delay, pad = pad),
+        Op::Printf(printf) => writeln!(f, "{:pad$}print {}", "", printf, pad = pad),
+        Op::Char => writeln!(f, "{:pad$}print %c", "", pad = pad),
+        Op::Param(n) => writeln!(f, "{:pad$}push param {}", "", n, pad = pad),
+        Op::SetVar(v) => writeln!(f, "{:pad$}set var {}", "", v, pad = pad),
+        Op::GetVar(v) => writeln!(f, "{:pad$}push var {}", "", v, pad = pad),
+        Op::CharConst(c) => writeln!(
+            f,
+            "{:pad$}push char '{}'",
+            "",
+            std::ascii::escape_default(*c),
+            pad = pad
+        ),
+        Op::IntConst(n) => writeln!(f, "{:pad$}push {}", "", n, pad = pad),
+        Op::StrLen => writeln!(f, "{:pad$}strlen", "", pad = pad),
+        Op::Binary(op) => writeln!(f, "{:pad$}apply %{}", "", op.symbol(), pad = pad),
+        Op::Unary(op) => writeln!(f, "{:pad$}apply %{}", "", op.symbol(), pad = pad),
+        Op::Increment => writeln!(f, "{:pad$}increment params", "", pad = pad),
+        Op::Conditional(conditional) => {
+            for (i, branch) in conditional.branches.iter().enumerate() {
+                let keyword = if i == 0 { "if" } else { "else if" };
+                writeln!(f, "{:pad$}{}", "", keyword, pad = pad)?;
+                write_ops(f, &branch.condition, indent + 1)?;
+                writeln!(f, "{:pad$}then", "", pad = pad)?;
+                write_ops(f, &branch.then, indent + 1)?;
+            }
+            if let Some(otherwise) = &conditional.otherwise {
+                writeln!(f, "{:pad$}else", "", pad = pad)?;
+                write_ops(f, otherwise, indent + 1)?;
+            }
+            writeln!(f, "{:pad$}end", "", pad = pad)
+        }
+    }
+}
+
+/// Tracks a conditional while it is being parsed.
+struct Frame {
+    offset: usize,
+    outer: Vec<Op>,
+    branches: Vec<Branch>,
+    condition: Vec<Op>,
+    part: Part,
+}
+
+/// Identifies the part of a conditional being parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part {
+    /// After `%?`, before `%t`.
+    Condition,
+    /// After `%t`.
+    Then,
+    /// After `%e`, which may be followed by another condition or be the final branch.
+    Else,
+}
+
+/// Parses a parameterized string into a program.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use unibilium::param::{self, Op};
+///
+/// let program = param::parse("\x1b[%?%p1%t1%e0%;m")?;
+/// assert_eq!(program.ops().len(), 3);
+/// assert!(matches!(program.ops()[1], Op::Conditional(_)));
+/// println!("{}", program);
+///
+/// assert!(param::parse("%?%p1%t1").is_err());
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns the first syntax error: malformed operations and unbalanced conditionals. Stack
+/// underflows and parameter counts are not checked, see lint for those.
+pub fn parse<S: AsRef<[u8]> + ?Sized>(s: &S) -> Result<Program, ParseError> {
+    let mut tokens = vec![];
+    let mut error = None;
+    tokenize(
+        s.as_ref(),
+        |offset, token| tokens.push((offset, token)),
+        |d| {
+            error.get_or_insert(d);
+        },
+    );
+    let mut frames: Vec<Frame> = vec![];
+    let mut current = vec![];
+    for (offset, token) in tokens {
+        // Errors from the tokenizer take precedence only if they come first.
+        if let Some(e) = &error {
+            if e.offset < offset {
+                break;
+            }
+        }
+        let unexpected = |c| Diagnostic {
+            offset,
+            kind: DiagnosticKind::UnexpectedConditional(c),
+        };
+        let op = match token {
+            Token::Literal(bytes) => {
+                // Adjacent literals, e.g. around "%%", are merged.
+                if let Some(Op::Literal(last)) = current.last_mut() {
+                    last.extend_from_slice(bytes);
+                    continue;
+                }
+                Op::Literal(bytes.to_vec())
+            }
+            Token::Delay(delay) => Op::Delay(delay),
+            Token::Printf(printf) => Op::Printf(printf),
+            Token::Char => Op::Char,
+            Token::Param(n) => Op::Param(n),
+            Token::SetVar(v) => Op::SetVar(char::from(v)),
+            Token::GetVar(v) => Op::GetVar(char::from(v)),
+            Token::CharConst(c) => Op::CharConst(c),
+            Token::IntConst(n) => Op::IntConst(n),
+            Token::StrLen => Op::StrLen,
+            Token::Binary(op) => Op::Binary(op),
+            Token::Unary(op) => Op::Unary(op),
+            Token::Increment => Op::Increment,
+            Token::If => {
+                frames.push(Frame {
+                    offset,
+                    outer: std::mem::take(&mut current),
+                    branches: vec![],
+                    condition: vec![],
+                    part: Part::Condition,
+                });
+                continue;
+            }
+            Token::Then => {
+                match frames.last_mut() {
+                    Some(frame) if frame.part != Part::Then => {
+                        frame.condition = std::mem::take(&mut current);
+                        frame.part = Part::Then;
+                    }
+                    _ => return Err(unexpected(b't')),
+                }
+                continue;
+            }
+            Token::Else => {
+                match frames.last_mut() {
+                    Some(frame) if frame.part == Part::Then => {
+                        frame.branches.push(Branch {
+                            condition: std::mem::take(&mut frame.condition),
+                            then: std::mem::take(&mut current),
+                        });
+                        frame.part = Part::Else;
+                    }
+                    _ => return Err(unexpected(b'e')),
+                }
+                continue;
+            }
+            Token::EndIf => {
+                let mut frame = match frames.pop() {
+                    Some(frame) if frame.part != Part::Condition => frame,
+                    _ => return Err(unexpected(b';')),
+                };
+                let otherwise = if frame.part == Part::Then {
+                    frame.branches.push(Branch {
+                        condition: frame.condition,
+                        then: std::mem::take(&mut current),
+                    });
+                    None
+                } else {
+                    Some(std::mem::take(&mut current))
+                };
+                current = frame.outer;
+                Op::Conditional(Conditional {
+                    branches: frame.branches,
+                    otherwise,
+                })
+            }
+        };
+        current.push(op);
+    }
+    if let Some(e) = error {
+        return Err(e);
+    }
+    match frames.first() {
+        Some(frame) => Err(Diagnostic {
+            offset: frame.offset,
+            kind: DiagnosticKind::UnterminatedConditional,
+        }),
+        None => Ok(Program { ops: current }),
+    }
+}
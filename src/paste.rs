@@ -0,0 +1,58 @@
+//! Bracketed paste support of a terminal.
+
+use crate::term::Term;
+
+/// The sequences of bracketed paste, in which the terminal marks pasted text so that programs
+/// can tell it apart from typed text.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct BracketedPaste {
+    /// Turns bracketed paste on (`BE`).
+    pub enable: Vec<u8>,
+    /// Turns bracketed paste off (`BD`).
+    pub disable: Vec<u8>,
+    /// Sent by the terminal before pasted text (`PS`).
+    pub start: Vec<u8>,
+    /// Sent by the terminal after pasted text (`PE`).
+    pub end: Vec<u8>,
+}
+
+impl Term {
+    /// Returns the bracketed paste sequences of the terminal, or None if it doesn't support
+    /// bracketed paste.
+    ///
+    /// The terminal supports bracketed paste if it defines any of the `BE`, `BD`, `PS` and `PE`
+    /// extended capabilities. The missing ones are filled in with the xterm sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let paste = Term::from_term_name("xterm-256color")?.bracketed_paste().unwrap();
+    /// assert_eq!(paste.enable, b"\x1b[?2004h".to_vec());
+    /// assert_eq!(paste.start, b"\x1b[200~".to_vec());
+    ///
+    /// assert_eq!(Term::from_term_name("vt100")?.bracketed_paste(), None);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn bracketed_paste(&self) -> Option<BracketedPaste> {
+        let enable = self.expand_ext("BE", &[]);
+        let disable = self.expand_ext("BD", &[]);
+        let start = self.expand_ext("PS", &[]);
+        let end = self.expand_ext("PE", &[]);
+        if enable.is_none() && disable.is_none() && start.is_none() && end.is_none() {
+            return None;
+        }
+        Some(BracketedPaste {
+            enable: enable.unwrap_or_else(|| b"\x1b[?2004h".to_vec()),
+            disable: disable.unwrap_or_else(|| b"\x1b[?2004l".to_vec()),
+            start: start.unwrap_or_else(|| b"\x1b[200~".to_vec()),
+            end: end.unwrap_or_else(|| b"\x1b[201~".to_vec()),
+        })
+    }
+}
@@ -0,0 +1,117 @@
+//! The locations unibilium searches for terminfo entries.
+
+use std::cell::RefCell;
+use std::env;
+#[cfg(feature = "hashed-db")]
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+thread_local! {
+    /// The directories searched instead of the usual ones on this thread, if any.
+    static SEARCH_DIRS: RefCell<Option<Vec<PathBuf>>> = const { RefCell::new(None) };
+}
+
+/// Restores the search directories of the thread when dropped.
+pub(crate) struct SearchDirsGuard {
+    previous: Option<Vec<PathBuf>>,
+}
+
+impl Drop for SearchDirsGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        SEARCH_DIRS.with(|dirs| *dirs.borrow_mut() = previous);
+    }
+}
+
+/// Makes the current thread search only the given directories until the guard is dropped.
+/// unibilium only knows the directories from the environment, so Term loads entries from the
+/// overridden directories itself.
+pub(crate) fn override_search_dirs(dirs: Vec<PathBuf>) -> SearchDirsGuard {
+    let previous = SEARCH_DIRS.with(|current| current.replace(Some(dirs)));
+    SearchDirsGuard { previous }
+}
+
+/// Returns whether the search directories of the current thread are overridden.
+pub(crate) fn is_overridden() -> bool {
+    SEARCH_DIRS.with(|dirs| dirs.borrow().is_some())
+}
+
+/// Returns the directories unibilium searches, in order: TERMINFO, ~/.terminfo, and then
+/// TERMINFO_DIRS or the directories unibilium was built with. Overridden directories are
+/// returned instead if there are any.
+pub(crate) fn search_dirs() -> Vec<PathBuf> {
+    if let Some(dirs) = SEARCH_DIRS.with(|dirs| dirs.borrow().clone()) {
+        return dirs;
+    }
+    let mut dirs = vec![];
+    if let Some(dir) = env::var_os("TERMINFO") {
+        dirs.push(PathBuf::from(dir));
+    }
+    if let Some(home) = env::var_os("HOME") {
+        dirs.push(Path::new(&home).join(".terminfo"));
+    }
+    let list = match env::var_os("TERMINFO_DIRS") {
+        Some(list) => list,
+        None => builtin_dirs().into(),
+    };
+    dirs.extend(env::split_paths(&list).filter(|dir| !dir.as_os_str().is_empty()));
+    dirs
+}
+
+/// Returns the directories unibilium was built with, separated by colons.
+#[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+fn builtin_dirs() -> String {
+    let dirs = unsafe { std::ffi::CStr::from_ptr(unibilium_sys::unibi_terminfo_dirs.as_ptr()) };
+    dirs.to_string_lossy().into_owned()
+}
+
+/// Returns the directories unibilium is built with by default, separated by colons.
+#[cfg(any(feature = "pure-rust", target_family = "wasm"))]
+fn builtin_dirs() -> String {
+    String::from("/etc/terminfo:/lib/terminfo:/usr/share/terminfo")
+}
+
+/// Returns the files unibilium tries when looking up the terminal name, in order. Each
+/// directory is searched for the name in a subdirectory named after its first byte, and then in
+/// one named after the hexadecimal code of that byte. Names unibilium rejects yield no files.
+pub(crate) fn candidate_paths(name: &str) -> Vec<PathBuf> {
+    let first = match name.bytes().next() {
+        Some(first) if first != b'.' && !name.contains('/') => first,
+        _ => return vec![],
+    };
+    let mut paths = vec![];
+    for dir in search_dirs() {
+        // A non-ASCII first byte doesn't form a valid path on its own, so only the hexadecimal
+        // form is listed for it.
+        if first.is_ascii() {
+            paths.push(dir.join((first as char).to_string()).join(name));
+        }
+        paths.push(dir.join(format!("{:02x}", first)).join(name));
+    }
+    paths
+}
+
+/// Returns the first file unibilium would open for the terminal name, if any exists.
+pub(crate) fn find_entry(name: &str) -> Option<PathBuf> {
+    candidate_paths(name)
+        .into_iter()
+        .find(|path| path.is_file())
+}
+
+/// Returns the hashed databases searched after the directories, which ncurses names after the
+/// directories with a `.db` suffix. Directories already named with the suffix are the databases.
+#[cfg(feature = "hashed-db")]
+pub(crate) fn hashed_databases() -> Vec<PathBuf> {
+    search_dirs()
+        .into_iter()
+        .map(|dir| {
+            if dir.extension() == Some(OsStr::new("db")) {
+                return dir;
+            }
+            let mut db = dir.into_os_string();
+            db.push(".db");
+            PathBuf::from(db)
+        })
+        .filter(|db| db.is_file())
+        .collect()
+}
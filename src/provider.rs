@@ -0,0 +1,160 @@
+//! A trait for sources of capability values, so code can accept any of them instead of Term.
+
+use crate::cache::CachedTerm;
+use crate::cap::{BooleanCap, NumericCap, StringCap};
+use crate::shared::SharedTerm;
+use crate::term::Term;
+use std::ffi::CStr;
+
+/// Provides the values of the standard and extended capabilities of a terminal. It is
+/// implemented by Term, SharedTerm and CachedTerm, and can be implemented by mocks or other
+/// backends.
+///
+/// Numeric and string values are None, and booleans are false, if the terminal doesn't define
+/// the capability.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use unibilium::provider::CapabilityProvider;
+/// use unibilium::{StringCap, Term};
+///
+/// fn can_clear(caps: &impl CapabilityProvider) -> bool {
+///     caps.string(StringCap::ClearScreen).is_some()
+/// }
+///
+/// let term = Term::from_term_name("vt100")?;
+/// assert!(can_clear(&term));
+/// assert!(can_clear(&term.cache()));
+/// assert_eq!(term.numeric_by_name("cols"), Some(80));
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+pub trait CapabilityProvider {
+    /// Returns whether the boolean capability is set.
+    fn boolean(&self, cap: BooleanCap) -> bool;
+
+    /// Returns the value of the numeric capability.
+    fn numeric(&self, cap: NumericCap) -> Option<i32>;
+
+    /// Returns the value of the string capability.
+    fn string(&self, cap: StringCap) -> Option<&[u8]>;
+
+    /// Returns whether the extended boolean capability with the given name is set.
+    fn ext_boolean(&self, name: &str) -> bool;
+
+    /// Returns the value of the extended numeric capability with the given name.
+    fn ext_numeric(&self, name: &str) -> Option<i32>;
+
+    /// Returns the value of the extended string capability with the given name.
+    fn ext_string(&self, name: &str) -> Option<&[u8]>;
+
+    /// Returns whether the boolean capability with the given short or long name is set,
+    /// looking at extended capabilities if no standard one has the name.
+    fn boolean_by_name(&self, name: &str) -> bool {
+        match BooleanCap::from_name(name) {
+            Some(cap) => self.boolean(cap),
+            None => self.ext_boolean(name),
+        }
+    }
+
+    /// Returns the value of the numeric capability with the given short or long name, looking
+    /// at extended capabilities if no standard one has the name.
+    fn numeric_by_name(&self, name: &str) -> Option<i32> {
+        match NumericCap::from_name(name) {
+            Some(cap) => self.numeric(cap),
+            None => self.ext_numeric(name),
+        }
+    }
+
+    /// Returns the value of the string capability with the given short or long name, looking
+    /// at extended capabilities if no standard one has the name.
+    fn string_by_name(&self, name: &str) -> Option<&[u8]> {
+        match StringCap::from_name(name) {
+            Some(cap) => self.string(cap),
+            None => self.ext_string(name),
+        }
+    }
+}
+
+impl CapabilityProvider for Term {
+    fn boolean(&self, cap: BooleanCap) -> bool {
+        self.has_boolean(cap)
+    }
+
+    fn numeric(&self, cap: NumericCap) -> Option<i32> {
+        self.numeric_value(cap)
+    }
+
+    fn string(&self, cap: StringCap) -> Option<&[u8]> {
+        self.string_cstr(cap).map(CStr::to_bytes)
+    }
+
+    fn ext_boolean(&self, name: &str) -> bool {
+        self.ext_boolean_value(name)
+    }
+
+    fn ext_numeric(&self, name: &str) -> Option<i32> {
+        self.ext_numeric_value(name)
+    }
+
+    fn ext_string(&self, name: &str) -> Option<&[u8]> {
+        self.ext_string_cstr(name).map(CStr::to_bytes)
+    }
+}
+
+impl CapabilityProvider for SharedTerm {
+    fn boolean(&self, cap: BooleanCap) -> bool {
+        (**self).boolean(cap)
+    }
+
+    fn numeric(&self, cap: NumericCap) -> Option<i32> {
+        (**self).numeric(cap)
+    }
+
+    fn string(&self, cap: StringCap) -> Option<&[u8]> {
+        (**self).string(cap)
+    }
+
+    fn ext_boolean(&self, name: &str) -> bool {
+        (**self).ext_boolean(name)
+    }
+
+    fn ext_numeric(&self, name: &str) -> Option<i32> {
+        (**self).ext_numeric(name)
+    }
+
+    fn ext_string(&self, name: &str) -> Option<&[u8]> {
+        (**self).ext_string(name)
+    }
+}
+
+impl CapabilityProvider for CachedTerm {
+    fn boolean(&self, cap: BooleanCap) -> bool {
+        self.has_boolean(cap)
+    }
+
+    fn numeric(&self, cap: NumericCap) -> Option<i32> {
+        CachedTerm::numeric(self, cap)
+    }
+
+    fn string(&self, cap: StringCap) -> Option<&[u8]> {
+        CachedTerm::string(self, cap)
+    }
+
+    fn ext_boolean(&self, name: &str) -> bool {
+        CachedTerm::ext_boolean(self, name)
+    }
+
+    fn ext_numeric(&self, name: &str) -> Option<i32> {
+        CachedTerm::ext_numeric(self, name)
+    }
+
+    fn ext_string(&self, name: &str) -> Option<&[u8]> {
+        CachedTerm::ext_string(self, name)
+    }
+}
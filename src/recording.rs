@@ -0,0 +1,176 @@
+//! Recording which capabilities an application queries.
+
+use crate::cap::{BooleanCap, CapId, NumericCap, StringCap};
+use crate::compat::Requirements;
+use crate::provider::CapabilityProvider;
+use crate::term::Term;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// The capabilities queried through a RecordingTerm.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Recording {
+    /// The standard capabilities queried, sorted.
+    pub caps: BTreeSet<CapId>,
+    /// The names of the extended capabilities queried, sorted.
+    pub ext: BTreeSet<String>,
+}
+
+impl Recording {
+    /// Returns Requirements listing the queried capabilities, for checking other terminals
+    /// with Term::is_compatible_with.
+    pub fn requirements(&self) -> Requirements {
+        let mut requirements = Requirements::new();
+        for cap in self.caps.iter() {
+            requirements = match *cap {
+                CapId::Boolean(cap) => requirements.boolean(cap),
+                CapId::Numeric(cap) => requirements.numeric(cap),
+                CapId::String(cap) => requirements.string(cap),
+            };
+        }
+        for name in self.ext.iter() {
+            requirements = requirements.ext(name);
+        }
+        requirements
+    }
+}
+
+/// Writes the short names of the standard capabilities and then the names of the extended
+/// ones, one per line.
+impl fmt::Display for Recording {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for cap in self.caps.iter() {
+            let name = match *cap {
+                CapId::Boolean(cap) => cap.short_name(),
+                CapId::Numeric(cap) => cap.short_name(),
+                CapId::String(cap) => cap.short_name(),
+            };
+            writeln!(f, "{}", name)?;
+        }
+        for name in self.ext.iter() {
+            writeln!(f, "{}", name)?;
+        }
+        Ok(())
+    }
+}
+
+/// Wraps a CapabilityProvider and records every capability queried through it, e.g. to find
+/// the minimal set of capabilities an application needs.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use unibilium::cap::CapId;
+/// use unibilium::provider::CapabilityProvider;
+/// use unibilium::recording::RecordingTerm;
+/// use unibilium::{NumericCap, StringCap, Term};
+///
+/// fn draw(caps: &impl CapabilityProvider) {
+///     caps.string(StringCap::ClearScreen);
+///     caps.numeric(NumericCap::MaxColors);
+///     caps.boolean_by_name("Tc");
+/// }
+///
+/// let term = RecordingTerm::new(Term::from_term_name("xterm")?);
+/// draw(&term);
+/// let recording = term.recording();
+/// assert!(recording.caps.contains(&CapId::String(StringCap::ClearScreen)));
+/// assert!(recording.ext.contains("Tc"));
+/// assert_eq!(recording.to_string(), "colors\nclear\nTc\n");
+///
+/// let vt100 = Term::from_term_name("vt100")?;
+/// let result = vt100.is_compatible_with(term.get_ref(), &recording.requirements());
+/// assert_eq!(result.missing, [CapId::Numeric(NumericCap::MaxColors)]);
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct RecordingTerm<P = Term> {
+    inner: P,
+    recording: Mutex<Recording>,
+}
+
+impl<P: CapabilityProvider> RecordingTerm<P> {
+    /// Wraps the provider with an empty recording.
+    pub fn new(inner: P) -> Self {
+        RecordingTerm {
+            inner,
+            recording: Mutex::new(Recording::default()),
+        }
+    }
+
+    /// Returns the wrapped provider. Queries through it aren't recorded.
+    pub fn get_ref(&self) -> &P {
+        &self.inner
+    }
+
+    /// Returns the wrapped provider, dropping the recording.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+
+    /// Returns a copy of the capabilities queried so far.
+    pub fn recording(&self) -> Recording {
+        self.lock().clone()
+    }
+
+    /// Clears the recording.
+    pub fn clear(&self) {
+        *self.lock() = Recording::default();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Recording> {
+        // The recording is valid even if a thread panicked while holding the lock.
+        self.recording
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn record(&self, cap: CapId) {
+        self.lock().caps.insert(cap);
+    }
+
+    fn record_ext(&self, name: &str) {
+        let mut recording = self.lock();
+        if !recording.ext.contains(name) {
+            recording.ext.insert(name.to_owned());
+        }
+    }
+}
+
+impl<P: CapabilityProvider> CapabilityProvider for RecordingTerm<P> {
+    fn boolean(&self, cap: BooleanCap) -> bool {
+        self.record(CapId::Boolean(cap));
+        self.inner.boolean(cap)
+    }
+
+    fn numeric(&self, cap: NumericCap) -> Option<i32> {
+        self.record(CapId::Numeric(cap));
+        self.inner.numeric(cap)
+    }
+
+    fn string(&self, cap: StringCap) -> Option<&[u8]> {
+        self.record(CapId::String(cap));
+        self.inner.string(cap)
+    }
+
+    fn ext_boolean(&self, name: &str) -> bool {
+        self.record_ext(name);
+        self.inner.ext_boolean(name)
+    }
+
+    fn ext_numeric(&self, name: &str) -> Option<i32> {
+        self.record_ext(name);
+        self.inner.ext_numeric(name)
+    }
+
+    fn ext_string(&self, name: &str) -> Option<&[u8]> {
+        self.record_ext(name);
+        self.inner.ext_string(name)
+    }
+}
@@ -0,0 +1,196 @@
+//! Textual comparison reports in the format of infocmp.
+
+use crate::cap::{BooleanCap, NumericCap, StringCap};
+use crate::string::{DisplayEscaped, Escape};
+use crate::term::Term;
+
+/// The capabilities a report lists, like the options of infocmp.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ReportMode {
+    /// The capabilities whose values differ, including those only one terminal defines, like
+    /// `infocmp -d`.
+    Differences,
+    /// The capabilities both terminals define with the same value, like `infocmp -c`.
+    Common,
+    /// The capabilities neither terminal defines, like `infocmp -n`.
+    Missing,
+}
+
+/// Options for report::infocmp.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct ReportOptions {
+    pub mode: ReportMode,
+    /// Includes the extended capabilities, like `infocmp -x`.
+    pub extended: bool,
+}
+
+impl Default for ReportOptions {
+    /// Reports the differences of the standard capabilities, like infocmp without options.
+    fn default() -> Self {
+        ReportOptions {
+            mode: ReportMode::Differences,
+            extended: false,
+        }
+    }
+}
+
+/// A capability in the report, with its value in each terminal.
+struct Entry<V> {
+    name: String,
+    a: Option<V>,
+    b: Option<V>,
+}
+
+/// Writes the section of one kind of capabilities, formatting the values of the terminals with
+/// value, which gets None for undefined capabilities.
+fn write_section<V, F>(
+    out: &mut String,
+    kind: &str,
+    mut entries: Vec<Entry<V>>,
+    mode: ReportMode,
+    value: F,
+) where
+    V: PartialEq,
+    F: Fn(Option<&V>) -> String,
+{
+    entries.sort_by(|x, y| x.name.cmp(&y.name));
+    out.push_str(&format!("    comparing {}.\n", kind));
+    for entry in entries {
+        let (a, b) = (entry.a.as_ref(), entry.b.as_ref());
+        let line = match mode {
+            ReportMode::Differences if a != b => {
+                format!("{}: {}, {}", entry.name, value(a), value(b))
+            }
+            ReportMode::Common if a.is_some() && a == b => format!("{}= {}", entry.name, value(a)),
+            ReportMode::Missing if a.is_none() && b.is_none() => format!("!{}", entry.name),
+            _ => continue,
+        };
+        out.push_str(&format!("\t{}.\n", line));
+    }
+}
+
+/// Returns the report infocmp writes when comparing a to b, e.g.:
+///
+/// ```text
+/// comparing xterm to vt100.
+///     comparing booleans.
+///     bce: T:F.
+///     comparing numbers.
+///     colors: 8, NULL.
+///     comparing strings.
+///     cbt: '\E[Z', NULL.
+/// ```
+///
+/// Capabilities are listed by their short names, sorted within each section, and the lines
+/// listing them start with a tab. Terminals are named by their first alias.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use unibilium::report::{self, ReportMode, ReportOptions};
+/// use unibilium::Term;
+///
+/// let vt100 = Term::from_term_name("vt100")?;
+/// let vt102 = Term::from_term_name("vt102")?;
+/// let report = report::infocmp(&vt100, &vt102, ReportOptions::default());
+/// assert!(report.starts_with("comparing vt100 to vt102.\n    comparing booleans.\n"));
+/// assert!(report.contains("\tdch1: NULL, '\\E[P'.\n"));
+///
+/// let options = ReportOptions {
+///     mode: ReportMode::Common,
+///     ..ReportOptions::default()
+/// };
+/// let report = report::infocmp(&vt100, &vt102, options);
+/// assert!(report.contains("\tam= T.\n"));
+/// assert!(report.contains("\tcols= 80.\n"));
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+pub fn infocmp(a: &Term, b: &Term, options: ReportOptions) -> String {
+    let first_name = |term: &Term| {
+        term.aliases()
+            .first()
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| term.name().into_owned())
+    };
+    let mut out = format!("comparing {} to {}.\n", first_name(a), first_name(b));
+    let (a, b) = (a.cache(), b.cache());
+
+    let mut booleans: Vec<Entry<bool>> = BooleanCap::ALL
+        .iter()
+        .map(|cap| Entry {
+            name: cap.short_name().to_owned(),
+            a: Some(true).filter(|_| a.booleans.contains(cap)),
+            b: Some(true).filter(|_| b.booleans.contains(cap)),
+        })
+        .collect();
+    let mut numerics: Vec<Entry<i32>> = NumericCap::ALL
+        .iter()
+        .map(|cap| Entry {
+            name: cap.short_name().to_owned(),
+            a: a.numerics.get(cap).copied(),
+            b: b.numerics.get(cap).copied(),
+        })
+        .collect();
+    let mut strings: Vec<Entry<&[u8]>> = StringCap::ALL
+        .iter()
+        .map(|cap| Entry {
+            name: cap.short_name().to_owned(),
+            a: a.strings.get(cap).map(Vec::as_slice),
+            b: b.strings.get(cap).map(Vec::as_slice),
+        })
+        .collect();
+    if options.extended {
+        for name in a.ext_booleans.union(&b.ext_booleans) {
+            booleans.push(Entry {
+                name: name.clone(),
+                a: Some(true).filter(|_| a.ext_booleans.contains(name)),
+                b: Some(true).filter(|_| b.ext_booleans.contains(name)),
+            });
+        }
+        for name in a.ext_numerics.keys().chain(b.ext_numerics.keys()) {
+            if numerics.iter().all(|entry| entry.name != *name) {
+                numerics.push(Entry {
+                    name: name.clone(),
+                    a: a.ext_numerics.get(name).copied(),
+                    b: b.ext_numerics.get(name).copied(),
+                });
+            }
+        }
+        for name in a.ext_strings.keys().chain(b.ext_strings.keys()) {
+            if strings.iter().all(|entry| entry.name != *name) {
+                strings.push(Entry {
+                    name: name.clone(),
+                    a: a.ext_strings.get(name).map(Vec::as_slice),
+                    b: b.ext_strings.get(name).map(Vec::as_slice),
+                });
+            }
+        }
+    }
+
+    let flag = |value: Option<&bool>| if value.is_some() { "T" } else { "F" }.to_owned();
+    if options.mode == ReportMode::Differences {
+        // infocmp writes differing booleans as `name: T:F`.
+        out.push_str("    comparing booleans.\n");
+        booleans.sort_by(|x, y| x.name.cmp(&y.name));
+        for entry in booleans.iter().filter(|entry| entry.a != entry.b) {
+            let (a, b) = (flag(entry.a.as_ref()), flag(entry.b.as_ref()));
+            out.push_str(&format!("\t{}: {}:{}.\n", entry.name, a, b));
+        }
+    } else {
+        write_section(&mut out, "booleans", booleans, options.mode, flag);
+    }
+    write_section(&mut out, "numbers", numerics, options.mode, |value| {
+        value.map_or_else(|| "NULL".to_owned(), |value| value.to_string())
+    });
+    let quoted = |value: Option<&&[u8]>| match value {
+        Some(value) => format!("'{}'", DisplayEscaped::new(value, Escape::Terminfo)),
+        None => "NULL".to_owned(),
+    };
+    write_section(&mut out, "strings", strings, options.mode, quoted);
+    out
+}
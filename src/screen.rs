@@ -0,0 +1,87 @@
+//! Helpers for switching between the normal and the alternate screen, and for synchronizing
+//! updates of the screen.
+
+use crate::cap::StringCap;
+use crate::format::Param;
+use crate::term::Term;
+
+impl Term {
+    /// Returns the sequence switching to the alternate screen, which full screen programs use
+    /// so that the previous content of the terminal is restored on exit. Returns None if the
+    /// terminal doesn't have an alternate screen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("xterm")?;
+    /// assert_eq!(term.enter_alt_screen(), Some(b"\x1b[?1049h\x1b[22;0;0t".to_vec()));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn enter_alt_screen(&self) -> Option<Vec<u8>> {
+        self.expand(StringCap::EnterCaMode, &[])
+    }
+
+    /// Returns the sequence switching back from the alternate screen, or None if the terminal
+    /// doesn't have an alternate screen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert_eq!(term.exit_alt_screen(), None);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn exit_alt_screen(&self) -> Option<Vec<u8>> {
+        self.expand(StringCap::ExitCaMode, &[])
+    }
+
+    /// Returns whether the terminal can synchronize updates, i.e. whether it defines the `Sync`
+    /// extended capability.
+    pub fn supports_sync(&self) -> bool {
+        self.ext_string_cstr("Sync").is_some()
+    }
+
+    /// Returns the sequence making the terminal hold back drawing until end_sync, so that
+    /// partial updates don't flicker, or None if the terminal can't synchronize updates.
+    ///
+    /// The `Sync` extended capability is expanded with 1, which selects DEC private mode 2026
+    /// on the terminals implementing it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert_eq!(term.begin_sync(), None);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn begin_sync(&self) -> Option<Vec<u8>> {
+        self.expand_ext("Sync", &[Param::num(1)])
+    }
+
+    /// Returns the sequence drawing the updates held back since begin_sync, or None if the
+    /// terminal can't synchronize updates. The `Sync` extended capability is expanded with 2.
+    pub fn end_sync(&self) -> Option<Vec<u8>> {
+        self.expand_ext("Sync", &[Param::num(2)])
+    }
+}
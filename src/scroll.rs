@@ -0,0 +1,147 @@
+//! Helpers for scrolling and inserting or deleting lines.
+
+use crate::cap::StringCap;
+use crate::format::Param;
+use crate::term::Term;
+
+impl Term {
+    /// Returns the sequence restricting scrolling to the lines from top to bottom, both zero
+    /// based and inclusive, or None if the terminal doesn't support scroll regions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("xterm")?;
+    /// assert_eq!(term.set_scroll_region(0, 23), Some(b"\x1b[1;24r".to_vec()));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn set_scroll_region(&self, top: u16, bottom: u16) -> Option<Vec<u8>> {
+        self.expand(
+            StringCap::ChangeScrollRegion,
+            &[Param::num(i32::from(top)), Param::num(i32::from(bottom))],
+        )
+    }
+
+    /// Returns the sequence inserting count blank lines at the cursor, or None if the terminal
+    /// can't insert lines.
+    ///
+    /// Uses `il1` for a single line and `il` otherwise, repeating `il1` when `il` is missing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("xterm")?;
+    /// assert_eq!(term.insert_lines(1), Some(b"\x1b[L".to_vec()));
+    /// assert_eq!(term.insert_lines(3), Some(b"\x1b[3L".to_vec()));
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert_eq!(term.insert_lines(3), None);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn insert_lines(&self, count: u16) -> Option<Vec<u8>> {
+        self.repeated(StringCap::InsertLine, StringCap::ParmInsertLine, count)
+    }
+
+    /// Returns the sequence deleting count lines at the cursor, or None if the terminal can't
+    /// delete lines.
+    ///
+    /// Uses `dl1` for a single line and `dl` otherwise, repeating `dl1` when `dl` is missing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("xterm")?;
+    /// assert_eq!(term.delete_lines(2), Some(b"\x1b[2M".to_vec()));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn delete_lines(&self, count: u16) -> Option<Vec<u8>> {
+        self.repeated(StringCap::DeleteLine, StringCap::ParmDeleteLine, count)
+    }
+
+    /// Returns the sequence scrolling the text up by count lines, or None if the terminal can't
+    /// scroll.
+    ///
+    /// Uses `ind` for a single line and `indn` otherwise, repeating `ind` when `indn` is missing.
+    /// Note that `ind` only scrolls when the cursor is on the bottom line of the scroll region.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert_eq!(term.scroll_forward(3), Some(b"\n\n\n".to_vec()));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn scroll_forward(&self, count: u16) -> Option<Vec<u8>> {
+        self.repeated(StringCap::ScrollForward, StringCap::ParmIndex, count)
+    }
+
+    /// Returns the sequence scrolling the text down by count lines, or None if the terminal
+    /// can't scroll backwards.
+    ///
+    /// Uses `ri` for a single line and `rin` otherwise, repeating `ri` when `rin` is missing.
+    /// Note that `ri` only scrolls when the cursor is on the top line of the scroll region.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("xterm")?;
+    /// assert_eq!(term.scroll_reverse(2), Some(b"\x1b[2T".to_vec()));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn scroll_reverse(&self, count: u16) -> Option<Vec<u8>> {
+        self.repeated(StringCap::ScrollReverse, StringCap::ParmRindex, count)
+    }
+
+    /// Returns the sequence performing an action count times, using the capability for a single
+    /// action or the parameterized one, whichever is available and shorter to use.
+    fn repeated(&self, single: StringCap, parm: StringCap, count: u16) -> Option<Vec<u8>> {
+        if count == 0 {
+            if self.has_string(single) || self.has_string(parm) {
+                return Some(vec![]);
+            }
+            return None;
+        }
+        if count > 1 || !self.has_string(single) {
+            if let Some(out) = self.expand(parm, &[Param::num(i32::from(count))]) {
+                return Some(out);
+            }
+        }
+        let once = self.expand(single, &[])?;
+        Some(once.repeat(usize::from(count)))
+    }
+}
@@ -0,0 +1,97 @@
+//! Serialization of capabilities with serde, enabled by the `serde` feature. Every capability is
+//! serialized as a record with its name and its value, so iterators over capabilities can be
+//! passed to serializers directly.
+//!
+//! # Examples
+//!
+//! ```
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use serde_json::json;
+//! use unibilium::Term;
+//!
+//! let term = Term::from_term_name("vt100")?;
+//! let cols = term.numerics().find(|n| n.short_name() == "cols").unwrap();
+//! assert_eq!(
+//!     serde_json::to_value(&cols)?,
+//!     json!({ "name": "columns", "value": 80 })
+//! );
+//!
+//! let mut out = vec![];
+//! serde_json::to_writer(&mut out, &term.strings().collect::<Vec<_>>())?;
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+
+use crate::boolean::{Boolean, ExtBoolean};
+use crate::capability::Capability;
+use crate::numeric::{ExtNumeric, Numeric};
+use crate::string::{ExtString, String};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+/// Serializes a record of the name and the value of a capability.
+fn serialize_record<S, V>(serializer: S, name: &str, value: V) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    V: Serialize,
+{
+    let mut record = serializer.serialize_struct("Capability", 2)?;
+    record.serialize_field("name", name)?;
+    record.serialize_field("value", &value)?;
+    record.end()
+}
+
+impl<'a> Serialize for Boolean<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_record(serializer, &self.name_lossy(), self.supported())
+    }
+}
+
+impl<'a> Serialize for ExtBoolean<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_record(serializer, &self.name_lossy(), self.supported())
+    }
+}
+
+/// Numeric values are serialized as given by unibilium, so negative values mean that the
+/// terminal doesn't define the capability.
+impl<'a> Serialize for Numeric<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_record(serializer, &self.name_lossy(), self.value())
+    }
+}
+
+impl<'a> Serialize for ExtNumeric<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_record(serializer, &self.name_lossy(), self.value())
+    }
+}
+
+/// String values are serialized as strings with invalid UTF-8 sequences replaced, or as none if
+/// the terminal doesn't define the capability.
+impl<'a> Serialize for String<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_record(serializer, &self.name_lossy(), self.value_lossy())
+    }
+}
+
+impl<'a> Serialize for ExtString<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_record(serializer, &self.name_lossy(), self.value_lossy())
+    }
+}
+
+impl<'a> Serialize for Capability<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Capability::Boolean(b) => b.serialize(serializer),
+            Capability::ExtBoolean(b) => b.serialize(serializer),
+            Capability::Numeric(n) => n.serialize(serializer),
+            Capability::ExtNumeric(n) => n.serialize(serializer),
+            Capability::String(s) => s.serialize(serializer),
+            Capability::ExtString(s) => s.serialize(serializer),
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! Cursor shapes and the sequences selecting them.
+
+use crate::format::Param;
+use crate::term::Term;
+
+/// A cursor shape, as selected by DECSCUSR.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum CursorShape {
+    /// The shape configured by the user.
+    Default,
+    BlinkingBlock,
+    SteadyBlock,
+    BlinkingUnderline,
+    SteadyUnderline,
+    BlinkingBar,
+    SteadyBar,
+}
+
+impl CursorShape {
+    /// Returns the parameter of DECSCUSR for the shape.
+    fn to_param(self) -> i32 {
+        match self {
+            CursorShape::Default => 0,
+            CursorShape::BlinkingBlock => 1,
+            CursorShape::SteadyBlock => 2,
+            CursorShape::BlinkingUnderline => 3,
+            CursorShape::SteadyUnderline => 4,
+            CursorShape::BlinkingBar => 5,
+            CursorShape::SteadyBar => 6,
+        }
+    }
+}
+
+impl Term {
+    /// Returns the sequence changing the shape of the cursor with the `Ss` extended capability,
+    /// or None if the terminal doesn't define it. The default shape is restored with the `Se`
+    /// extended capability when available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::shape::CursorShape;
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("xterm-256color")?;
+    /// assert_eq!(term.set_cursor_shape(CursorShape::SteadyBar), Some(b"\x1b[6 q".to_vec()));
+    /// assert_eq!(term.set_cursor_shape(CursorShape::Default), Some(b"\x1b[2 q".to_vec()));
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert_eq!(term.set_cursor_shape(CursorShape::SteadyBar), None);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn set_cursor_shape(&self, shape: CursorShape) -> Option<Vec<u8>> {
+        if shape == CursorShape::Default {
+            if let Some(se) = self.expand_ext("Se", &[]) {
+                return Some(se);
+            }
+        }
+        self.expand_ext("Ss", &[Param::num(shape.to_param())])
+    }
+}
@@ -0,0 +1,74 @@
+//! A read-only Term shared between threads.
+
+use crate::term::Term;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// A reference-counted, read-only handle to a Term. Cloning it is cheap and the clones can be
+/// used from multiple threads at once, without a Mutex. All reading methods of Term are
+/// available through Deref.
+#[derive(Debug, Clone)]
+pub struct SharedTerm {
+    term: Arc<Term>,
+}
+
+impl SharedTerm {
+    /// Returns whether both handles refer to the same Term.
+    pub fn ptr_eq(this: &SharedTerm, other: &SharedTerm) -> bool {
+        Arc::ptr_eq(&this.term, &other.term)
+    }
+}
+
+impl Deref for SharedTerm {
+    type Target = Term;
+
+    fn deref(&self) -> &Term {
+        &self.term
+    }
+}
+
+impl AsRef<Term> for SharedTerm {
+    fn as_ref(&self) -> &Term {
+        &self.term
+    }
+}
+
+impl From<Term> for SharedTerm {
+    fn from(term: Term) -> Self {
+        SharedTerm {
+            term: Arc::new(term),
+        }
+    }
+}
+
+impl Term {
+    /// Converts the Term into a SharedTerm, which can be cloned and queried from multiple
+    /// threads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::thread;
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?.into_shared();
+    /// let handles: Vec<_> = (0..4)
+    ///     .map(|_| {
+    ///         let term = term.clone();
+    ///         thread::spawn(move || term.columns())
+    ///     })
+    ///     .collect();
+    /// for handle in handles {
+    ///     assert_eq!(handle.join().unwrap(), Some(80));
+    /// }
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn into_shared(self) -> SharedTerm {
+        SharedTerm::from(self)
+    }
+}
@@ -0,0 +1,104 @@
+//! The size of the screen, from the tty, the environment or the capabilities.
+
+use crate::env::EnvSnapshot;
+use crate::term::Term;
+
+/// The size of the screen in character cells.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Size {
+    pub columns: u16,
+    pub lines: u16,
+}
+
+/// Asks the tty on stdout, stderr or stdin for its size with the TIOCGWINSZ ioctl.
+#[cfg(all(feature = "ioctl", unix))]
+fn tty_size() -> Option<Size> {
+    for fd in [libc::STDOUT_FILENO, libc::STDERR_FILENO, libc::STDIN_FILENO].iter() {
+        let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+        if unsafe { libc::ioctl(*fd, libc::TIOCGWINSZ, &mut size) } == 0
+            && size.ws_col > 0
+            && size.ws_row > 0
+        {
+            return Some(Size {
+                columns: size.ws_col,
+                lines: size.ws_row,
+            });
+        }
+    }
+    None
+}
+
+#[cfg(not(all(feature = "ioctl", unix)))]
+fn tty_size() -> Option<Size> {
+    None
+}
+
+/// Returns the positive number the variable is set to.
+fn env_dimension(env: &EnvSnapshot, name: &str) -> Option<u16> {
+    env.get_str(name)
+        .and_then(|value| value.trim().parse().ok())
+        .filter(|value| *value > 0)
+}
+
+impl Term {
+    /// Returns the size of the screen, or None if it isn't known. The sources are tried in this
+    /// order:
+    ///
+    /// 1. The tty on stdout, stderr or stdin, when the `ioctl` feature is enabled on Unix.
+    /// 2. The LINES and COLUMNS environment variables.
+    /// 3. The `lines` and `cols` capabilities.
+    ///
+    /// The tty reports both dimensions at once. Otherwise each dimension is taken from the
+    /// first of the other sources which has it, as ncurses does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// if let Some(size) = Term::from_env()?.current_size() {
+    ///     println!("{}x{}", size.columns, size.lines);
+    /// }
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn current_size(&self) -> Option<Size> {
+        tty_size().or_else(|| self.current_size_with_env(&EnvSnapshot::capture()))
+    }
+
+    /// Returns the size of the screen like current_size, but without asking the tty, reading
+    /// LINES and COLUMNS from the given environment. The result only depends on the terminal
+    /// and the environment, which makes it deterministic in tests.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::env::EnvSnapshot;
+    /// use unibilium::size::Size;
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// let env = EnvSnapshot::new();
+    /// let size = Size { columns: 80, lines: 24 };
+    /// assert_eq!(term.current_size_with_env(&env), Some(size));
+    ///
+    /// let env = env.set("COLUMNS", "132");
+    /// let size = Size { columns: 132, lines: 24 };
+    /// assert_eq!(term.current_size_with_env(&env), Some(size));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn current_size_with_env(&self, env: &EnvSnapshot) -> Option<Size> {
+        let columns = env_dimension(env, "COLUMNS").or_else(|| self.columns())?;
+        let lines = env_dimension(env, "LINES").or_else(|| self.lines())?;
+        Some(Size { columns, lines })
+    }
+}
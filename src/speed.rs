@@ -0,0 +1,69 @@
+//! Reading the output speed of a tty, for deciding on padding like curses does.
+
+use crate::term::Term;
+use std::os::unix::io::AsRawFd;
+
+/// The termios speed constants and the speeds in bits per second they stand for. BSDs and
+/// macOS define the constants as the speeds themselves, Linux encodes them.
+const SPEEDS: &[(libc::speed_t, u32)] = &[
+    (libc::B50, 50),
+    (libc::B75, 75),
+    (libc::B110, 110),
+    (libc::B134, 134),
+    (libc::B150, 150),
+    (libc::B200, 200),
+    (libc::B300, 300),
+    (libc::B600, 600),
+    (libc::B1200, 1200),
+    (libc::B1800, 1800),
+    (libc::B2400, 2400),
+    (libc::B4800, 4800),
+    (libc::B9600, 9600),
+    (libc::B19200, 19200),
+    (libc::B38400, 38400),
+    (libc::B57600, 57600),
+    (libc::B115200, 115_200),
+    (libc::B230400, 230_400),
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    (libc::B460800, 460_800),
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    (libc::B921600, 921_600),
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    (libc::B4000000, 4_000_000),
+];
+
+impl Term {
+    /// Returns the output speed of the tty in bits per second, as set in its termios settings,
+    /// or None if fd isn't a tty or the speed is 0, which means hang up. Requires the `termios`
+    /// feature.
+    ///
+    /// The speed decides whether delays need padding, see pad_policy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use std::io;
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// let baud = term.output_speed(&io::stdout()).unwrap_or(38400);
+    /// let policy = term.pad_policy(baud);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn output_speed<F: AsRawFd>(&self, fd: &F) -> Option<u32> {
+        let mut termios: libc::termios = unsafe { std::mem::zeroed() };
+        if unsafe { libc::tcgetattr(fd.as_raw_fd(), &mut termios) } != 0 {
+            return None;
+        }
+        let speed = unsafe { libc::cfgetospeed(&termios) };
+        SPEEDS
+            .iter()
+            .find(|(constant, _)| *constant == speed)
+            .map(|(_, baud)| *baud)
+    }
+}
@@ -0,0 +1,125 @@
+//! Counts of the capabilities of a terminal.
+
+use crate::cap::{BooleanCap, NumericCap, StringCap};
+use crate::term::Term;
+use std::fmt;
+
+/// Counts of the standard capabilities of one kind.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KindStats {
+    /// Number of capabilities the terminal sets or defines.
+    pub defined: usize,
+    /// Number of capabilities the terminal doesn't mention.
+    pub absent: usize,
+    /// Number of capabilities the terminal explicitly cancels.
+    pub cancelled: usize,
+}
+
+impl fmt::Display for KindStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} defined, {} absent, {} cancelled",
+            self.defined, self.absent, self.cancelled
+        )
+    }
+}
+
+/// Counts of the capabilities of a terminal, as returned by Term::stats.
+///
+/// unibilium only keeps cancelled numeric capabilities apart from absent ones, so cancelled
+/// booleans and strings are counted as absent.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TermStats {
+    pub booleans: KindStats,
+    pub numerics: KindStats,
+    pub strings: KindStats,
+    /// Number of extended boolean capabilities, whether set or not.
+    pub ext_booleans: usize,
+    /// Number of extended numeric capabilities, whether defined or not.
+    pub ext_numerics: usize,
+    /// Number of extended string capabilities, whether defined or not.
+    pub ext_strings: usize,
+    /// Total length of the values of all defined standard and extended string capabilities.
+    pub string_bytes: usize,
+}
+
+impl TermStats {
+    /// Returns the number of defined standard capabilities of all kinds, which is a rough
+    /// measure of how rich the terminal description is.
+    pub fn defined(&self) -> usize {
+        self.booleans.defined + self.numerics.defined + self.strings.defined
+    }
+}
+
+impl fmt::Display for TermStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "booleans: {}", self.booleans)?;
+        writeln!(f, "numerics: {}", self.numerics)?;
+        writeln!(f, "strings: {}", self.strings)?;
+        writeln!(
+            f,
+            "extended: {} booleans, {} numerics, {} strings",
+            self.ext_booleans, self.ext_numerics, self.ext_strings
+        )?;
+        write!(f, "string bytes: {}", self.string_bytes)
+    }
+}
+
+impl Term {
+    /// Counts the defined, absent and cancelled capabilities of each kind, the extended
+    /// capabilities and the size of the string values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let vt100 = Term::from_term_name("vt100")?.stats();
+    /// let xterm = Term::from_term_name("xterm")?.stats();
+    /// assert_eq!(vt100.numerics.defined, 4);
+    /// assert!(xterm.defined() > vt100.defined());
+    /// assert!(vt100.to_string().starts_with("booleans: "));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn stats(&self) -> TermStats {
+        let mut stats = TermStats::default();
+        for cap in BooleanCap::ALL.iter() {
+            if self.has_boolean(*cap) {
+                stats.booleans.defined += 1;
+            } else {
+                stats.booleans.absent += 1;
+            }
+        }
+        for cap in NumericCap::ALL.iter() {
+            match self.numeric_raw(*cap) {
+                -2 => stats.numerics.cancelled += 1,
+                value if value < 0 => stats.numerics.absent += 1,
+                _ => stats.numerics.defined += 1,
+            }
+        }
+        for cap in StringCap::ALL.iter() {
+            match self.string_cstr(*cap) {
+                Some(value) => {
+                    stats.strings.defined += 1;
+                    stats.string_bytes += value.to_bytes().len();
+                }
+                None => stats.strings.absent += 1,
+            }
+        }
+        stats.ext_booleans = self.ext_booleans().len();
+        stats.ext_numerics = self.ext_numerics().len();
+        stats.ext_strings = self.ext_strings().len();
+        stats.string_bytes += self
+            .ext_strings()
+            .filter_map(|s| s.value_bytes())
+            .map(<[u8]>::len)
+            .sum::<usize>();
+        stats
+    }
+}
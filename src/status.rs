@@ -0,0 +1,109 @@
+//! The status line some terminals show besides the screen, often the window title.
+
+use crate::cap::{BooleanCap, NumericCap, StringCap};
+use crate::format::{self, Param};
+use crate::term::Term;
+use std::convert::TryFrom;
+use std::ffi::CStr;
+
+/// The status line capabilities of a terminal, which are only meaningful together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusLine<'a> {
+    /// Number of columns of the status line (`wsl`), or None if it is as wide as the screen.
+    pub width: Option<u16>,
+    /// Whether escape sequences can be written on the status line (`eslok`).
+    pub escapes_ok: bool,
+    /// Moves to a column of the status line (`tsl`).
+    pub to: &'a CStr,
+    /// Returns from the status line (`fsl`).
+    pub from: &'a CStr,
+    /// Disables the status line (`dsl`).
+    pub disable: Option<&'a CStr>,
+}
+
+impl StatusLine<'_> {
+    /// Returns the sequence writing text on the status line from the column, and returning to
+    /// the screen. Control characters are removed unless escapes_ok, and the text is cut at the
+    /// width of the status line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("tmux")?;
+    /// let status = term.status_line().unwrap();
+    /// assert_eq!(status.write(0, "make\x1b[2J"), b"\x1b]0;make[2J\x07");
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn write(&self, column: u16, text: &str) -> Vec<u8> {
+        let mut out = vec![];
+        format::format_into(self.to, &[Param::num(i32::from(column))], &mut out);
+        let available = match self.width {
+            Some(width) => usize::from(width.saturating_sub(column)),
+            None => usize::MAX,
+        };
+        let text = text
+            .chars()
+            .filter(|c| self.escapes_ok || !c.is_control())
+            .take(available);
+        for c in text {
+            let mut buf = [0; 4];
+            out.extend(c.encode_utf8(&mut buf).as_bytes());
+        }
+        format::format_into(self.from, &[], &mut out);
+        out
+    }
+
+    /// Returns the sequence disabling the status line, or None if the terminal can't.
+    pub fn disable(&self) -> Option<Vec<u8>> {
+        self.disable.map(|disable| {
+            let mut out = vec![];
+            format::format_into(disable, &[], &mut out);
+            out
+        })
+    }
+}
+
+impl Term {
+    /// Returns the status line of the terminal, or None if it has none: it needs the `hs`
+    /// boolean and the `tsl` and `fsl` string capabilities.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("tmux")?;
+    /// let status = term.status_line().unwrap();
+    /// assert_eq!(status.width, None);
+    /// assert_eq!(status.disable(), Some(b"\x1b]0;\x07".to_vec()));
+    ///
+    /// assert!(Term::from_term_name("vt100")?.status_line().is_none());
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn status_line(&self) -> Option<StatusLine<'_>> {
+        if !self.has_boolean(BooleanCap::HasStatusLine) {
+            return None;
+        }
+        Some(StatusLine {
+            width: self
+                .numeric_value(NumericCap::WidthStatusLine)
+                .and_then(|width| u16::try_from(width).ok()),
+            escapes_ok: self.has_boolean(BooleanCap::StatusLineEscOk),
+            to: self.string_cstr(StringCap::ToStatusLine)?,
+            from: self.string_cstr(StringCap::FromStatusLine)?,
+            disable: self.string_cstr(StringCap::DisStatusLine),
+        })
+    }
+}
@@ -1,35 +1,65 @@
+use crate::cap::StringCap;
+use crate::error::{CapError, UnescapeError};
 use crate::term::Term;
+use std::borrow::Cow;
+use std::convert::TryFrom;
 use std::ffi::CStr;
 use std::fmt;
-use unibilium_sys::unibi_string;
 
-/// Represents string terminal capabilities.
-#[derive(Debug)]
+/// Represents string terminal capabilities. Its Debug output shows the names and the value.
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use unibilium::testing::MockTerm;
+///
+/// let term = MockTerm::new("fake").string("bel", "\x07").build()?;
+/// let bel = term.strings().find(|s| s.short_name() == "bel").unwrap();
+/// assert_eq!(
+///     format!("{:?}", bel),
+///     "String { name: \"bell\", short_name: \"bel\", value: Some(\"\\u{7}\") }"
+/// );
+/// #
+/// #    Ok(())
+/// # }
+/// ```
 pub struct String<'a> {
     term: &'a Term,
-    string: unibi_string,
+    cap: StringCap,
 }
 
 impl<'a> String<'a> {
-    /// Creates a string capability from its lower level representation. Use Term::booleans
-    /// instead, this is intended for internal use.
-    pub(crate) fn from_unibi_string_unchecked(string: unibi_string, term: &'a Term) -> Self {
-        String { string, term }
+    /// Creates a string capability for the standard capability. It's intended for internal use.
+    pub(crate) fn from_cap(cap: StringCap, term: &'a Term) -> Self {
+        String { cap, term }
     }
 
     /// Returns the name of the capability.
-    ///
-    /// # Panics
-    ///
-    /// Panics if it internally encounters invalid UTF-8 characters.
     pub fn name(&self) -> &str {
-        // Returns static string if called with value between begin and end.
-        let name = unsafe { unibilium_sys::unibi_name_str(self.string) };
-        if name.is_null() {
-            panic!("Invalid unibi_string value: {}", self.string);
-        }
-        let name = unsafe { CStr::from_ptr(name) };
-        name.to_str().expect("Invalid UTF-8 string encountered")
+        self.cap.name()
+    }
+
+    /// Returns the name of the capability like name, but returns an error instead of panicking.
+    pub fn try_name(&self) -> Result<&str, CapError> {
+        Ok(self.cap.name())
+    }
+
+    /// Returns the name of the capability, replacing invalid UTF-8 sequences with
+    /// U+FFFD REPLACEMENT CHARACTER instead of panicking.
+    pub fn name_lossy(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.cap.name())
+    }
+
+    /// Returns the short terminfo name of the capability.
+    pub fn short_name(&self) -> &str {
+        self.cap.short_name()
+    }
+
+    /// Returns the short terminfo name of the capability like short_name, but returns an error
+    /// instead of panicking.
+    pub fn try_short_name(&self) -> Result<&str, CapError> {
+        Ok(self.cap.short_name())
     }
 
     /// Returns the value of the capability.
@@ -38,33 +68,225 @@ impl<'a> String<'a> {
     ///
     /// Panics if it internally encounters invalid UTF-8 characters.
     pub fn value(&self) -> Option<&str> {
-        let value = unsafe { unibilium_sys::unibi_get_str(self.term.unibi_term(), self.string) };
-        if value.is_null() {
-            return None;
-        }
-        let value = unsafe { CStr::from_ptr(value) };
-        Some(value.to_str().expect("Invalid UTF-8 string encountered"))
+        self.value_cstr()
+            .map(|value| value.to_str().expect("Invalid UTF-8 string encountered"))
+    }
+
+    /// Returns the value of the capability like value, but returns an error instead of
+    /// panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// for s in term.strings() {
+    ///     match s.try_value() {
+    ///         Ok(value) => println!("{}: {:?}", s.try_name()?, value),
+    ///         Err(err) => println!("{}: {}", s.try_name()?, err),
+    ///     }
+    /// }
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn try_value(&self) -> Result<Option<&str>, CapError> {
+        self.value_cstr()
+            .map(CStr::to_str)
+            .transpose()
+            .map_err(CapError::NotUnicode)
+    }
+
+    /// Returns the value of the capability as raw bytes. Unlike value, it works for values which
+    /// aren't valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// let cup = term
+    ///     .strings()
+    ///     .find(|s| s.short_name() == "cup")
+    ///     .unwrap();
+    /// assert_eq!(cup.value_bytes(), Some(&b"\x1b[%i%p1%d;%p2%dH$<5>"[..]));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn value_bytes(&self) -> Option<&'a [u8]> {
+        self.value_cstr().map(CStr::to_bytes)
+    }
+
+    /// Returns the value of the capability as a C string, for passing it to C functions without
+    /// copying.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// let clear = term
+    ///     .strings()
+    ///     .find(|s| s.short_name() == "clear")
+    ///     .unwrap();
+    /// assert_eq!(clear.value_cstr().unwrap().to_bytes(), b"\x1b[H\x1b[J$<50>");
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn value_cstr(&self) -> Option<&'a CStr> {
+        self.term.string_cstr(self.cap)
+    }
+
+    /// Returns the value of the capability, replacing invalid UTF-8 sequences with
+    /// U+FFFD REPLACEMENT CHARACTER instead of panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// for s in term.strings() {
+    ///     println!("{}: {:?}", s.name_lossy(), s.value_lossy());
+    /// }
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn value_lossy(&self) -> Option<Cow<'a, str>> {
+        self.value_bytes().map(std::string::String::from_utf8_lossy)
     }
 
     /// Returns escaped std::string::String representing the value of the capability. Escaping is
     /// done according to the rules by std::ascii::escape_default, with the exception that
-    /// escape(0x1b) is represented as '^['.
+    /// escape(0x1b) is represented as '^['. See Escape::Legacy.
     pub fn escaped_value(&self) -> Option<std::string::String> {
-        escape_string(self.value())
+        self.escaped_value_with(Escape::Legacy)
+    }
+
+    /// Returns escaped std::string::String representing the value of the capability, using the
+    /// given escaping style.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::string::Escape;
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// let el = term
+    ///     .strings()
+    ///     .find(|s| s.short_name() == "el")
+    ///     .unwrap();
+    /// assert_eq!(el.escaped_value_with(Escape::Caret).unwrap(), "^[[K$<3>");
+    /// assert_eq!(el.escaped_value_with(Escape::Terminfo).unwrap(), "\\E[K$<3>");
+    /// assert_eq!(el.escaped_value_with(Escape::Hex).unwrap(), "\\x1b[K$<3>");
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn escaped_value_with(&self, style: Escape) -> Option<std::string::String> {
+        self.display_value(style).map(|value| value.to_string())
+    }
+
+    /// Returns a wrapper displaying the value of the capability escaped in the given style,
+    /// without allocating like escaped_value_with does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::string::Escape;
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// for s in term.strings() {
+    ///     if let Some(value) = s.display_value(Escape::Terminfo) {
+    ///         println!("{}={},", s.short_name(), value);
+    ///     }
+    /// }
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn display_value(&self, style: Escape) -> Option<DisplayEscaped<'a>> {
+        self.value_bytes()
+            .map(|value| DisplayEscaped::new(value, style))
+    }
+
+    /// Copies the names and the value of the capability into a StringValue, which doesn't
+    /// borrow the Term and displays the same way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// let cuu1 = term.strings().find(|s| s.short_name() == "cuu1").unwrap();
+    /// let owned = cuu1.to_owned();
+    /// assert_eq!(owned.name, "cursor_up");
+    /// assert_eq!(owned.value, Some(b"\x1b[A$<2>".to_vec()));
+    /// assert_eq!(owned.to_string(), "cursor_up: ^[[A$<2>");
+    /// assert_eq!(owned.to_string(), cuu1.to_string());
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn to_owned(&self) -> StringValue {
+        StringValue {
+            name: self.name_lossy().into_owned(),
+            short_name: self.try_short_name().unwrap_or_default().to_owned(),
+            value: self.value_bytes().map(<[u8]>::to_vec),
+        }
+    }
+}
+
+impl<'a> fmt::Debug for String<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("String")
+            .field("name", &self.name_lossy())
+            .field("short_name", &self.try_short_name().unwrap_or_default())
+            .field("value", &self.value_lossy())
+            .finish()
     }
 }
 
 impl<'a> fmt::Display for String<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.escaped_value() {
-            None => write!(f, "{}: NULL", self.name()),
-            Some(value) => write!(f, "{}: {}", self.name(), value),
+        match self.display_value(Escape::Legacy) {
+            None => write!(f, "{}: NULL", self.name_lossy()),
+            Some(value) => write!(f, "{}: {}", self.name_lossy(), value),
         }
     }
 }
 
 /// Represents extended string terminal capabilities.
-#[derive(Debug)]
 pub struct ExtString<'a> {
     index: u64,
     term: &'a Term,
@@ -83,60 +305,409 @@ impl<'a> ExtString<'a> {
     ///
     /// Panics if it internally encounters invalid UTF-8 characters.
     pub fn name(&self) -> &str {
-        // Returns static string if called with value between 0 and count
-        let name =
-            unsafe { unibilium_sys::unibi_get_ext_str_name(self.term.unibi_term(), self.index) };
-        if name.is_null() {
-            panic!(
-                "Invalid index for extended string capability: {}",
-                self.index
-            );
-        }
-        let name = unsafe { CStr::from_ptr(name) };
+        let name = self
+            .term
+            .backend()
+            .ext_string_name(self.index)
+            .unwrap_or_else(|| {
+                panic!(
+                    "Invalid index for extended string capability: {}",
+                    self.index
+                )
+            });
         name.to_str().expect("Invalid UTF-8 string encountered")
     }
 
+    /// Returns the name of the capability like name, but returns an error instead of panicking.
+    pub fn try_name(&self) -> Result<&str, CapError> {
+        let name = self
+            .term
+            .backend()
+            .ext_string_name(self.index)
+            .ok_or(CapError::InvalidIndex(self.index))?;
+        name.to_str().map_err(CapError::NotUnicode)
+    }
+
+    /// Returns the name of the capability, replacing invalid UTF-8 sequences with
+    /// U+FFFD REPLACEMENT CHARACTER instead of panicking.
+    pub fn name_lossy(&self) -> Cow<'_, str> {
+        match self.term.backend().ext_string_name(self.index) {
+            Some(name) => name.to_string_lossy(),
+            None => Cow::Borrowed(""),
+        }
+    }
+
     /// Returns the value of the capability.
     ///
     /// # Panics
     ///
     /// Panics if it internally encounters invalid UTF-8 characters.
     pub fn value(&self) -> Option<&str> {
-        let value = unsafe { unibilium_sys::unibi_get_ext_str(self.term.unibi_term(), self.index) };
-        if value.is_null() {
-            return None;
-        }
-        let value = unsafe { CStr::from_ptr(value) };
-        Some(value.to_str().expect("Invalid UTF-8 string encountered"))
+        self.value_cstr()
+            .map(|value| value.to_str().expect("Invalid UTF-8 string encountered"))
+    }
+
+    /// Returns the value of the capability like value, but returns an error instead of
+    /// panicking.
+    pub fn try_value(&self) -> Result<Option<&str>, CapError> {
+        self.value_cstr()
+            .map(CStr::to_str)
+            .transpose()
+            .map_err(CapError::NotUnicode)
+    }
+
+    /// Returns the value of the capability as raw bytes. Unlike value, it works for values which
+    /// aren't valid UTF-8.
+    pub fn value_bytes(&self) -> Option<&'a [u8]> {
+        self.value_cstr().map(CStr::to_bytes)
+    }
+
+    /// Returns the value of the capability as a C string, for passing it to C functions without
+    /// copying.
+    pub fn value_cstr(&self) -> Option<&'a CStr> {
+        self.term.backend().ext_string(self.index)
+    }
+
+    /// Returns the value of the capability, replacing invalid UTF-8 sequences with
+    /// U+FFFD REPLACEMENT CHARACTER instead of panicking.
+    pub fn value_lossy(&self) -> Option<Cow<'a, str>> {
+        self.value_bytes().map(std::string::String::from_utf8_lossy)
     }
 
     /// Returns escaped std::string::String representing the value of the capability. Escaping is
     /// done according to the rules by std::ascii::escape_default, with the exception that
-    /// escape(0x1b) is represented as '^['.
+    /// escape(0x1b) is represented as '^['. See Escape::Legacy.
     pub fn escaped_value(&self) -> Option<std::string::String> {
-        escape_string(self.value())
+        self.escaped_value_with(Escape::Legacy)
+    }
+
+    /// Returns escaped std::string::String representing the value of the capability, using the
+    /// given escaping style.
+    pub fn escaped_value_with(&self, style: Escape) -> Option<std::string::String> {
+        self.display_value(style).map(|value| value.to_string())
+    }
+
+    /// Returns a wrapper displaying the value of the capability escaped in the given style,
+    /// without allocating like escaped_value_with does.
+    pub fn display_value(&self, style: Escape) -> Option<DisplayEscaped<'a>> {
+        self.value_bytes()
+            .map(|value| DisplayEscaped::new(value, style))
+    }
+
+    /// Copies the name and the value of the capability into a StringValue, which doesn't
+    /// borrow the Term. Extended capabilities only have a short name, which is used for both
+    /// names.
+    pub fn to_owned(&self) -> StringValue {
+        let name = self.name_lossy().into_owned();
+        StringValue {
+            short_name: name.clone(),
+            name,
+            value: self.value_bytes().map(<[u8]>::to_vec),
+        }
+    }
+}
+
+/// The names and the value of a string capability, standard or extended, owned rather than
+/// borrowed from a Term. The value is None if the terminal doesn't define the capability.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct StringValue {
+    pub name: std::string::String,
+    pub short_name: std::string::String,
+    pub value: Option<Vec<u8>>,
+}
+
+impl fmt::Display for StringValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value {
+            None => write!(f, "{}: NULL", self.name),
+            Some(ref value) => write!(
+                f,
+                "{}: {}",
+                self.name,
+                DisplayEscaped::new(value, Escape::Legacy)
+            ),
+        }
+    }
+}
+
+impl<'a> fmt::Debug for ExtString<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtString")
+            .field("name", &self.name_lossy())
+            .field("value", &self.value_lossy())
+            .finish()
     }
 }
 
 impl<'a> fmt::Display for ExtString<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.escaped_value() {
-            None => write!(f, "{}: NULL", self.name()),
-            Some(value) => write!(f, "{}: {}", self.name(), value),
+        match self.display_value(Escape::Legacy) {
+            None => write!(f, "{}: NULL", self.name_lossy()),
+            Some(value) => write!(f, "{}: {}", self.name_lossy(), value),
         }
     }
 }
 
-fn escape_string(s: Option<&str>) -> Option<std::string::String> {
-    s.map(|s| {
-        std::string::String::from_utf8(
-            s.as_bytes()
-                .iter()
-                .map(|c| std::ascii::escape_default(*c))
-                .flatten()
-                .collect(),
-        )
-        .expect("Invalid UTF-8 string encountered")
-        .replace("\\x1b", "^[")
-    })
+/// The style of escaping bytes of capability values for display.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Escape {
+    /// Control characters in caret notation, e.g. `^[` for escape, `^J` for newline and `^?`
+    /// for delete. The 8-bit C1 control characters are written as `M-` followed by the caret
+    /// notation of the corresponding 7-bit character, e.g. `M-^[` for CSI, like `cat -v` does.
+    /// Backslashes and carets are escaped with a backslash, other bytes outside of printable
+    /// ASCII are written as `\xNN`.
+    Caret,
+    /// The notation of terminfo source files, as understood by tic: `\E` for escape, `\n`,
+    /// `\r`, `\t`, `\b` and `\f` for the usual control characters, caret notation for the
+    /// others including delete, and octal for bytes outside of ASCII. Backslashes, carets,
+    /// commas and colons are escaped with a backslash.
+    Terminfo,
+    /// The rules of std::ascii::escape_default, e.g. `\x1b` for escape and `\n` for newline.
+    Hex,
+    /// No escaping. Invalid UTF-8 sequences are replaced with U+FFFD REPLACEMENT CHARACTER.
+    /// Control characters are written as they are, so the output shouldn't go to a terminal.
+    Raw,
+    /// The rules of std::ascii::escape_default, except that escape is written as `^[`. This is
+    /// what escaped_value and the Display of String and ExtString use, as they always did.
+    Legacy,
+    /// Well known sequences by their names in angle brackets, like Caret otherwise: `<CSI>`,
+    /// `<OSC>`, `<DCS>`, `<APC>`, `<PM>` and `<ST>` for both their 7-bit forms starting with
+    /// escape and their 8-bit forms, `<ESC>` for other escapes, and `<BEL>`, `<BS>`, `<HT>`,
+    /// `<LF>` and `<CR>`. Angle brackets are escaped with a backslash.
+    Symbolic,
+}
+
+/// Displays bytes escaped in the given style, writing them directly to the formatter.
+///
+/// # Examples
+///
+/// ```
+/// use unibilium::string::{DisplayEscaped, Escape};
+///
+/// let escaped = DisplayEscaped::new(b"\x1b[H\n", Escape::Caret);
+/// assert_eq!(escaped.to_string(), "^[[H^J");
+///
+/// let escaped = DisplayEscaped::new(b"\x1b[H\n", Escape::Legacy);
+/// assert_eq!(escaped.to_string(), "^[[H\\n");
+///
+/// let escaped = DisplayEscaped::new(b"\x9b2J\x7f", Escape::Caret);
+/// assert_eq!(escaped.to_string(), "M-^[2J^?");
+///
+/// let escaped = DisplayEscaped::new(b"\x9b2J\x7f", Escape::Terminfo);
+/// assert_eq!(escaped.to_string(), "\\2332J^?");
+///
+/// let escaped = DisplayEscaped::new(b"\x1b]2;<title>\x07\x1b[?5h\x1b7", Escape::Symbolic);
+/// assert_eq!(escaped.to_string(), "<OSC>2;\\<title\\><BEL><CSI>?5h<ESC>7");
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct DisplayEscaped<'a> {
+    bytes: &'a [u8],
+    style: Escape,
+}
+
+impl<'a> DisplayEscaped<'a> {
+    pub fn new(bytes: &'a [u8], style: Escape) -> Self {
+        DisplayEscaped { bytes, style }
+    }
+}
+
+impl<'a> fmt::Display for DisplayEscaped<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_escaped(f, self.bytes, self.style)
+    }
+}
+
+/// Returns the bytes written in the notation of terminfo source files, as read by tic: `\E`
+/// and `\e` for escape, `\n`, `\l`, `\r`, `\t`, `\b`, `\f` and `\s` for newline,
+/// line feed, carriage return, tab, backspace, form feed and space, octal escapes of up to
+/// three digits like `\017`, and caret notation like `^X` and `^?`. Backslashes, carets,
+/// commas and colons are escaped with a backslash. As NUL can't be part of a value, `\0`
+/// stands for `\200` like in tic. Percent signs, `%%` included, are kept for
+/// format::format_into to expand.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use unibilium::error::UnescapeError;
+/// use unibilium::string;
+///
+/// assert_eq!(string::unescape("\\E[31m")?, b"\x1b[31m");
+/// assert_eq!(string::unescape("^N\\017\\,%%")?, b"\x0e\x0f,%%");
+/// assert_eq!(string::unescape("\\777"), Err(UnescapeError::InvalidOctal(0)));
+/// assert_eq!(string::unescape("ab^"), Err(UnescapeError::Incomplete(2)));
+/// assert_eq!(string::unescape("\\q"), Err(UnescapeError::InvalidEscape(0)));
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns UnescapeError if an escape is incomplete, unknown or out of range.
+pub fn unescape(s: &str) -> Result<Vec<u8>, UnescapeError> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        let byte = bytes[i];
+        i += 1;
+        if byte != b'\\' && byte != b'^' {
+            out.push(byte);
+            continue;
+        }
+        let next = *bytes.get(i).ok_or(UnescapeError::Incomplete(start))?;
+        i += 1;
+        if byte == b'^' {
+            out.push(match next {
+                b'?' => 0x7f,
+                b'@'..=b'_' | b'a'..=b'z' => next & 0x1f,
+                _ => return Err(UnescapeError::InvalidEscape(start)),
+            });
+            continue;
+        }
+        out.push(match next {
+            b'E' | b'e' => 0x1b,
+            b'n' | b'l' => b'\n',
+            b'r' => b'\r',
+            b't' => b'\t',
+            b'b' => 0x08,
+            b'f' => 0x0c,
+            b's' => b' ',
+            b'\\' | b'^' | b',' | b':' => next,
+            b'0'..=b'7' => {
+                let mut value = u32::from(next - b'0');
+                while i < bytes.len() && i - start < 4 && (b'0'..=b'7').contains(&bytes[i]) {
+                    value = value * 8 + u32::from(bytes[i] - b'0');
+                    i += 1;
+                }
+                match value {
+                    0 => 0x80,
+                    value => u8::try_from(value).map_err(|_| UnescapeError::InvalidOctal(start))?,
+                }
+            }
+            _ => return Err(UnescapeError::InvalidEscape(start)),
+        });
+    }
+    Ok(out)
+}
+
+/// Returns the bytes in the notation of terminfo source files, the reverse of unescape. This
+/// is DisplayEscaped with Escape::Terminfo collected into a string.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use unibilium::string;
+///
+/// let escaped = string::escape(b"\x1b[%i%p1%d;%p2%dH\x0f");
+/// assert_eq!(escaped, "\\E[%i%p1%d;%p2%dH^O");
+/// assert_eq!(string::unescape(&escaped)?, b"\x1b[%i%p1%d;%p2%dH\x0f");
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+pub fn escape(bytes: &[u8]) -> std::string::String {
+    DisplayEscaped::new(bytes, Escape::Terminfo).to_string()
+}
+
+/// Returns the caret notation letter of an ASCII control character.
+fn caret(byte: u8) -> char {
+    (byte ^ 0x40) as char
+}
+
+/// Writes the bytes escaped in the given style.
+fn write_escaped(out: &mut dyn fmt::Write, bytes: &[u8], style: Escape) -> fmt::Result {
+    if style == Escape::Raw {
+        return out.write_str(&std::string::String::from_utf8_lossy(bytes));
+    }
+    if style == Escape::Symbolic {
+        return write_symbolic(out, bytes);
+    }
+    for byte in bytes.iter().copied() {
+        match (style, byte) {
+            (Escape::Legacy, 0x1b) => out.write_str("^[")?,
+            (Escape::Hex, _) | (Escape::Legacy, _) => {
+                for c in std::ascii::escape_default(byte) {
+                    out.write_char(c as char)?;
+                }
+            }
+            (Escape::Terminfo, 0x1b) => out.write_str("\\E")?,
+            (Escape::Terminfo, b'\n') => out.write_str("\\n")?,
+            (Escape::Terminfo, b'\r') => out.write_str("\\r")?,
+            (Escape::Terminfo, b'\t') => out.write_str("\\t")?,
+            (Escape::Terminfo, 0x08) => out.write_str("\\b")?,
+            (Escape::Terminfo, 0x0c) => out.write_str("\\f")?,
+            (Escape::Terminfo, b',') | (Escape::Terminfo, b':') => {
+                write!(out, "\\{}", byte as char)?
+            }
+            (_, b'\\') | (_, b'^') => write!(out, "\\{}", byte as char)?,
+            (_, 0x00..=0x1f) | (_, 0x7f) => write!(out, "^{}", caret(byte))?,
+            (Escape::Caret, 0x80..=0x9f) => write!(out, "M-^{}", caret(byte & 0x7f))?,
+            (_, 0x20..=0x7e) => out.write_char(byte as char)?,
+            (Escape::Terminfo, _) => write!(out, "\\{:03o}", byte)?,
+            (_, _) => write!(out, "\\x{:02x}", byte)?,
+        }
+    }
+    Ok(())
+}
+
+/// Returns the name of the sequence introduced by the byte after an escape, or by the byte
+/// itself in its 8-bit form.
+fn introducer_name(byte: u8) -> Option<&'static str> {
+    match byte {
+        b'[' | 0x9b => Some("CSI"),
+        b']' | 0x9d => Some("OSC"),
+        b'P' | 0x90 => Some("DCS"),
+        b'_' | 0x9f => Some("APC"),
+        b'^' | 0x9e => Some("PM"),
+        b'\\' | 0x9c => Some("ST"),
+        _ => None,
+    }
+}
+
+/// Writes the bytes escaped in the Symbolic style.
+fn write_symbolic(out: &mut dyn fmt::Write, bytes: &[u8]) -> fmt::Result {
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        i += 1;
+        let name = match byte {
+            0x1b => match bytes.get(i).copied().and_then(introducer_name) {
+                Some(name) => {
+                    i += 1;
+                    name
+                }
+                None => "ESC",
+            },
+            0x80..=0x9f => match introducer_name(byte) {
+                Some(name) => name,
+                None => {
+                    write!(out, "M-^{}", caret(byte & 0x7f))?;
+                    continue;
+                }
+            },
+            0x07 => "BEL",
+            0x08 => "BS",
+            b'\t' => "HT",
+            b'\n' => "LF",
+            b'\r' => "CR",
+            b'<' | b'>' => {
+                write!(out, "\\{}", byte as char)?;
+                continue;
+            }
+            _ => {
+                write_escaped(out, &[byte], Escape::Caret)?;
+                continue;
+            }
+        };
+        write!(out, "<{}>", name)?;
+    }
+    Ok(())
 }
@@ -0,0 +1,69 @@
+//! Tab stops, and setting them like the `tabs` utility.
+
+use crate::cap::{NumericCap, StringCap};
+use crate::term::Term;
+use std::convert::TryFrom;
+
+impl Term {
+    /// Returns the distance between the tab stops the terminal has initially (`it`), or None if
+    /// it isn't known.
+    pub fn init_tabs(&self) -> Option<u16> {
+        self.numeric_value(NumericCap::InitTabs)
+            .and_then(|value| u16::try_from(value).ok())
+    }
+
+    /// Returns the sequence moving to the next tab stop (`ht`), or None if the terminal doesn't
+    /// define it.
+    pub fn tab(&self) -> Option<Vec<u8>> {
+        self.expand(StringCap::Tab, &[])
+    }
+
+    /// Returns the sequence moving to the previous tab stop (`cbt`), or None if the terminal
+    /// doesn't define it.
+    pub fn back_tab(&self) -> Option<Vec<u8>> {
+        self.expand(StringCap::BackTab, &[])
+    }
+
+    /// Returns the sequence replacing the tab stops of a screen with the given number of columns
+    /// by stops every `every` columns, starting at column `every` counted from 0, as `tabs -N`
+    /// does. Returns None if the terminal can't clear (`tbc`) and set (`hts`) tab stops.
+    ///
+    /// The sequence moves to the first column with a carriage return, clears all tab stops,
+    /// then writes spaces up to each stop and sets it. It ends with a carriage return, so the
+    /// line should be empty or cleared afterwards. An `every` of 0 only clears the stops.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert_eq!(term.init_tabs(), Some(8));
+    /// assert_eq!(
+    ///     term.set_tabs(4, 10),
+    ///     Some(b"\r\x1b[3g    \x1bH    \x1bH\r".to_vec())
+    /// );
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn set_tabs(&self, every: u16, columns: u16) -> Option<Vec<u8>> {
+        let clear = self.expand(StringCap::ClearAllTabs, &[])?;
+        let set = self.expand(StringCap::SetTab, &[])?;
+        let mut out = b"\r".to_vec();
+        out.extend(clear);
+        if every > 0 {
+            let mut column = 0;
+            while columns - column > every {
+                out.resize(out.len() + usize::from(every), b' ');
+                out.extend(&set);
+                column += every;
+            }
+        }
+        out.push(b'\r');
+        Some(out)
+    }
+}
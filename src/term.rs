@@ -1,18 +1,94 @@
-use crate::boolean::{Boolean, ExtBoolean};
+use crate::backend::Backend;
+use crate::cap::{BooleanCap, CapId, CapValue, NumericCap, StringCap};
+use crate::capability::{glob_match, Capability};
+use crate::entry::Entry;
 use crate::error::TermError;
-use crate::numeric::{ExtNumeric, Numeric};
-use crate::string::{ExtString, String};
-use std::error::Error;
-use std::ffi::CString;
-use unibilium_sys::{
-    unibi_boolean, unibi_from_env, unibi_from_term, unibi_numeric, unibi_string, unibi_term,
+use crate::iter::{
+    Booleans, Capabilities, ExtBooleans, ExtNumerics, ExtStrings, Numerics, Strings,
 };
+use crate::shared::SharedTerm;
+use crate::trace::event;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::ptr;
+use std::sync::{Mutex, PoisonError};
+
+pub use crate::merge::{merge3, Conflict, MergeError};
+pub use crate::tput::{tput, TputError};
+
+#[cfg(any(feature = "pure-rust", target_family = "wasm"))]
+type Inner = Entry;
+#[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+type Inner = crate::unibi::Unibi;
+#[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+use unibilium_sys::unibi_term;
 
 /// The main structure provided by this library. Used to represent and manipulate capabilities of a
 /// terminal.
-#[derive(Debug, Hash, Eq, PartialEq)]
+///
+/// It is Send and Sync, so it can be moved to other threads or shared between them, e.g. in a
+/// `static` initialized once.
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::sync::Arc;
+/// use std::thread;
+/// use unibilium::Term;
+///
+/// let term = Arc::new(Term::from_term_name("vt100")?);
+/// let other = Arc::clone(&term);
+/// let columns = thread::spawn(move || other.columns()).join().unwrap();
+/// assert_eq!(columns, term.columns());
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+///
+/// Terms are compared and hashed by their contents, i.e. their names and capabilities, so
+/// loading the same entry twice gives equal Terms.
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use unibilium::Term;
+///
+/// assert_eq!(Term::from_term_name("vt100")?, Term::from_term_name("vt100")?);
+/// assert_ne!(Term::from_term_name("vt100")?, Term::from_term_name("xterm")?);
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+///
+/// Its Debug output lists the description, the aliases and the number of capabilities of each
+/// kind the terminal defines.
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use unibilium::testing::MockTerm;
+///
+/// let term = MockTerm::new("fake").bool("am", true).num("cols", 80).build()?;
+/// assert_eq!(
+///     format!("{:?}", term),
+///     "Term { name: \"fake\", aliases: [\"fake\"], booleans: 1, numerics: 1, strings: 0, \
+///      ext_booleans: 0, ext_numerics: 0, ext_strings: 0 }"
+/// );
+/// #
+/// #    Ok(())
+/// # }
+/// ```
 pub struct Term {
-    term: *mut unibi_term,
+    inner: Inner,
 }
 
 impl Term {
@@ -32,17 +108,53 @@ impl Term {
     /// # }
     /// ```
     ///
+    /// On Windows, where there is usually neither TERM nor a terminfo database, it falls back to
+    /// the built-in `ms-terminal` entry of the windows module when no entry is found.
+    ///
     /// # Errors
     ///
     /// Returns error if:
     /// * TERM contains terminal without present termcap file
     /// * TERM contains non-UTF8 string
-    pub fn from_env() -> Result<Term, Box<dyn Error>> {
-        let term = unsafe { unibi_from_env() };
-        if term.is_null() {
-            Err(Box::new(TermError::from_term_var()))
-        } else {
-            Ok(Term { term })
+    /// * the termcap file can't be read or parsed
+    pub fn from_env() -> Result<Term, TermError> {
+        let result = Term::from_term_var();
+        #[cfg(windows)]
+        if let Err(TermError::NotFound { .. }) = result {
+            event!(DEBUG, "falling back to the built-in Windows entry");
+            return crate::windows::builtin();
+        }
+        result
+    }
+
+    /// Loads the entry TERM names.
+    fn from_term_var() -> Result<Term, TermError> {
+        // unibilium doesn't know hashed databases, so the lookup goes through the name then.
+        #[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+        if !crate::path::is_overridden() && !cfg!(feature = "hashed-db") {
+            return match Inner::from_env() {
+                Ok(inner) => {
+                    event!(
+                        DEBUG,
+                        term = ?std::env::var_os("TERM"),
+                        "loaded terminfo entry of TERM"
+                    );
+                    Ok(Term { inner })
+                }
+                Err(err) => {
+                    let err = TermError::from_term_var(err);
+                    event!(DEBUG, error = %err, "loading terminfo entry of TERM failed");
+                    Err(err)
+                }
+            };
+        }
+        match std::env::var("TERM") {
+            Ok(name) => Term::from_term_name(&name),
+            Err(std::env::VarError::NotPresent) => Err(TermError::from_os_error(
+                "",
+                io::Error::from(io::ErrorKind::NotFound),
+            )),
+            Err(std::env::VarError::NotUnicode(_)) => Err(TermError::NotUnicode),
         }
     }
 
@@ -71,10 +183,24 @@ impl Term {
     /// # use std::error::Error;
     /// #
     /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::error::TermError;
     /// use unibilium::Term;
     /// let term_name = "blahblah2234";
     ///
     /// assert!(Term::from_term_name(term_name).is_err());
+    ///
+    /// match Term::from_term_name(term_name) {
+    ///     Err(TermError::NotFound { name, searched, .. }) => {
+    ///         assert_eq!(name, term_name);
+    ///         assert!(!searched.is_empty());
+    ///     }
+    ///     _ => panic!("expected NotFound"),
+    /// }
+    ///
+    /// match Term::from_term_name("vt\0100") {
+    ///     Err(TermError::InvalidName { position, .. }) => assert_eq!(position, 2),
+    ///     _ => panic!("expected InvalidName"),
+    /// }
     /// #
     /// #     Ok(())
     /// # }
@@ -84,18 +210,173 @@ impl Term {
     ///
     /// Returns error if:
     /// * name presents terminal without present termcap file
-    /// * name presents non-UTF8 string
-    pub fn from_term_name(name: &str) -> Result<Term, Box<dyn Error>> {
+    /// * name contains NUL
+    /// * the termcap file can't be read or parsed
+    pub fn from_term_name(name: &str) -> Result<Term, TermError> {
         let cname = match CString::new(name) {
             Ok(cname) => cname,
-            Err(_) => return Err(Box::new(TermError::from_name(name))),
+            Err(err) => {
+                return Err(TermError::InvalidName {
+                    name: name.to_owned(),
+                    position: err.nul_position(),
+                    source: err,
+                })
+            }
         };
-        let term = unsafe { unibi_from_term(cname.as_ptr()) };
-        if term.is_null() {
-            Err(Box::new(TermError::from_name(name)))
-        } else {
-            Ok(Term { term })
+        #[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+        if !crate::path::is_overridden() {
+            return match Inner::from_term(&cname) {
+                Ok(inner) => {
+                    event!(
+                        DEBUG,
+                        name,
+                        path = ?crate::path::find_entry(name),
+                        "loaded terminfo entry"
+                    );
+                    Ok(Term { inner })
+                }
+                Err(err) => {
+                    #[cfg(feature = "hashed-db")]
+                    if err.kind() == io::ErrorKind::NotFound {
+                        if let Some(result) = Term::from_hashed_db(name) {
+                            return result;
+                        }
+                    }
+                    let err = TermError::from_os_error(name, err);
+                    event!(DEBUG, name, error = %err, "loading terminfo entry failed");
+                    Err(err)
+                }
+            };
         }
+        // Without unibilium the name was only checked for NUL.
+        #[cfg(any(feature = "pure-rust", target_family = "wasm"))]
+        drop(cname);
+        Term::from_search_dirs(name)
+    }
+
+    /// Loads the entry from the search directories without unibilium, failing like unibilium
+    /// would. It is used for overridden search directories and by the pure Rust backend.
+    fn from_search_dirs(name: &str) -> Result<Term, TermError> {
+        let path = match crate::path::find_entry(name) {
+            Some(path) => path,
+            None => {
+                #[cfg(feature = "hashed-db")]
+                if let Some(result) = Term::from_hashed_db(name) {
+                    return result;
+                }
+                let err = io::Error::from(io::ErrorKind::NotFound);
+                return Err(TermError::from_os_error(name, err));
+            }
+        };
+        let result = fs::read(&path).map_err(TermError::Io).and_then(|data| {
+            match Term::from_compiled(&data) {
+                Err(TermError::Io(err)) => Err(TermError::from_os_error(name, err)),
+                result => result,
+            }
+        });
+        match result {
+            Ok(term) => {
+                event!(DEBUG, name, path = ?path, "loaded terminfo entry");
+                Ok(term)
+            }
+            Err(err) => {
+                event!(DEBUG, name, error = %err, "loading terminfo entry failed");
+                Err(err)
+            }
+        }
+    }
+
+    /// Loads the entry from the hashed databases, or returns None if none of them has it.
+    #[cfg(feature = "hashed-db")]
+    fn from_hashed_db(name: &str) -> Option<Result<Term, TermError>> {
+        let (path, data) = crate::hashed::find_entry(name)?;
+        match Term::from_compiled(&data) {
+            Ok(term) => {
+                event!(DEBUG, name, path = ?path, "loaded terminfo entry");
+                Some(Ok(term))
+            }
+            Err(TermError::Io(err)) => Some(Err(TermError::malformed(path, &data, &err))),
+            Err(err) => Some(Err(err)),
+        }
+    }
+
+    /// Creates a Term from terminfo data in the compiled format. It is intended for internal use.
+    pub(crate) fn from_compiled(data: &[u8]) -> Result<Term, TermError> {
+        #[cfg(any(feature = "pure-rust", target_family = "wasm"))]
+        let inner = Entry::parse(data);
+        #[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+        let inner = Inner::from_mem(data);
+        inner.map(|inner| Term { inner }).map_err(TermError::Io)
+    }
+
+    /// Creates a Term from an entry built in Rust. It goes through the compiled format, so the
+    /// Term reads like one loaded from a file. It is intended for internal use.
+    pub(crate) fn from_entry(entry: &Entry) -> Result<Term, TermError> {
+        match entry.to_bytes() {
+            Some(data) => Term::from_compiled(&data),
+            None => Err(TermError::Io(io::Error::from(io::ErrorKind::InvalidData))),
+        }
+    }
+
+    /// Returns the pointer to the unibilium structure, e.g. to pass it to another C library.
+    /// The Term keeps owning it, so it must not be used after the Term is dropped. Modifying the
+    /// structure through the pointer while the Term is shared between threads is a data race.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert!(!term.as_ptr().is_null());
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+    pub fn as_ptr(&self) -> *mut unibi_term {
+        self.inner.as_ptr()
+    }
+
+    /// Takes ownership of a unibilium structure, which is destroyed with `unibi_destroy` when
+    /// the Term is dropped.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be non-null and point to a valid structure created by unibilium, e.g.
+    /// with `unibi_from_term` or returned by into_raw. Nothing else may destroy it or access it
+    /// afterwards, except through the Term.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let ptr = Term::from_term_name("vt100")?.into_raw();
+    /// let term = unsafe { Term::from_raw(ptr) };
+    /// assert_eq!(term.columns(), Some(80));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+    pub unsafe fn from_raw(term: *mut unibi_term) -> Term {
+        Term {
+            inner: Inner::from_raw(term),
+        }
+    }
+
+    /// Releases ownership of the unibilium structure without destroying it. The caller becomes
+    /// responsible for destroying it with `unibi_destroy`, or for passing it back to from_raw.
+    #[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+    pub fn into_raw(self) -> *mut unibi_term {
+        self.inner.into_raw()
     }
 
     /// Returns all boolean capabilities for the terminal.
@@ -119,15 +400,27 @@ impl Term {
     /// #    Ok(())
     /// # }
     /// ```
-    pub fn booleans(&self) -> Vec<Boolean> {
-        let mut all = vec![];
-        let first = unibi_boolean::unibi_boolean_begin_.0 + 1;
-        let end = unibi_boolean::unibi_boolean_end_.0;
-        for current in first..end {
-            let b = Boolean::from_unibi_bool_unchecked(unibi_boolean(current), self);
-            all.push(b);
-        }
-        all
+    ///
+    /// Taking the last one without going through the others
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// let booleans = term.booleans();
+    /// assert_eq!(booleans.len(), 44);
+    /// assert_eq!(booleans.rev().next().unwrap().short_name(), "OTxr");
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn booleans(&self) -> Booleans<'_> {
+        let first = 0;
+        let end = BooleanCap::ALL.len();
+        Booleans::new(first, end, self)
     }
 
     /// Returns all extended capabilities for the terminal.
@@ -151,14 +444,9 @@ impl Term {
     /// #    Ok(())
     /// # }
     /// ```
-    pub fn ext_booleans(&self) -> Vec<ExtBoolean> {
-        let mut all = vec![];
-        let end = unsafe { unibilium_sys::unibi_count_ext_bool(self.term) };
-        for index in 0..end {
-            let b = ExtBoolean::from_index_unchecked(index, self);
-            all.push(b);
-        }
-        all
+    pub fn ext_booleans(&self) -> ExtBooleans<'_> {
+        let end = self.inner.ext_boolean_count();
+        ExtBooleans::new(0, end, self)
     }
 
     /// Returns all numeric capabilities for the terminal.
@@ -182,15 +470,10 @@ impl Term {
     /// #    Ok(())
     /// # }
     /// ```
-    pub fn numerics(&self) -> Vec<Numeric> {
-        let mut all = vec![];
-        let first = unibi_numeric::unibi_numeric_begin_.0 + 1;
-        let end = unibi_numeric::unibi_numeric_end_.0;
-        for current in first..end {
-            let n = Numeric::from_unibi_numeric_unchecked(unibi_numeric(current), self);
-            all.push(n);
-        }
-        all
+    pub fn numerics(&self) -> Numerics<'_> {
+        let first = 0;
+        let end = NumericCap::ALL.len();
+        Numerics::new(first, end, self)
     }
 
     /// Returns all extended numeric capabilities for the terminal.
@@ -214,14 +497,9 @@ impl Term {
     /// #    Ok(())
     /// # }
     /// ```
-    pub fn ext_numerics(&self) -> Vec<ExtNumeric> {
-        let mut all = vec![];
-        let end = unsafe { unibilium_sys::unibi_count_ext_num(self.term) };
-        for index in 0..end {
-            let b = ExtNumeric::from_index_unchecked(index, self);
-            all.push(b);
-        }
-        all
+    pub fn ext_numerics(&self) -> ExtNumerics<'_> {
+        let end = self.inner.ext_numeric_count();
+        ExtNumerics::new(0, end, self)
     }
 
     /// Returns all string capabilities for the terminal.
@@ -245,15 +523,10 @@ impl Term {
     /// #    Ok(())
     /// # }
     /// ```
-    pub fn strings(&self) -> Vec<String> {
-        let mut all = vec![];
-        let first = unibi_string::unibi_string_begin_.0 + 1;
-        let end = unibi_string::unibi_string_end_.0;
-        for current in first..end {
-            let s = String::from_unibi_string_unchecked(unibi_string(current), self);
-            all.push(s);
-        }
-        all
+    pub fn strings(&self) -> Strings<'_> {
+        let first = 0;
+        let end = StringCap::ALL.len();
+        Strings::new(first, end, self)
     }
 
     /// Returns all extended string capabilities for the terminal.
@@ -277,28 +550,716 @@ impl Term {
     /// #    Ok(())
     /// # }
     /// ```
-    pub fn ext_strings(&self) -> Vec<ExtString> {
-        let mut all = vec![];
-        let end = unsafe { unibilium_sys::unibi_count_ext_str(self.term) };
-        for index in 0..end {
-            let b = ExtString::from_index_unchecked(index, self);
-            all.push(b);
+    pub fn ext_strings(&self) -> ExtStrings<'_> {
+        let end = self.inner.ext_string_count();
+        ExtStrings::new(0, end, self)
+    }
+
+    /// Returns all capabilities of the terminal, standard and extended, of every kind. Iterating
+    /// over `&Term` does the same.
+    ///
+    /// # Examples
+    ///
+    /// Printing the whole entry
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// for cap in &term {
+    ///     println!("{}", cap);
+    /// }
+    ///
+    /// let all = term.capabilities();
+    /// assert_eq!(
+    ///     all.len(),
+    ///     term.booleans().len()
+    ///         + term.ext_booleans().len()
+    ///         + term.numerics().len()
+    ///         + term.ext_numerics().len()
+    ///         + term.strings().len()
+    ///         + term.ext_strings().len()
+    /// );
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn capabilities(&self) -> Capabilities<'_> {
+        Capabilities::new(self)
+    }
+
+    /// Returns the standard and extended boolean capabilities whose short name matches the glob
+    /// pattern. `*` matches any sequence of characters and `?` matches a single character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// for cap in term.booleans_matching("x*") {
+    ///     println!("{}", cap);
+    /// }
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn booleans_matching(&self, pattern: &str) -> Vec<Capability<'_>> {
+        let standard = self
+            .booleans()
+            .filter(|b| glob_match(pattern, b.short_name()))
+            .map(Capability::Boolean);
+        let extended = self
+            .ext_booleans()
+            .filter(|b| glob_match(pattern, &b.name_lossy()))
+            .map(Capability::ExtBoolean);
+        standard.chain(extended).collect()
+    }
+
+    /// Returns the standard and extended numeric capabilities whose short name matches the glob
+    /// pattern. `*` matches any sequence of characters and `?` matches a single character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// let caps = term.numerics_matching("col?");
+    /// assert_eq!(caps[0].short_name(), "cols");
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn numerics_matching(&self, pattern: &str) -> Vec<Capability<'_>> {
+        let standard = self
+            .numerics()
+            .filter(|n| glob_match(pattern, n.short_name()))
+            .map(Capability::Numeric);
+        let extended = self
+            .ext_numerics()
+            .filter(|n| glob_match(pattern, &n.name_lossy()))
+            .map(Capability::ExtNumeric);
+        standard.chain(extended).collect()
+    }
+
+    /// Returns the standard and extended string capabilities whose short name matches the glob
+    /// pattern. `*` matches any sequence of characters and `?` matches a single character.
+    ///
+    /// # Examples
+    ///
+    /// Printing all key related capabilities
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// for cap in term.strings_matching("k*") {
+    ///     println!("{}", cap);
+    /// }
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn strings_matching(&self, pattern: &str) -> Vec<Capability<'_>> {
+        let standard = self
+            .strings()
+            .filter(|s| glob_match(pattern, s.short_name()))
+            .map(Capability::String);
+        let extended = self
+            .ext_strings()
+            .filter(|s| glob_match(pattern, &s.name_lossy()))
+            .map(Capability::ExtString);
+        standard.chain(extended).collect()
+    }
+
+    /// Returns the standard and extended string capabilities whose value contains the given
+    /// byte sequence. An empty sequence matches every defined string capability.
+    ///
+    /// # Examples
+    ///
+    /// Finding which capabilities produce a received escape sequence
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// for cap in term.find_by_value(b"\x1b[A") {
+    ///     println!("{}", cap);
+    /// }
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn find_by_value(&self, needle: &[u8]) -> Vec<Capability<'_>> {
+        let contains = |value: Option<&[u8]>| match value {
+            None => false,
+            Some(_) if needle.is_empty() => true,
+            Some(value) => value.windows(needle.len()).any(|window| window == needle),
+        };
+        let standard = self
+            .strings()
+            .filter(|s| contains(s.value_bytes()))
+            .map(Capability::String);
+        let extended = self
+            .ext_strings()
+            .filter(|s| contains(s.value_bytes()))
+            .map(Capability::ExtString);
+        standard.chain(extended).collect()
+    }
+
+    /// Returns whether the terminal has the boolean capability set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::{BooleanCap, Term};
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert!(term.has_boolean(BooleanCap::AutoRightMargin));
+    /// assert!(!term.has_boolean(BooleanCap::HardCopy));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn has_boolean(&self, cap: BooleanCap) -> bool {
+        self.inner.boolean(cap)
+    }
+
+    /// Returns whether the terminal defines the numeric capability. Absent and cancelled
+    /// capabilities are reported by unibilium as negative values and both yield false.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::{NumericCap, Term};
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert!(term.has_numeric(NumericCap::Columns));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn has_numeric(&self, cap: NumericCap) -> bool {
+        self.inner.numeric(cap) >= 0
+    }
+
+    /// Returns whether the terminal defines the string capability. Absent and cancelled
+    /// capabilities both yield false.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::{StringCap, Term};
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert!(term.has_string(StringCap::CursorAddress));
+    /// assert!(!term.has_string(StringCap::SetAForeground));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn has_string(&self, cap: StringCap) -> bool {
+        self.inner.string(cap).is_some()
+    }
+
+    /// Returns the values of the given standard capabilities in the same order, fetched in one
+    /// pass. It is meant for feature detection reading many capabilities at startup.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::cap::{CapId, CapValue};
+    /// use unibilium::{BooleanCap, NumericCap, StringCap, Term};
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// let values = term.get_many(&[
+    ///     CapId::from(BooleanCap::AutoRightMargin),
+    ///     CapId::from(NumericCap::MaxColors),
+    ///     CapId::from(StringCap::CursorUp),
+    /// ]);
+    /// assert_eq!(
+    ///     values,
+    ///     [
+    ///         CapValue::Boolean(true),
+    ///         CapValue::Numeric(None),
+    ///         CapValue::String(Some(&b"\x1b[A$<2>"[..])),
+    ///     ]
+    /// );
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn get_many(&self, caps: &[CapId]) -> Vec<CapValue<'_>> {
+        caps.iter()
+            .map(|cap| match *cap {
+                CapId::Boolean(cap) => CapValue::Boolean(self.has_boolean(cap)),
+                CapId::Numeric(cap) => CapValue::Numeric(self.numeric_value(cap)),
+                CapId::String(cap) => CapValue::String(self.string_cstr(cap).map(CStr::to_bytes)),
+            })
+            .collect()
+    }
+
+    /// Returns whether the terminal has an extended capability with the given name which is set
+    /// (booleans), non-negative (numerics) or non-NULL (strings).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert!(!term.has_ext("blahblah2234"));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn has_ext(&self, name: &str) -> bool {
+        self.ext_booleans()
+            .any(|b| b.name_lossy() == name && b.supported())
+            || self
+                .ext_numerics()
+                .any(|n| n.name_lossy() == name && n.value() >= 0)
+            || self
+                .ext_strings()
+                .any(|s| s.name_lossy() == name && s.value_bytes().is_some())
+    }
+
+    /// Returns the description of the terminal, the last field of the names of the entry, with
+    /// invalid UTF-8 sequences replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert_eq!(term.name(), "DEC VT100 (w/advanced video)");
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn name(&self) -> Cow<'_, str> {
+        self.name_and_aliases().0.to_string_lossy()
+    }
+
+    /// Returns the names of the terminal, all fields of the names of the entry but the last
+    /// one, with invalid UTF-8 sequences replaced. The first one is the primary name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert_eq!(term.aliases(), ["vt100", "vt100-am"]);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn aliases(&self) -> Vec<Cow<'_, str>> {
+        self.name_and_aliases()
+            .1
+            .into_iter()
+            .map(CStr::to_string_lossy)
+            .collect()
+    }
+
+    /// Returns whether name is the primary name or one of the aliases of the terminal.
+    /// Terminal names are compared case-sensitively. An entry with a single name field has
+    /// that field as its name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert!(term.matches_name("vt100"));
+    /// assert!(term.matches_name("vt100-am"));
+    /// assert!(!term.matches_name("VT100"));
+    /// assert!(!term.matches_name("DEC VT100 (w/advanced video)"));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn matches_name(&self, name: &str) -> bool {
+        let (description, aliases) = self.name_and_aliases();
+        if aliases.is_empty() {
+            description.to_bytes() == name.as_bytes()
+        } else {
+            aliases
+                .iter()
+                .any(|alias| alias.to_bytes() == name.as_bytes())
+        }
+    }
+
+    /// Returns whether the terminal can't do more than print text and move to the next line,
+    /// so no control sequences should be sent: it is named `dumb`, as set in `TERM` for
+    /// consoles without terminal emulation, or it has the generic type flag (`gn`) of entries
+    /// like `unknown`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// assert!(Term::from_term_name("dumb")?.is_dumb());
+    /// assert!(!Term::from_term_name("vt100")?.is_dumb());
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn is_dumb(&self) -> bool {
+        self.matches_name("dumb") || self.has_boolean(BooleanCap::GenericType)
+    }
+
+    /// Returns whether the terminal is a printing terminal (`hc`), on which output can't be
+    /// erased or overwritten.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::testing::MockTerm;
+    /// use unibilium::Term;
+    ///
+    /// assert!(!Term::from_term_name("vt100")?.is_hardcopy());
+    /// assert!(MockTerm::new("printer").bool("hc", true).build()?.is_hardcopy());
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn is_hardcopy(&self) -> bool {
+        self.has_boolean(BooleanCap::HardCopy)
+    }
+
+    /// Returns the number of columns of the terminal (`cols`), or None if it isn't known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert_eq!(term.columns(), Some(80));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn columns(&self) -> Option<u16> {
+        self.numeric_value(NumericCap::Columns)
+            .and_then(|value| u16::try_from(value).ok())
+    }
+
+    /// Returns the number of lines of the terminal (`lines`), or None if it isn't known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert_eq!(term.lines(), Some(24));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn lines(&self) -> Option<u16> {
+        self.numeric_value(NumericCap::Lines)
+            .and_then(|value| u16::try_from(value).ok())
+    }
+
+    /// Returns the number of colors the terminal supports (`colors`), or None if it doesn't
+    /// support colors. Direct color entries store 2^24 colors or more, which needs the 32-bit
+    /// terminfo format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("xterm-256color")?;
+    /// assert_eq!(term.max_colors(), Some(256));
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn max_colors(&self) -> Option<u32> {
+        self.numeric_value(NumericCap::MaxColors)
+            .map(|value| value as u32)
+    }
+
+    /// Returns the number of color pairs the terminal supports (`pairs`), or None if it doesn't
+    /// support colors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert_eq!(term.max_pairs(), None);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn max_pairs(&self) -> Option<u32> {
+        self.numeric_value(NumericCap::MaxPairs)
+            .map(|value| value as u32)
+    }
+
+    /// Returns the value of the string capability as a C string, or None if the terminal
+    /// doesn't define it. It is intended for internal use.
+    pub(crate) fn string_cstr(&self, cap: StringCap) -> Option<&CStr> {
+        self.inner.string(cap)
+    }
+
+    /// Returns the value of the numeric capability, or None if the terminal doesn't define it.
+    /// It is intended for internal use.
+    pub(crate) fn numeric_value(&self, cap: NumericCap) -> Option<i32> {
+        let value = self.inner.numeric(cap);
+        if value >= 0 {
+            Some(value)
+        } else {
+            None
         }
-        all
     }
 
-    /// Returns the wrapped pointer to the C library structure. It is intended for internal use
-    /// where the lower level structure needs to be passed.
-    pub(crate) fn unibi_term(&self) -> *mut unibi_term {
-        self.term
+    /// Returns the value of the numeric capability as stored, -1 if it is absent and -2 if it is
+    /// cancelled. It is intended for internal use.
+    pub(crate) fn numeric_raw(&self, cap: NumericCap) -> i32 {
+        self.inner.numeric(cap)
+    }
+
+    /// Returns the index of the extended capability with the given name, given the count and a
+    /// function returning the names of the capabilities of one kind.
+    fn ext_index<'a>(
+        &'a self,
+        count: u64,
+        get_name: impl Fn(&'a Inner, u64) -> Option<&'a CStr>,
+        name: &str,
+    ) -> Option<u64> {
+        (0..count).find(|index| {
+            get_name(&self.inner, *index)
+                .is_some_and(|ext_name| ext_name.to_bytes() == name.as_bytes())
+        })
+    }
+
+    /// Returns whether the extended boolean capability with the given name is set. It is
+    /// intended for internal use.
+    pub(crate) fn ext_boolean_value(&self, name: &str) -> bool {
+        let count = self.inner.ext_boolean_count();
+        self.ext_index(count, <Inner as Backend>::ext_boolean_name, name)
+            .is_some_and(|index| self.inner.ext_boolean(index))
+    }
+
+    /// Returns the value of the extended numeric capability with the given name, or None if the
+    /// terminal doesn't define it. It is intended for internal use.
+    pub(crate) fn ext_numeric_value(&self, name: &str) -> Option<i32> {
+        let count = self.inner.ext_numeric_count();
+        let index = self.ext_index(count, <Inner as Backend>::ext_numeric_name, name)?;
+        let value = self.inner.ext_numeric(index);
+        if value >= 0 {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value of the extended string capability with the given name as a C string,
+    /// or None if the terminal doesn't define it. It is intended for internal use.
+    pub(crate) fn ext_string_cstr(&self, name: &str) -> Option<&CStr> {
+        let count = self.inner.ext_string_count();
+        let index = self.ext_index(count, <Inner as Backend>::ext_string_name, name)?;
+        self.inner.ext_string(index)
+    }
+
+    /// Returns the name and the aliases of the terminal. It is intended for internal use.
+    pub(crate) fn name_and_aliases(&self) -> (&CStr, Vec<&CStr>) {
+        (self.inner.name(), self.inner.aliases())
+    }
+
+    /// Returns the terminal in the compiled terminfo format, or None if it can't be represented
+    /// in that format. It is intended for internal use.
+    pub(crate) fn dump(&self) -> Option<Vec<u8>> {
+        self.inner.dump()
+    }
+
+    /// Returns the backend the capability views read from. It is intended for internal use.
+    pub(crate) fn backend(&self) -> &dyn Backend {
+        &self.inner
     }
 }
 
-impl Drop for Term {
-    /// Calls the destructor for the low level C structure. Prevents leaks.
-    fn drop(&mut self) {
+impl PartialEq for Term {
+    fn eq(&self, other: &Term) -> bool {
+        if ptr::eq(self, other) {
+            return true;
+        }
+        match (self.dump(), other.dump()) {
+            (Some(a), Some(b)) => a == b,
+            // Terminals too big for the compiled format are compared by their values.
+            _ => {
+                self.name_and_aliases() == other.name_and_aliases() && self.cache() == other.cache()
+            }
+        }
+    }
+}
+
+impl Eq for Term {}
+
+impl fmt::Debug for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cache = self.cache();
+        f.debug_struct("Term")
+            .field("name", &self.name())
+            .field("aliases", &self.aliases())
+            .field("booleans", &cache.booleans.len())
+            .field("numerics", &cache.numerics.len())
+            .field("strings", &cache.strings.len())
+            .field("ext_booleans", &cache.ext_booleans.len())
+            .field("ext_numerics", &cache.ext_numerics.len())
+            .field("ext_strings", &cache.ext_strings.len())
+            .finish()
+    }
+}
+
+impl Hash for Term {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.dump().hash(state);
+    }
+}
+
+/// The terminals loaded by cached, by name.
+static CACHE: Mutex<Option<HashMap<String, SharedTerm>>> = Mutex::new(None);
+
+/// Returns the terminal with the given name like Term::from_term_name, but loads each name only
+/// once per process and shares the result. Failed lookups aren't cached. Use
+/// Term::from_term_name to bypass the cache and clear_cache to reload terminals.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use unibilium::shared::SharedTerm;
+/// use unibilium::term;
+///
+/// let first = term::cached("vt100")?;
+/// let second = term::cached("vt100")?;
+/// assert!(SharedTerm::ptr_eq(&first, &second));
+///
+/// term::clear_cache();
+/// let third = term::cached("vt100")?;
+/// assert!(!SharedTerm::ptr_eq(&first, &third));
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns the same errors as Term::from_term_name.
+pub fn cached(name: &str) -> Result<SharedTerm, TermError> {
+    // Entries from overridden search directories must not leak into other threads.
+    if crate::path::is_overridden() {
+        return Ok(Term::from_term_name(name)?.into_shared());
+    }
+    let mut cache = CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+    let terms = cache.get_or_insert_with(HashMap::new);
+    if let Some(term) = terms.get(name) {
+        return Ok(term.clone());
+    }
+    let term = Term::from_term_name(name)?.into_shared();
+    terms.insert(name.to_owned(), term.clone());
+    Ok(term)
+}
+
+/// Removes all terminals from the cache of cached. Handles returned before stay valid.
+pub fn clear_cache() {
+    let mut cache = CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+    *cache = None;
+}
+
+#[cfg(all(test, not(any(feature = "pure-rust", target_family = "wasm"))))]
+mod tests {
+    use super::*;
+
+    /// Returns a terminal with a string too long for the compiled format, so that dump fails.
+    fn undumpable(value: u8) -> Term {
+        // unibilium doesn't copy strings set with unibi_set_str, so the value is leaked.
+        let value = CString::new(vec![value; 0x8000]).unwrap().into_raw();
         unsafe {
-            unibilium_sys::unibi_destroy(self.term);
+            let term = unibilium_sys::unibi_dummy();
+            unibilium_sys::unibi_set_str(term, StringCap::Bell.to_unibi(), value);
+            Term::from_raw(term)
         }
     }
+
+    #[test]
+    fn eq_compares_values_if_dump_fails() {
+        let term = undumpable(b'x');
+        let clone = undumpable(b'x');
+        assert_eq!(term.dump(), None);
+        assert_eq!(term, clone);
+        assert_ne!(term, undumpable(b'y'));
+    }
 }
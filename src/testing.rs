@@ -0,0 +1,477 @@
+//! Helpers for testing code which uses terminals, without depending on the terminfo database
+//! of the host.
+
+use crate::cache::CachedTerm;
+use crate::cap::{BooleanCap, NumericCap, StringCap};
+use crate::error::TermError;
+use crate::path;
+use crate::string::{self, DisplayEscaped, Escape};
+use crate::term::Term;
+use std::env;
+use std::ffi::CString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Builds a real Term with the given capabilities, for deterministic tests.
+///
+/// Capabilities are given by their short or long name. Names which aren't standard
+/// capabilities add extended capabilities.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use unibilium::format::Param;
+/// use unibilium::testing::MockTerm;
+/// use unibilium::{BooleanCap, StringCap};
+///
+/// let term = MockTerm::new("fake-term")
+///     .bool("am", true)
+///     .num("colors", 256)
+///     .string_source("cup", "\\E[%i%p1%d;%p2%dH")
+///     .string("cuu1", "\x1b[A")
+///     .bool("Tc", true)
+///     .build()?;
+/// assert!(term.has_boolean(BooleanCap::AutoRightMargin));
+/// assert_eq!(term.max_colors(), Some(256));
+/// assert_eq!(
+///     term.expand(StringCap::CursorAddress, &[Param::num(1), Param::num(2)]),
+///     Some(b"\x1b[2;3H".to_vec())
+/// );
+/// assert!(term.has_string(StringCap::CursorUp));
+/// assert!(term.has_ext("Tc"));
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockTerm {
+    name: String,
+    caps: CachedTerm,
+}
+
+impl MockTerm {
+    /// Starts a terminal with the given name and no capabilities.
+    pub fn new(name: &str) -> Self {
+        MockTerm {
+            name: name.to_owned(),
+            caps: CachedTerm::default(),
+        }
+    }
+
+    /// Sets or unsets the boolean capability.
+    pub fn bool(mut self, name: &str, value: bool) -> Self {
+        match BooleanCap::from_name(name) {
+            Some(cap) if value => {
+                self.caps.booleans.insert(cap);
+            }
+            Some(cap) => {
+                self.caps.booleans.remove(&cap);
+            }
+            None if value => {
+                self.caps.ext_booleans.insert(name.to_owned());
+            }
+            None => {
+                self.caps.ext_booleans.remove(name);
+            }
+        }
+        self
+    }
+
+    /// Defines the numeric capability. A negative value removes it.
+    pub fn num(mut self, name: &str, value: i32) -> Self {
+        match NumericCap::from_name(name) {
+            Some(cap) if value >= 0 => {
+                self.caps.numerics.insert(cap, value);
+            }
+            Some(cap) => {
+                self.caps.numerics.remove(&cap);
+            }
+            None if value >= 0 => {
+                self.caps.ext_numerics.insert(name.to_owned(), value);
+            }
+            None => {
+                self.caps.ext_numerics.remove(name);
+            }
+        }
+        self
+    }
+
+    /// Defines the string capability. The value is taken as is, so escapes like `\E` aren't
+    /// interpreted, see string_source for those. Values containing NUL are left out, since
+    /// unibilium can't represent them.
+    pub fn string<V: AsRef<[u8]>>(mut self, name: &str, value: V) -> Self {
+        let value = value.as_ref().to_vec();
+        match StringCap::from_name(name) {
+            Some(cap) => {
+                self.caps.strings.insert(cap, value);
+            }
+            None => {
+                self.caps.ext_strings.insert(name.to_owned(), value);
+            }
+        }
+        self
+    }
+
+    /// Defines the string capability from a value written in the notation of terminfo source
+    /// files, like `\E[%i%p1%d;%p2%dH`. See string::unescape for the escapes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value contains an incomplete, unknown or out of range escape.
+    pub fn string_source(self, name: &str, value: &str) -> Self {
+        match string::unescape(value) {
+            Ok(value) => self.string(name, value),
+            Err(err) => panic!("Invalid value for {}: {}", name, err),
+        }
+    }
+
+    /// Creates the Term.
+    ///
+    /// # Errors
+    ///
+    /// Returns TermError::InvalidName if the name contains NUL, or TermError::Io if unibilium
+    /// can't create the terminal.
+    pub fn build(&self) -> Result<Term, TermError> {
+        let name = CString::new(self.name.as_str()).map_err(|err| TermError::InvalidName {
+            name: self.name.clone(),
+            position: err.nul_position(),
+            source: err,
+        })?;
+        self.caps.to_term(&name, &[&name])
+    }
+}
+
+/// Counts the directories created by with_empty_database, to keep their names unique.
+static EMPTY_DIRS: AtomicUsize = AtomicUsize::new(0);
+
+/// Removes the directory when dropped.
+struct EmptyDir(PathBuf);
+
+impl Drop for EmptyDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir(&self.0);
+    }
+}
+
+/// Runs f with the terminfo database replaced by an empty temporary directory, so loading any
+/// entry fails with TermError::NotFound. This tests the handling of missing entries without
+/// changing environment variables, which other tests running in parallel would see.
+///
+/// Only loading on the current thread is affected. term::cached bypasses its cache while the
+/// database is replaced.
+///
+/// # Examples
+///
+/// ```
+/// use unibilium::error::TermError;
+/// use unibilium::{term, testing, Term};
+///
+/// testing::with_empty_database(|| {
+///     match Term::from_term_name("vt100") {
+///         Err(TermError::NotFound { searched, .. }) => assert!(!searched.is_empty()),
+///         _ => panic!("expected NotFound"),
+///     }
+///     assert!(term::cached("vt100").is_err());
+/// });
+/// assert!(Term::from_term_name("vt100").is_ok());
+/// ```
+///
+/// # Panics
+///
+/// Panics if the temporary directory can't be created.
+pub fn with_empty_database<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let name = format!(
+        "unibilium-empty-{}-{}",
+        process::id(),
+        EMPTY_DIRS.fetch_add(1, Ordering::Relaxed)
+    );
+    let dir = EmptyDir(env::temp_dir().join(name));
+    fs::create_dir_all(&dir.0)
+        .unwrap_or_else(|err| panic!("Failed to create {}: {}", dir.0.display(), err));
+    let _guard = path::override_search_dirs(vec![dir.0.clone()]);
+    f()
+}
+
+/// Environment variable which makes assert_snapshot write the snapshot instead of comparing it.
+pub const UPDATE_SNAPSHOTS_VAR: &str = "UNIBILIUM_UPDATE_SNAPSHOTS";
+
+/// Returns the capabilities of the terminal in a canonical text form, one capability per line
+/// in the syntax of terminfo source files, like `am`, `cols#80` or `cup=\E[%i%p1%d;%p2%dH`.
+/// Standard capabilities come first and each kind is sorted by name, so the form doesn't depend
+/// on the order of the entry.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use unibilium::testing::{self, MockTerm};
+///
+/// let term = MockTerm::new("fake-term")
+///     .string("cuu1", "\x1b[A")
+///     .num("cols", 80)
+///     .bool("am", true)
+///     .build()?;
+/// assert_eq!(testing::snapshot(&term), "am\ncols#80\ncuu1=\\E[A\n");
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+pub fn snapshot(term: &Term) -> String {
+    let cache = term.cache();
+    let mut booleans: Vec<&str> = cache.booleans.iter().map(|cap| cap.short_name()).collect();
+    let mut numerics: Vec<(&str, i32)> = cache
+        .numerics
+        .iter()
+        .map(|(cap, value)| (cap.short_name(), *value))
+        .collect();
+    let mut strings: Vec<(&str, &[u8])> = cache
+        .strings
+        .iter()
+        .map(|(cap, value)| (cap.short_name(), value.as_slice()))
+        .collect();
+    booleans.sort_unstable();
+    numerics.sort_unstable();
+    strings.sort_unstable();
+    let mut ext_booleans: Vec<&str> = cache.ext_booleans.iter().map(String::as_str).collect();
+    let mut ext_numerics: Vec<(&str, i32)> = cache
+        .ext_numerics
+        .iter()
+        .map(|(name, value)| (name.as_str(), *value))
+        .collect();
+    let mut ext_strings: Vec<(&str, &[u8])> = cache
+        .ext_strings
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_slice()))
+        .collect();
+    ext_booleans.sort_unstable();
+    ext_numerics.sort_unstable();
+    ext_strings.sort_unstable();
+    let mut out = String::new();
+    for (booleans, numerics, strings) in [
+        (booleans, numerics, strings),
+        (ext_booleans, ext_numerics, ext_strings),
+    ]
+    .iter()
+    {
+        for name in booleans {
+            out.push_str(&format!("{}\n", name));
+        }
+        for (name, value) in numerics {
+            out.push_str(&format!("{}#{}\n", name, value));
+        }
+        for (name, value) in strings {
+            let value = DisplayEscaped::new(value, Escape::Terminfo);
+            out.push_str(&format!("{}={}\n", name, value));
+        }
+    }
+    out
+}
+
+/// Compares the snapshot of the terminal with the one stored in the file at path, and panics
+/// with the differing lines if they don't match. If the environment variable named by
+/// UPDATE_SNAPSHOTS_VAR is set, the file is written instead, creating its directory if needed.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::fs;
+/// use unibilium::testing::{self, MockTerm};
+///
+/// let term = MockTerm::new("fake-term").num("colors", 8).build()?;
+/// let name = format!("unibilium-assert-snapshot-{}.snap", std::process::id());
+/// let path = std::env::temp_dir().join(name);
+/// fs::write(&path, testing::snapshot(&term))?;
+/// testing::assert_snapshot(&term, &path);
+/// fs::remove_file(&path)?;
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+///
+/// A terminal which differs from the snapshot panics, showing the lines which differ:
+///
+/// ```should_panic
+/// # use std::error::Error;
+/// # use std::path::PathBuf;
+/// #
+/// # struct RemoveOnDrop(PathBuf);
+/// #
+/// # impl Drop for RemoveOnDrop {
+/// #     fn drop(&mut self) {
+/// #         let _ = std::fs::remove_file(&self.0);
+/// #     }
+/// # }
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::fs;
+/// use unibilium::testing::{self, MockTerm};
+///
+/// let term = MockTerm::new("fake-term").num("colors", 8).build()?;
+/// let name = format!("unibilium-assert-snapshot-{}.snap", std::process::id());
+/// let path = std::env::temp_dir().join(name);
+/// # let _remove = RemoveOnDrop(path.clone());
+/// fs::write(&path, testing::snapshot(&term))?;
+/// let other = MockTerm::new("fake-term").num("colors", 256).build()?;
+/// testing::assert_snapshot(&other, &path);
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// Panics if the snapshots differ, or if the file can't be read or written.
+pub fn assert_snapshot<P: AsRef<Path>>(term: &Term, path: P) {
+    let path = path.as_ref();
+    let actual = snapshot(term);
+    if env::var_os(UPDATE_SNAPSHOTS_VAR).is_some() {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .unwrap_or_else(|err| panic!("Failed to create {}: {}", dir.display(), err));
+        }
+        fs::write(path, actual)
+            .unwrap_or_else(|err| panic!("Failed to write {}: {}", path.display(), err));
+        return;
+    }
+    let expected = fs::read_to_string(path).unwrap_or_else(|err| {
+        panic!(
+            "Failed to read snapshot {}: {}; set {} to create it",
+            path.display(),
+            err,
+            UPDATE_SNAPSHOTS_VAR
+        )
+    });
+    if expected != actual {
+        panic!(
+            "Snapshot {} doesn't match, set {} to update it:\n{}",
+            path.display(),
+            UPDATE_SNAPSHOTS_VAR,
+            line_diff(&expected, &actual)
+        );
+    }
+}
+
+/// Number of unchanged lines line_diff shows around each change.
+const DIFF_CONTEXT: usize = 2;
+
+/// Returns the lines of expected and actual in order, with the removed lines prefixed with `-`,
+/// the added lines with `+` and the unchanged lines with a space. Unchanged lines further than
+/// DIFF_CONTEXT lines from a change are left out and marked with `...`.
+fn line_diff(expected: &str, actual: &str) -> String {
+    let a: Vec<&str> = expected.lines().collect();
+    let b: Vec<&str> = actual.lines().collect();
+    // lcs[i][j] is the length of the longest common subsequence of a[i..] and b[j..].
+    let mut lcs = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let mut lines = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            lines.push((' ', a[i]));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(('-', a[i]));
+            i += 1;
+        } else {
+            lines.push(('+', b[j]));
+            j += 1;
+        }
+    }
+    let changes: Vec<usize> = (0..lines.len()).filter(|&k| lines[k].0 != ' ').collect();
+    let mut out = String::new();
+    let mut skipped = false;
+    for (k, (tag, line)) in lines.iter().enumerate() {
+        let near = changes
+            .iter()
+            .any(|&c| c <= k + DIFF_CONTEXT && k <= c + DIFF_CONTEXT);
+        if !near {
+            skipped = true;
+            continue;
+        }
+        if skipped {
+            out.push_str("...\n");
+            skipped = false;
+        }
+        out.push_str(&format!("{}{}\n", tag, line));
+    }
+    if skipped {
+        out.push_str("...\n");
+    }
+    out
+}
+
+/// Asserts that the terminal matches the snapshot at a path relative to the directory of the
+/// manifest of the calling crate, see testing::assert_snapshot.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use unibilium::{assert_term_snapshot, Term};
+///
+/// let term = Term::from_term_name("xterm")?;
+/// assert_term_snapshot!(term, "fixtures/xterm.snap");
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! assert_term_snapshot {
+    ($term:expr, $path:expr) => {
+        $crate::testing::assert_snapshot(
+            &$term,
+            ::std::path::Path::new(::std::env!("CARGO_MANIFEST_DIR")).join($path),
+        )
+    };
+}
+
+#[doc(inline)]
+pub use crate::assert_term_snapshot;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_diff_keeps_the_order_of_lines() {
+        assert_eq!(line_diff("am\nbce\n", "bce\nam\n"), "-am\n bce\n+am\n");
+    }
+
+    #[test]
+    fn line_diff_reports_duplicated_lines() {
+        let diff = line_diff("am\nbce\n", "am\nbce\nbce\n");
+        assert_eq!(diff, " am\n bce\n+bce\n");
+    }
+
+    #[test]
+    fn line_diff_leaves_out_distant_lines() {
+        let expected = "a\nb\nc\nd\ne\nf\ng\nh\n";
+        let actual = "a\nb\nc\nd\nE\nf\ng\nh\n";
+        let diff = line_diff(expected, actual);
+        assert_eq!(diff, "...\n c\n d\n-e\n+E\n f\n g\n...\n");
+    }
+}
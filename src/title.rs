@@ -0,0 +1,63 @@
+//! Helpers for setting the window title.
+
+use crate::cap::{BooleanCap, StringCap};
+use crate::format::Param;
+use crate::term::Term;
+
+impl Term {
+    /// Returns the sequence setting the window title, or None if the terminal can't set it.
+    ///
+    /// The title is written between the `TS` extended capability, or `tsl` when the terminal
+    /// has a status line (`hs`), and `fsl`. Terminals with the `XT` extended capability use
+    /// the xterm OSC 2 sequence otherwise. Control characters are removed from the title, since
+    /// they could end the sequence early.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use unibilium::Term;
+    ///
+    /// let term = Term::from_term_name("xterm-256color")?;
+    /// assert_eq!(
+    ///     term.set_title_sequence("vim\x07 README"),
+    ///     Some(b"\x1b]2;vim README\x07".to_vec())
+    /// );
+    ///
+    /// let term = Term::from_term_name("vt100")?;
+    /// assert_eq!(term.set_title_sequence("vim"), None);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn set_title_sequence(&self, title: &str) -> Option<Vec<u8>> {
+        let start = match self.expand_ext("TS", &[]) {
+            Some(ts) => Some(ts),
+            None if self.has_boolean(BooleanCap::HasStatusLine) => {
+                self.expand(StringCap::ToStatusLine, &[Param::num(0)])
+            }
+            None => None,
+        };
+        let (mut out, end) = match start {
+            Some(start) => (
+                start,
+                self.expand(StringCap::FromStatusLine, &[])
+                    .unwrap_or_else(|| b"\x07".to_vec()),
+            ),
+            None if self.ext_boolean_value("XT") => (b"\x1b]2;".to_vec(), b"\x07".to_vec()),
+            None => return None,
+        };
+        let title: std::string::String = title.chars().filter(|c| !c.is_control()).collect();
+        out.extend(title.as_bytes());
+        out.extend(end);
+        Some(out)
+    }
+
+    /// Returns the sequence disabling the status line (`dsl`), which resets the title on
+    /// terminals using it for the window title, or None if the terminal doesn't define it.
+    pub fn disable_status_line(&self) -> Option<Vec<u8>> {
+        self.expand(StringCap::DisStatusLine, &[])
+    }
+}
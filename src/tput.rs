@@ -0,0 +1,228 @@
+//! The equivalent of the tput command, see term::tput.
+
+use crate::cap::{BooleanCap, NumericCap, StringCap};
+use crate::format::{self, Param, MAX_PARAMS};
+use crate::param::{self, Conversion, Op, Printf};
+use crate::term::Term;
+use std::error::Error;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::io;
+
+/// An error of term::tput, carrying the exit status tput has in the same situation.
+#[derive(Debug)]
+pub enum TputError {
+    /// The boolean capability isn't set or the string capability isn't defined. tput exits
+    /// with 1.
+    Absent,
+    /// The parameter at the index can't be converted to the type the capability expects, or
+    /// there are more than MAX_PARAMS parameters. tput exits with 2.
+    InvalidParam(usize),
+    /// No capability has the name. tput exits with 4.
+    UnknownCapability(String),
+    /// Reading the file named by the init or reset capabilities failed. tput exits with 4.
+    Io(io::Error),
+}
+
+impl TputError {
+    /// Returns the exit status of tput for the error.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            TputError::Absent => 1,
+            TputError::InvalidParam(_) => 2,
+            TputError::UnknownCapability(_) | TputError::Io(_) => 4,
+        }
+    }
+}
+
+impl Error for TputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TputError::Io(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TputError::Absent => write!(f, "capability is absent"),
+            TputError::InvalidParam(index) => write!(f, "invalid parameter {}", index + 1),
+            TputError::UnknownCapability(ref name) => write!(f, "unknown capability '{}'", name),
+            TputError::Io(ref err) => write!(f, "failed to read init file: {}", err),
+        }
+    }
+}
+
+impl From<io::Error> for TputError {
+    fn from(err: io::Error) -> Self {
+        TputError::Io(err)
+    }
+}
+
+/// Marks the parameters the operations pop as strings, with `%s` or `%l`, right after pushing
+/// them. This is the analysis tput does to convert its arguments.
+fn mark_string_params(ops: &[Op], strings: &mut [bool; MAX_PARAMS], last: &mut Option<u8>) {
+    for op in ops {
+        match op {
+            Op::Param(n) => {
+                *last = Some(*n);
+                continue;
+            }
+            Op::StrLen
+            | Op::Printf(Printf {
+                conversion: Conversion::String,
+                ..
+            }) => {
+                if let Some(n) = *last {
+                    strings[usize::from(n) - 1] = true;
+                }
+            }
+            Op::Conditional(conditional) => {
+                for branch in conditional.branches.iter() {
+                    mark_string_params(&branch.condition, strings, last);
+                    mark_string_params(&branch.then, strings, last);
+                }
+                if let Some(ref otherwise) = conditional.otherwise {
+                    mark_string_params(otherwise, strings, last);
+                }
+            }
+            _ => {}
+        }
+        *last = None;
+    }
+}
+
+/// Converts the parameters to the types the parameterized string expects, storing the strings
+/// created from numbers in storage.
+fn coerce_params<'a>(
+    fmt: &CStr,
+    params: &[Param<'a>],
+    storage: &'a mut Vec<CString>,
+) -> Result<Vec<Param<'a>>, TputError> {
+    if params.len() > MAX_PARAMS {
+        return Err(TputError::InvalidParam(MAX_PARAMS));
+    }
+    let mut strings = [false; MAX_PARAMS];
+    if let Ok(program) = param::parse(fmt.to_bytes()) {
+        mark_string_params(program.ops(), &mut strings, &mut None);
+    }
+    for (i, param) in params.iter().enumerate() {
+        if let Param::Num(n) = param {
+            if strings[i] {
+                storage.push(CString::new(n.to_string()).expect("Number contains NUL"));
+            }
+        }
+    }
+    let storage: &'a [CString] = storage;
+    let mut created = storage.iter();
+    let mut coerced = Vec::with_capacity(params.len());
+    for (i, param) in params.iter().enumerate() {
+        coerced.push(match *param {
+            Param::Num(_) if strings[i] => Param::Str(created.next().expect("Missing string")),
+            Param::Str(s) if !strings[i] => s
+                .to_str()
+                .ok()
+                .and_then(|s| s.trim().parse().ok())
+                .map(Param::Num)
+                .ok_or(TputError::InvalidParam(i))?,
+            param => param,
+        });
+    }
+    Ok(coerced)
+}
+
+/// Expands the parameterized string after converting the parameters.
+fn expand(fmt: &CStr, params: &[Param]) -> Result<Vec<u8>, TputError> {
+    let mut storage = vec![];
+    let params = coerce_params(fmt, params, &mut storage)?;
+    let mut out = vec![];
+    format::format_into(fmt, &params, &mut out);
+    Ok(out)
+}
+
+/// Returns what the tput command writes for the capability with the given short or long name,
+/// or the error matching its exit status.
+///
+/// * Booleans write nothing, and return TputError::Absent when not set.
+/// * Numerics write the value and a newline, `-1` when the terminal doesn't define them.
+/// * Strings write the expanded value, and return TputError::Absent when not defined.
+///   Parameters are converted like tput converts its arguments: numbers passed where the
+///   capability expects a string become decimal strings, and strings holding numbers become
+///   numbers. Padding directives are dropped.
+/// * `longname` writes the description of the terminal, and `init` and `reset` write
+///   Term::init_sequences and Term::reset_sequences.
+///
+/// Extended capabilities are looked up by their names.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::ffi::CString;
+/// use unibilium::format::Param;
+/// use unibilium::term::{self, TputError};
+/// use unibilium::Term;
+///
+/// let term = Term::from_term_name("xterm-256color")?;
+/// assert_eq!(term::tput(&term, "setaf", &[Param::num(1)])?, b"\x1b[31m");
+/// let two = CString::new("2")?;
+/// assert_eq!(term::tput(&term, "setaf", &[Param::str(&two)])?, b"\x1b[32m");
+/// assert_eq!(term::tput(&term, "cols", &[])?, b"80\n");
+/// assert_eq!(term::tput(&term, "am", &[])?, b"");
+///
+/// let err = term::tput(&term, "hc", &[]).unwrap_err();
+/// assert_eq!(err.exit_code(), 1);
+/// let err = term::tput(&term, "nonsense", &[]).unwrap_err();
+/// assert!(matches!(err, TputError::UnknownCapability(_)));
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns TputError for absent booleans and strings, unknown names, parameters which can't
+/// be converted, and files of the init and reset capabilities which can't be read.
+pub fn tput(term: &Term, name: &str, params: &[Param]) -> Result<Vec<u8>, TputError> {
+    match name {
+        "longname" => return Ok(term.name().into_owned().into_bytes()),
+        "init" => return Ok(term.init_sequences()?),
+        "reset" => return Ok(term.reset_sequences()?),
+        _ => {}
+    }
+    if let Some(cap) = BooleanCap::from_name(name) {
+        return if term.has_boolean(cap) {
+            Ok(vec![])
+        } else {
+            Err(TputError::Absent)
+        };
+    }
+    if let Some(cap) = NumericCap::from_name(name) {
+        let value = term.numeric_value(cap).unwrap_or(-1);
+        return Ok(format!("{}\n", value).into_bytes());
+    }
+    if let Some(cap) = StringCap::from_name(name) {
+        return match term.string_cstr(cap) {
+            Some(fmt) => expand(fmt, params),
+            None => Err(TputError::Absent),
+        };
+    }
+    if term.ext_booleans().any(|b| b.name_lossy() == name) {
+        return if term.ext_boolean_value(name) {
+            Ok(vec![])
+        } else {
+            Err(TputError::Absent)
+        };
+    }
+    if let Some(value) = term.ext_numeric_value(name) {
+        return Ok(format!("{}\n", value).into_bytes());
+    }
+    if let Some(fmt) = term.ext_string_cstr(name) {
+        return expand(fmt, params);
+    }
+    Err(TputError::UnknownCapability(name.to_owned()))
+}
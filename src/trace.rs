@@ -0,0 +1,18 @@
+//! Events about loading entries and expanding capabilities, emitted with tracing when the
+//! `tracing` feature is enabled and compiled out otherwise.
+
+/// Emits a tracing event at the given level, e.g. `event!(DEBUG, name, "loaded")`. The fields
+/// aren't evaluated without the `tracing` feature.
+#[cfg(feature = "tracing")]
+macro_rules! event {
+    ($level:ident, $($arg:tt)+) => {
+        tracing::event!(tracing::Level::$level, $($arg)+)
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! event {
+    ($level:ident, $($arg:tt)+) => {};
+}
+
+pub(crate) use event;
@@ -0,0 +1,172 @@
+//! Terminal entries loaded and owned by the unibilium C library.
+
+use crate::backend::Backend;
+use crate::cap::{BooleanCap, NumericCap, StringCap};
+use std::convert::TryFrom;
+use std::ffi::CStr;
+use std::io;
+use std::mem;
+use std::os::raw::c_char;
+use std::ptr;
+use unibilium_sys::unibi_term;
+
+/// Owns a unibilium structure, which is destroyed with `unibi_destroy` when dropped.
+pub(crate) struct Unibi {
+    term: *mut unibi_term,
+}
+
+impl Unibi {
+    /// Wraps the result of one of the unibilium constructors, which report failures through
+    /// errno.
+    fn from_result(term: *mut unibi_term) -> io::Result<Unibi> {
+        if term.is_null() {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(Unibi { term })
+        }
+    }
+
+    pub(crate) fn from_env() -> io::Result<Unibi> {
+        Unibi::from_result(unsafe { unibilium_sys::unibi_from_env() })
+    }
+
+    pub(crate) fn from_term(name: &CStr) -> io::Result<Unibi> {
+        Unibi::from_result(unsafe { unibilium_sys::unibi_from_term(name.as_ptr()) })
+    }
+
+    pub(crate) fn from_mem(data: &[u8]) -> io::Result<Unibi> {
+        let term = unsafe {
+            unibilium_sys::unibi_from_mem(data.as_ptr() as *const c_char, data.len() as _)
+        };
+        Unibi::from_result(term)
+    }
+
+    /// # Safety
+    ///
+    /// See Term::from_raw.
+    pub(crate) unsafe fn from_raw(term: *mut unibi_term) -> Unibi {
+        Unibi { term }
+    }
+
+    pub(crate) fn as_ptr(&self) -> *mut unibi_term {
+        self.term
+    }
+
+    pub(crate) fn into_raw(self) -> *mut unibi_term {
+        let term = self.term;
+        mem::forget(self);
+        term
+    }
+}
+
+impl Backend for Unibi {
+    fn name(&self) -> &CStr {
+        let name = unsafe { unibilium_sys::unibi_get_name(self.term) };
+        unsafe { cstr(name) }.unwrap_or_default()
+    }
+
+    fn aliases(&self) -> Vec<&CStr> {
+        let mut aliases = vec![];
+        unsafe {
+            let mut alias = unibilium_sys::unibi_get_aliases(self.term);
+            if !alias.is_null() {
+                while !(*alias).is_null() {
+                    aliases.push(CStr::from_ptr(*alias));
+                    alias = alias.add(1);
+                }
+            }
+        }
+        aliases
+    }
+
+    fn boolean(&self, cap: BooleanCap) -> bool {
+        unsafe { unibilium_sys::unibi_get_bool(self.term, cap.to_unibi()) > 0 }
+    }
+
+    fn numeric(&self, cap: NumericCap) -> i32 {
+        unsafe { unibilium_sys::unibi_get_num(self.term, cap.to_unibi()) }
+    }
+
+    fn string(&self, cap: StringCap) -> Option<&CStr> {
+        unsafe { cstr(unibilium_sys::unibi_get_str(self.term, cap.to_unibi())) }
+    }
+
+    fn ext_boolean_count(&self) -> u64 {
+        unsafe { unibilium_sys::unibi_count_ext_bool(self.term) }
+    }
+
+    fn ext_boolean_name(&self, index: u64) -> Option<&CStr> {
+        unsafe { cstr(unibilium_sys::unibi_get_ext_bool_name(self.term, index)) }
+    }
+
+    fn ext_boolean(&self, index: u64) -> bool {
+        unsafe { unibilium_sys::unibi_get_ext_bool(self.term, index) > 0 }
+    }
+
+    fn ext_numeric_count(&self) -> u64 {
+        unsafe { unibilium_sys::unibi_count_ext_num(self.term) }
+    }
+
+    fn ext_numeric_name(&self, index: u64) -> Option<&CStr> {
+        unsafe { cstr(unibilium_sys::unibi_get_ext_num_name(self.term, index)) }
+    }
+
+    fn ext_numeric(&self, index: u64) -> i32 {
+        unsafe { unibilium_sys::unibi_get_ext_num(self.term, index) }
+    }
+
+    fn ext_string_count(&self) -> u64 {
+        unsafe { unibilium_sys::unibi_count_ext_str(self.term) }
+    }
+
+    fn ext_string_name(&self, index: u64) -> Option<&CStr> {
+        unsafe { cstr(unibilium_sys::unibi_get_ext_str_name(self.term, index)) }
+    }
+
+    fn ext_string(&self, index: u64) -> Option<&CStr> {
+        unsafe { cstr(unibilium_sys::unibi_get_ext_str(self.term, index)) }
+    }
+
+    fn dump(&self) -> Option<Vec<u8>> {
+        let size = unsafe { unibilium_sys::unibi_dump(self.term, ptr::null_mut(), 0) };
+        // A size which doesn't fit into memory signals a value too large for the format.
+        let len = usize::try_from(size)
+            .ok()
+            .filter(|len| *len < isize::MAX as usize)?;
+        let mut data = vec![0u8; len];
+        let written =
+            unsafe { unibilium_sys::unibi_dump(self.term, data.as_mut_ptr() as *mut c_char, size) };
+        if written == size {
+            Some(data)
+        } else {
+            None
+        }
+    }
+}
+
+/// Borrows a string returned by unibilium, which stays valid as long as the structure.
+unsafe fn cstr<'a>(s: *const c_char) -> Option<&'a CStr> {
+    if s.is_null() {
+        None
+    } else {
+        Some(CStr::from_ptr(s))
+    }
+}
+
+// unibi_term is a plain heap allocation owned by the Term, without thread-local state or
+// references to other data, so it can be freed from any thread. The crate never modifies it
+// after one of the unibi_from_* functions has built it: unibi_get_*, unibi_count_ext_* and
+// unibi_dump only read from it, so sharing it between threads is sound. Term::as_ptr hands out
+// a *mut pointer from &self; modifying the structure through it while the Term is shared is
+// the caller's responsibility, as its documentation says.
+unsafe impl Send for Unibi {}
+unsafe impl Sync for Unibi {}
+
+impl Drop for Unibi {
+    /// Calls the destructor for the low level C structure. Prevents leaks.
+    fn drop(&mut self) {
+        unsafe {
+            unibilium_sys::unibi_destroy(self.term);
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! The version of the linked unibilium library and the limits of the terminfo format.
+
+use crate::format::MAX_PARAMS;
+use std::fmt;
+#[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+use unibilium_sys::{unibi_boolean, unibi_numeric, unibi_string};
+
+/// Number of standard boolean capabilities unibilium knows.
+#[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+pub const BOOLEAN_COUNT: usize =
+    (unibi_boolean::unibi_boolean_end_.0 - unibi_boolean::unibi_boolean_begin_.0 - 1) as usize;
+
+/// Number of standard numeric capabilities unibilium knows.
+#[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+pub const NUMERIC_COUNT: usize =
+    (unibi_numeric::unibi_numeric_end_.0 - unibi_numeric::unibi_numeric_begin_.0 - 1) as usize;
+
+/// Number of standard string capabilities unibilium knows.
+#[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+pub const STRING_COUNT: usize =
+    (unibi_string::unibi_string_end_.0 - unibi_string::unibi_string_begin_.0 - 1) as usize;
+
+/// Number of standard boolean capabilities unibilium knows.
+#[cfg(any(feature = "pure-rust", target_family = "wasm"))]
+pub const BOOLEAN_COUNT: usize = crate::cap::BooleanCap::ALL.len();
+
+/// Number of standard numeric capabilities unibilium knows.
+#[cfg(any(feature = "pure-rust", target_family = "wasm"))]
+pub const NUMERIC_COUNT: usize = crate::cap::NumericCap::ALL.len();
+
+/// Number of standard string capabilities unibilium knows.
+#[cfg(any(feature = "pure-rust", target_family = "wasm"))]
+pub const STRING_COUNT: usize = crate::cap::StringCap::ALL.len();
+
+/// Maximum number of parameters of a string capability.
+pub const PARAM_COUNT: usize = MAX_PARAMS;
+
+/// Largest numeric value of the legacy format, which stores numbers in 16 bits.
+pub const MAX_NUMERIC_16BIT: i32 = i16::MAX as i32;
+
+/// Largest numeric value of the extended number format, which stores numbers in 32 bits.
+pub const MAX_NUMERIC_32BIT: i32 = i32::MAX;
+
+/// The version of the unibilium library, as reported by the library at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl Version {
+    /// Returns whether the library reads entries in the extended number format, with 32-bit
+    /// numbers, as written by ncurses 6.1 and later for values beyond MAX_NUMERIC_16BIT. It
+    /// was added in unibilium 2.1.
+    pub fn supports_32bit_numerics(self) -> bool {
+        self >= Version { major: 2, minor: 1 }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// Returns the version of the linked unibilium library. With the pure Rust backend, i.e. with the
+/// `pure-rust` feature or on WebAssembly, it returns 2.1, the version whose file format the Rust
+/// backend reads.
+///
+/// # Examples
+///
+/// ```
+/// use unibilium::version::{self, Version};
+///
+/// let version = unibilium::version();
+/// assert!(version >= Version { major: 2, minor: 0 });
+/// if version.supports_32bit_numerics() {
+///     println!("unibilium {} reads numbers up to {}", version, version::MAX_NUMERIC_32BIT);
+/// }
+/// assert_eq!(version::BOOLEAN_COUNT, unibilium::BooleanCap::ALL.len());
+/// ```
+pub fn version() -> Version {
+    // unibilium encodes the version as major * 1000 + minor.
+    #[cfg(not(any(feature = "pure-rust", target_family = "wasm")))]
+    let version = unsafe { unibilium_sys::unibi_get_version() }.max(0) as u32;
+    // The pure Rust backend reads what unibilium 2.1 reads.
+    #[cfg(any(feature = "pure-rust", target_family = "wasm"))]
+    let version = 2001;
+    Version {
+        major: version / 1000,
+        minor: version % 1000,
+    }
+}
@@ -0,0 +1,263 @@
+//! Windows support: a built-in entry for the Windows console and the VT support of the console.
+//!
+//! Windows has no terminfo database, and TERM is usually unset there. Since Windows 10 the
+//! console interprets the escape sequences of xterm once ENABLE_VIRTUAL_TERMINAL_PROCESSING is
+//! set, and Windows Terminal and other hosts built on ConPTY do so out of the box. Term::from_env
+//! therefore falls back to the built-in `ms-terminal` entry when no entry can be found.
+
+use crate::cap::{BooleanCap, NumericCap, StringCap};
+use crate::entry::Entry;
+use crate::error::TermError;
+use crate::term::Term;
+use std::env;
+use std::ffi::CString;
+use std::os::raw::c_void;
+
+/// Name of the built-in entry, after the one ncurses has for Windows Terminal.
+pub const BUILTIN_NAME: &str = "ms-terminal";
+
+const BOOLEANS: &[&str] = &[
+    "am", "bce", "ccc", "km", "mc5i", "mir", "msgr", "npc", "xenl",
+];
+
+const NUMERICS: &[(&str, i32)] = &[
+    ("cols", 80),
+    ("it", 8),
+    ("lines", 24),
+    ("colors", 256),
+    ("pairs", 65536),
+];
+
+const STRINGS: &[(&str, &str)] = &[
+    ("bel", "\x07"),
+    ("blink", "\x1b[5m"),
+    ("bold", "\x1b[1m"),
+    ("cbt", "\x1b[Z"),
+    ("civis", "\x1b[?25l"),
+    ("clear", "\x1b[H\x1b[2J"),
+    ("cnorm", "\x1b[?12l\x1b[?25h"),
+    ("cr", "\r"),
+    ("csr", "\x1b[%i%p1%d;%p2%dr"),
+    ("cub", "\x1b[%p1%dD"),
+    ("cub1", "\x08"),
+    ("cud", "\x1b[%p1%dB"),
+    ("cud1", "\n"),
+    ("cuf", "\x1b[%p1%dC"),
+    ("cuf1", "\x1b[C"),
+    ("cup", "\x1b[%i%p1%d;%p2%dH"),
+    ("cuu", "\x1b[%p1%dA"),
+    ("cuu1", "\x1b[A"),
+    ("cvvis", "\x1b[?12;25h"),
+    ("dch", "\x1b[%p1%dP"),
+    ("dch1", "\x1b[P"),
+    ("dim", "\x1b[2m"),
+    ("dl", "\x1b[%p1%dM"),
+    ("dl1", "\x1b[M"),
+    ("ech", "\x1b[%p1%dX"),
+    ("ed", "\x1b[J"),
+    ("el", "\x1b[K"),
+    ("el1", "\x1b[1K"),
+    ("home", "\x1b[H"),
+    ("hpa", "\x1b[%i%p1%dG"),
+    ("ht", "\t"),
+    ("hts", "\x1bH"),
+    ("ich", "\x1b[%p1%d@"),
+    ("il", "\x1b[%p1%dL"),
+    ("il1", "\x1b[L"),
+    ("ind", "\n"),
+    ("indn", "\x1b[%p1%dS"),
+    ("invis", "\x1b[8m"),
+    ("kbs", "\x7f"),
+    ("kcbt", "\x1b[Z"),
+    ("kcub1", "\x1bOD"),
+    ("kcud1", "\x1bOB"),
+    ("kcuf1", "\x1bOC"),
+    ("kcuu1", "\x1bOA"),
+    ("kdch1", "\x1b[3~"),
+    ("kend", "\x1bOF"),
+    ("kf1", "\x1bOP"),
+    ("kf2", "\x1bOQ"),
+    ("kf3", "\x1bOR"),
+    ("kf4", "\x1bOS"),
+    ("kf5", "\x1b[15~"),
+    ("kf6", "\x1b[17~"),
+    ("kf7", "\x1b[18~"),
+    ("kf8", "\x1b[19~"),
+    ("kf9", "\x1b[20~"),
+    ("kf10", "\x1b[21~"),
+    ("kf11", "\x1b[23~"),
+    ("kf12", "\x1b[24~"),
+    ("khome", "\x1bOH"),
+    ("kich1", "\x1b[2~"),
+    ("kmous", "\x1b[<"),
+    ("knp", "\x1b[6~"),
+    ("kpp", "\x1b[5~"),
+    ("op", "\x1b[39;49m"),
+    ("rc", "\x1b8"),
+    ("rev", "\x1b[7m"),
+    ("ri", "\x1bM"),
+    ("rin", "\x1b[%p1%dT"),
+    ("ritm", "\x1b[23m"),
+    ("rmacs", "\x1b(B"),
+    ("rmam", "\x1b[?7l"),
+    ("rmcup", "\x1b[?1049l"),
+    ("rmir", "\x1b[4l"),
+    ("rmkx", "\x1b[?1l"),
+    ("rmso", "\x1b[27m"),
+    ("rmul", "\x1b[24m"),
+    ("sc", "\x1b7"),
+    (
+        "setab",
+        "\x1b[%?%p1%{8}%<%t4%p1%d%e%p1%{16}%<%t10%p1%{8}%-%d%e48;5;%p1%d%;m",
+    ),
+    (
+        "setaf",
+        "\x1b[%?%p1%{8}%<%t3%p1%d%e%p1%{16}%<%t9%p1%{8}%-%d%e38;5;%p1%d%;m",
+    ),
+    ("sgr0", "\x1b(B\x1b[m"),
+    ("sitm", "\x1b[3m"),
+    ("smacs", "\x1b(0"),
+    ("smam", "\x1b[?7h"),
+    ("smcup", "\x1b[?1049h"),
+    ("smir", "\x1b[4h"),
+    ("smkx", "\x1b[?1h"),
+    ("smso", "\x1b[7m"),
+    ("smul", "\x1b[4m"),
+    ("tbc", "\x1b[3g"),
+    ("vpa", "\x1b[%i%p1%dd"),
+];
+
+const EXT_BOOLEANS: &[&str] = &["AX", "XT"];
+
+const EXT_STRINGS: &[(&str, &str)] = &[("Se", "\x1b[0 q"), ("Ss", "\x1b[%p1%d q")];
+
+/// Returns the built-in entry for the Windows console, which describes the xterm subset the
+/// console supports with VT processing enabled.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use unibilium::windows;
+///
+/// let term = windows::builtin()?;
+/// assert_eq!(term.name(), windows::BUILTIN_NAME);
+/// assert_eq!(term.max_colors(), Some(256));
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns TermError::Io if the entry can't be created, which doesn't happen for the built-in
+/// capabilities.
+pub fn builtin() -> Result<Term, TermError> {
+    let cstring = |s: &str| CString::new(s).expect("Built-in capability contains NUL");
+    let mut entry = Entry::new(cstring(BUILTIN_NAME), vec![]);
+    for name in BOOLEANS {
+        entry.set_boolean(BooleanCap::from_name(name).expect("Unknown boolean"), true);
+    }
+    for (name, value) in NUMERICS {
+        entry.set_numeric(
+            NumericCap::from_name(name).expect("Unknown numeric"),
+            *value,
+        );
+    }
+    for (name, value) in STRINGS {
+        let cap = StringCap::from_name(name).expect("Unknown string");
+        entry.set_string(cap, cstring(value));
+    }
+    for name in EXT_BOOLEANS {
+        entry.add_ext_boolean(cstring(name), true);
+    }
+    for (name, value) in EXT_STRINGS {
+        entry.add_ext_string(cstring(name), cstring(value));
+    }
+    Term::from_entry(&entry)
+}
+
+/// The VT support of the console the process is attached to, see Term::windows_console_profile.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct ConsoleProfile {
+    /// Whether standard output is a console rather than a file or a pipe.
+    pub console: bool,
+    /// Whether ENABLE_VIRTUAL_TERMINAL_PROCESSING is set on standard output, so the console
+    /// interprets escape sequences.
+    pub vt_output: bool,
+    /// Whether ENABLE_VIRTUAL_TERMINAL_INPUT is set on standard input, so keys arrive as escape
+    /// sequences.
+    pub vt_input: bool,
+    /// Whether DISABLE_NEWLINE_AUTO_RETURN is set on standard output, so a line feed only
+    /// moves down like on other terminals.
+    pub no_auto_return: bool,
+    /// Whether the console is hosted by ConPTY, which is guessed from the variables Windows
+    /// Terminal and Visual Studio Code set. Enabling VT processing succeeds on those hosts.
+    pub conpty: bool,
+}
+
+impl ConsoleProfile {
+    /// Returns whether escape sequences written to standard output take effect, either because
+    /// VT processing is enabled or because output doesn't go to a console at all.
+    pub fn supports_vt(&self) -> bool {
+        self.vt_output || !self.console
+    }
+}
+
+const STD_INPUT_HANDLE: u32 = -10i32 as u32;
+const STD_OUTPUT_HANDLE: u32 = -11i32 as u32;
+const ENABLE_VIRTUAL_TERMINAL_PROCESSING: u32 = 0x0004;
+const DISABLE_NEWLINE_AUTO_RETURN: u32 = 0x0008;
+const ENABLE_VIRTUAL_TERMINAL_INPUT: u32 = 0x0200;
+
+#[link(name = "kernel32")]
+extern "system" {
+    fn GetStdHandle(std_handle: u32) -> *mut c_void;
+    fn GetConsoleMode(handle: *mut c_void, mode: *mut u32) -> i32;
+}
+
+/// Returns the mode of the standard handle, or None if it isn't a console.
+fn console_mode(std_handle: u32) -> Option<u32> {
+    let handle = unsafe { GetStdHandle(std_handle) };
+    // INVALID_HANDLE_VALUE is -1.
+    if handle.is_null() || handle as isize == -1 {
+        return None;
+    }
+    let mut mode = 0;
+    if unsafe { GetConsoleMode(handle, &mut mode) } != 0 {
+        Some(mode)
+    } else {
+        None
+    }
+}
+
+impl Term {
+    /// Returns the VT support of the console, from the console modes of standard output and
+    /// standard input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unibilium::Term;
+    ///
+    /// let profile = Term::windows_console_profile();
+    /// if !profile.supports_vt() {
+    ///     println!("escape sequences would be printed verbatim");
+    /// }
+    /// ```
+    pub fn windows_console_profile() -> ConsoleProfile {
+        let output = console_mode(STD_OUTPUT_HANDLE);
+        let input = console_mode(STD_INPUT_HANDLE);
+        let has = |mode: Option<u32>, flag: u32| mode.is_some_and(|mode| mode & flag != 0);
+        ConsoleProfile {
+            console: output.is_some(),
+            vt_output: has(output, ENABLE_VIRTUAL_TERMINAL_PROCESSING),
+            vt_input: has(input, ENABLE_VIRTUAL_TERMINAL_INPUT),
+            no_auto_return: has(output, DISABLE_NEWLINE_AUTO_RETURN),
+            conpty: env::var_os("WT_SESSION").is_some()
+                || env::var_os("TERM_PROGRAM").is_some_and(|program| program == "vscode"),
+        }
+    }
+}